@@ -0,0 +1,201 @@
+file-menu = File
+edit-menu = Edit
+search-menu = Search
+tools-menu = Tools
+options-menu = Options
+
+new-file = New
+open-file = Open
+save-file = Save
+close-file = Close
+export-csv = Export as CSV
+export-json = Export as JSON
+import-file = Import CSV / JSON
+recent-files = Recent files
+
+undo = Undo
+redo = Redo
+undo-history = Undo history
+undo-history-initial = Initial state
+undo-add-line = Add line { $line }
+undo-add-lines = Add { $count } lines
+undo-delete-line = Delete line { $line } ({ $type })
+undo-delete-lines = Delete { $count } lines
+undo-edit-line = Edit line { $line } ({ $type })
+undo-edit-lines = Edit { $count } lines
+undo-edit-properties = Edit properties
+cut = Cut
+copy = Copy
+paste = Paste
+select-all = Select all
+
+goto-line = Goto line
+jump-to-line = Jump
+goto-mode-line = Line
+goto-mode-frame = Frame
+goto-mode-time = Time
+goto-frame-position = Line { $line }, frame { $frame } of the framebulk
+goto-not-found = No framebulk plays at that point
+goto-split-framebulk = Split the framebulk there
+
+find-replace = Find / replace
+find = Find
+replace = Replace
+replace-all = Replace all
+find-next = Next
+find-previous = Previous
+match-case = Match case
+search-target-line = Line
+search-target-comment = Comment
+search-target-command = Command
+search-target-save = Save
+search-target-frametime = Frametime
+filter-lines = Filter lines
+filter-query = Query
+filter-query-err = Invalid query
+filter-matches = Matching lines
+filter-only-show-matches = Only show matching lines
+filter-select-matches = Select matching lines
+
+no-dupe-framebulk = No duplicate framebulk
+no-comments = No comments
+
+statistics = Statistics
+stats-copy = Copy as text
+stats-total-frames = Total frames
+stats-zero-ms-frames = 0ms frames
+stats-total-time = Total time
+stats-saves = Saves
+stats-console-commands = Console commands
+stats-sections = Sections
+stats-start-section = (start)
+stats-lines = Lines
+stats-strafe-frames = Strafe frames
+stats-autoaction-frames = Autoaction frames
+stats-frametimes = Frametimes
+
+compare = Compare
+compare-old = Old
+compare-new = New
+compare-on-disk = on disk
+compare-swap = Swap
+compare-refresh = Refresh
+compare-tab-closed = The tab got closed
+compare-no-file = The tab isn't saved to a file
+
+merge = Three-way merge
+merge-base = Base
+merge-ours = Ours
+merge-theirs = Theirs
+merge-start = Merge
+merge-unresolved = Unresolved conflicts
+merge-open-result = Open result in new tab
+merge-properties-conflict = Properties were changed differently
+merge-conflict = Conflict
+merge-resolved = (resolved)
+merge-by-field = By field
+merge-ours-then-theirs = Ours then theirs
+merge-theirs-then-ours = Theirs then ours
+
+problems = Problems
+problems-none = No problems found
+problems-errors = Errors
+problems-warnings = Warnings
+problems-line = Line
+lint-jump-with-leave-ground-action = Jump key is held while auto jump or ducktap is enabled
+lint-keys-with-jumpbug = Jump or duck key is held while jumpbug is enabled
+lint-zero-ms-without-frametime = 0ms ducktap is used without the 0ms frametime property
+lint-invalid-frametime = Frametime isn't a positive number
+lint-change-without-target = Change is used before any strafing or target yaw override
+lint-buttons-without-strafing = Strafing buttons are set but no strafing uses them
+lint-empty-console-command = Console command is empty
+quick-fixes = Quick fixes
+quick-fix-all = Fix all
+quick-fix-drop-jump-key = Release jump key
+quick-fix-drop-leave-ground-action = Disable auto jump / ducktap
+quick-fix-drop-jump-duck-keys = Release jump and duck keys
+quick-fix-enable-zero-ms-frametime = Enable 0ms frametime property
+quick-fix-disable-zero-ms = Disable 0ms ducktap
+quick-fix-normalize-frametime = Use default frametime
+quick-fix-remove-console-command = Remove console command
+
+framebulk-menu = Framebulk
+split-framebulk = Split at frame
+merge-framebulks = Merge with following framebulks
+merge-framebulks-tooltip = Merges the following framebulks that only differ in frame count
+
+bulk-edit = Bulk edit
+bulk-edit-selected = { $count } framebulks selected
+bulk-edit-no-framebulks = Select framebulks to edit them at once
+bulk-edit-mixed = (mixed)
+bulk-edit-frametime = Frametime
+bulk-edit-strafe-type = Strafe type
+bulk-edit-strafe-dir = Strafe direction
+bulk-edit-yaw = Yaw
+bulk-edit-pitch = Pitch
+bulk-edit-lgagst = Lgagst
+bulk-edit-keys = Keys
+bulk-edit-append-command = Append command
+bulk-edit-apply = Apply
+bulk-edit-revert = Revert
+
+transform-selection = Transform selection
+transform-selected-lines = { $count } lines selected
+transform-yaw-offset = Yaw offset
+transform-pitch-offset = Pitch offset
+transform-frame-count = Frame count
+transform-scale = Scale
+transform-offset = Offset
+transform-ratio = Ratio
+transform-rounding-nearest = Round
+transform-rounding-down = Round down
+transform-rounding-up = Round up
+transform-preview = Preview
+transform-selection-total = Selection
+transform-script-total = Script
+transform-apply = Apply
+
+open-options-menu = Options menu
+toggle-graphics-editor = Toggle graphics editor
+toggle-simple-view = Toggle simple view
+toggle-timing-gutter = Toggle frame / time gutter
+command-palette = Command palette
+new-line = New line
+
+new-file-title = New file
+
+save-file-question = Would you like to save the modified file?
+recovery-question = Some unsaved files were recovered, would you like to restore them?
+recovery-discard-question = Discard the recovered files? Otherwise they are kept for the next start.
+external-change-title = File changed on disk
+external-change-question = The file was changed by another program while it has unsaved changes.
+reload = Reload
+keep-mine = Keep mine
+show-diff = Show diff
+hide-diff = Hide diff
+
+warning = Warning!
+error = Error
+
+frametime-f32-parse-err = Cannot parse frametime as f32
+setting-default-frametime = Setting frametime to default option
+
+properties = Properties
+demo-name = Demo name
+set-demo-recording = Set demo recording
+save-name = Save name
+save-after-hltas = Save after hltas
+enable-0ms-ducktap = Enable 0ms ducktap
+zero-ms-frametime = 0ms frametime
+enable-shared-nonshared = Enable shared / non-shared rng
+set-hlstrafe-version = Set hlstrafe version
+hlstrafe-version = hlstrafe version
+set-hltas-load-commands = Set hltas load commands
+load-commands = Load commands
+commands = Commands
+shared-rng = Shared rng
+non-shared-rng = Non-shared rng
+
+lines = Lines
+
+text-editor-parse-err = Cannot parse the text as hltas, fix it before switching to the graphics editor
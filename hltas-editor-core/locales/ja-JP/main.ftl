@@ -0,0 +1,201 @@
+file-menu = ファイル
+edit-menu = 編集
+search-menu = 検索
+tools-menu = ツール
+options-menu = オプション
+
+new-file = 新規作成
+open-file = 開く
+save-file = 保存
+close-file = 閉じる
+export-csv = CSVとしてエクスポート
+export-json = JSONとしてエクスポート
+import-file = CSV / JSONをインポート
+recent-files = 最近使用したファイル
+
+undo = 元に戻す
+redo = やり直す
+undo-history = 元に戻す履歴
+undo-history-initial = 初期状態
+undo-add-line = { $line } 行目を追加
+undo-add-lines = { $count } 行を追加
+undo-delete-line = { $line } 行目を削除 ({ $type })
+undo-delete-lines = { $count } 行を削除
+undo-edit-line = { $line } 行目を編集 ({ $type })
+undo-edit-lines = { $count } 行を編集
+undo-edit-properties = プロパティを編集
+cut = 切り取り
+copy = コピー
+paste = 貼り付け
+select-all = すべて選択
+
+goto-line = 行へジャンプ
+jump-to-line = ジャンプ
+goto-mode-line = 行
+goto-mode-frame = フレーム
+goto-mode-time = 時間
+goto-frame-position = { $line } 行目、フレームバルクの { $frame } フレーム目
+goto-not-found = その位置のフレームバルクがありません
+goto-split-framebulk = そこでフレームバルクを分割
+
+find-replace = 検索 / 置換
+find = 検索
+replace = 置換
+replace-all = すべて置換
+find-next = 次へ
+find-previous = 前へ
+match-case = 大文字と小文字を区別する
+search-target-line = 行
+search-target-comment = コメント
+search-target-command = コマンド
+search-target-save = セーブ
+search-target-frametime = フレームタイム
+filter-lines = 行のフィルター
+filter-query = クエリ
+filter-query-err = 無効なクエリ
+filter-matches = 一致する行
+filter-only-show-matches = 一致する行のみ表示
+filter-select-matches = 一致する行を選択
+
+no-dupe-framebulk = 複製のframebulkを一つに合わせる
+no-comments = コメントを消す
+
+statistics = 統計
+stats-copy = テキストとしてコピー
+stats-total-frames = 合計フレーム
+stats-zero-ms-frames = 0ms フレーム
+stats-total-time = 合計時間
+stats-saves = セーブ
+stats-console-commands = コンソールコマンド
+stats-sections = セクション
+stats-start-section = (最初)
+stats-lines = 行
+stats-strafe-frames = ストレイフのフレーム
+stats-autoaction-frames = オートアクションのフレーム
+stats-frametimes = フレームタイム
+
+compare = 比較
+compare-old = 変更前
+compare-new = 変更後
+compare-on-disk = ディスク上
+compare-swap = 入れ替え
+compare-refresh = 更新
+compare-tab-closed = タブが閉じられました
+compare-no-file = タブがファイルに保存されていません
+
+merge = 3方向マージ
+merge-base = ベース
+merge-ours = 自分側
+merge-theirs = 相手側
+merge-start = マージ
+merge-unresolved = 未解決のコンフリクト
+merge-open-result = 結果を新しいタブで開く
+merge-properties-conflict = プロパティが両方で異なる変更をされています
+merge-conflict = コンフリクト
+merge-resolved = (解決済み)
+merge-by-field = フィールドごと
+merge-ours-then-theirs = 自分側の後に相手側
+merge-theirs-then-ours = 相手側の後に自分側
+
+problems = 問題
+problems-none = 問題は見つかりませんでした
+problems-errors = エラー
+problems-warnings = 警告
+problems-line = 行
+lint-jump-with-leave-ground-action = 自動ジャンプまたはダックタップが有効な状態でジャンプキーが押されています
+lint-keys-with-jumpbug = ジャンプバグが有効な状態でジャンプまたはしゃがみキーが押されています
+lint-zero-ms-without-frametime = 0msフレームタイムのプロパティなしで0msダックタップが使われています
+lint-invalid-frametime = フレームタイムが正の数値ではありません
+lint-change-without-target = ストレイフまたはターゲットヨーオーバーライドより前にチェンジが使われています
+lint-buttons-without-strafing = ストレイフボタンが設定されていますが、使用するストレイフがありません
+lint-empty-console-command = コンソールコマンドが空です
+quick-fixes = クイックフィックス
+quick-fix-all = すべて修正
+quick-fix-drop-jump-key = ジャンプキーを離す
+quick-fix-drop-leave-ground-action = 自動ジャンプ / ダックタップを無効化
+quick-fix-drop-jump-duck-keys = ジャンプとしゃがみキーを離す
+quick-fix-enable-zero-ms-frametime = 0msフレームタイムのプロパティを有効化
+quick-fix-disable-zero-ms = 0msダックタップを無効化
+quick-fix-normalize-frametime = デフォルトのフレームタイムを使う
+quick-fix-remove-console-command = コンソールコマンドを削除
+
+framebulk-menu = フレームバルク
+split-framebulk = このフレームで分割
+merge-framebulks = 後のフレームバルクと結合
+merge-framebulks-tooltip = フレーム数だけが違う後のフレームバルクを結合します
+
+bulk-edit = 一括編集
+bulk-edit-selected = { $count } 個のフレームバルクを選択中
+bulk-edit-no-framebulks = まとめて編集するフレームバルクを選択してください
+bulk-edit-mixed = (混在)
+bulk-edit-frametime = フレームタイム
+bulk-edit-strafe-type = ストレイフの種類
+bulk-edit-strafe-dir = ストレイフの方向
+bulk-edit-yaw = ヨー
+bulk-edit-pitch = ピッチ
+bulk-edit-lgagst = Lgagst
+bulk-edit-keys = キー
+bulk-edit-append-command = コマンドを追加
+bulk-edit-apply = 適用
+bulk-edit-revert = 元に戻す
+
+transform-selection = 選択範囲を変換
+transform-selected-lines = { $count } 行を選択中
+transform-yaw-offset = ヨーのオフセット
+transform-pitch-offset = ピッチのオフセット
+transform-frame-count = フレーム数
+transform-scale = 倍率
+transform-offset = オフセット
+transform-ratio = 比率
+transform-rounding-nearest = 四捨五入
+transform-rounding-down = 切り捨て
+transform-rounding-up = 切り上げ
+transform-preview = プレビュー
+transform-selection-total = 選択範囲
+transform-script-total = スクリプト
+transform-apply = 適用
+
+open-options-menu = オプションメニュー
+toggle-graphics-editor = グラフィックエディタの切り替え
+toggle-simple-view = 簡単表示
+toggle-timing-gutter = フレーム / 時間表示
+command-palette = コマンドパレット
+new-line = 新しい行
+
+new-file-title = 新規
+
+save-file-question = 編集したファイルを保存したいですか？
+recovery-question = 保存されていないファイルが復元されました。開きますか？
+recovery-discard-question = 復元されたファイルを破棄しますか？破棄しない場合は次回の起動時まで保持されます。
+external-change-title = ファイルが変更されました
+external-change-question = 保存されていない変更があるファイルが他のプログラムによって変更されました。
+reload = 再読み込み
+keep-mine = 自分の変更を保持
+show-diff = 差分を表示
+hide-diff = 差分を隠す
+
+warning = 警告
+error = エラー
+
+frametime-f32-parse-err = フレームタイムをf32として解析できません
+setting-default-frametime = ディフォルトのフレームタイムに変えます
+
+properties = プロパティ
+demo-name = デモ名
+set-demo-recording = デモを録画する
+save-name = セーブ名
+save-after-hltas = hltas実行後にセーブ
+enable-0ms-ducktap = 0ms ダックタップを有効にする
+zero-ms-frametime = 0ms フレームタイム
+enable-shared-nonshared = 共用 / 非共用 乱数を有効
+set-hlstrafe-version = hlstrafeバージョンを選択する
+hlstrafe-version = hlstrafeバージョン
+set-hltas-load-commands = hltasロードコマンドを選択する
+load-commands = ロードコマンド
+commands = コマンド
+shared-rng = 共用乱数
+non-shared-rng = 非共用乱数
+
+lines = 行
+
+text-editor-parse-err = テキストをhltasとして解析できません、グラフィックエディタに切り替える前に修正してください
@@ -0,0 +1,670 @@
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
+
+use fluent_templates::{LanguageIdentifier, Loader};
+use hltas::{
+    types::{Line, Properties},
+    HLTAS,
+};
+use hltas_cleaner::CleanerResult;
+
+use crate::{
+    helpers::hltas::{
+        framebulk_edit::{merge_framebulks, split_framebulk},
+        hltas_to_str, lines_to_str,
+        lint::{FixDefaults, QuickFix},
+        str_to_lines,
+    },
+    locale::LOCALES,
+    selection::Selection,
+    undo_redo_hltas::UndoRedoHandler,
+};
+
+/// States kept for each line of the document, like the selection or the states of a front-end
+///
+/// * Gets updated every time the lines change, including undo / redo
+pub trait LineStates {
+    fn insert_line(&mut self, index: usize, line: &Line);
+    fn remove_line(&mut self, index: usize);
+    /// Line got replaced, which could also change the line type
+    fn update_line(&mut self, index: usize, line: &Line);
+    /// All lines got replaced with the lines of `hltas`
+    fn reset_lines(&mut self, hltas: &HLTAS);
+    fn selection(&self) -> &Selection;
+    fn selection_mut(&mut self) -> &mut Selection;
+}
+
+/// Modified time of the file, `None` if it can't be read
+pub fn read_disk_modified_time(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+}
+
+/// Title of a file, falls back to the new file title if the path has no file name
+pub fn title_from_path(path: &Path, lang: &LanguageIdentifier) -> String {
+    match path.file_name() {
+        Some(file_name) => file_name.to_string_lossy().to_string(),
+        None => default_title(lang),
+    }
+}
+
+// BUG fix language change for title
+fn default_title(lang: &LanguageIdentifier) -> String {
+    LOCALES.lookup(lang, "new-file-title")
+}
+
+/// Opened hltas file with its undo history
+///
+/// * Every edit that goes through the document keeps `line_states` in sync with the lines
+#[derive(Clone, Debug, Default)]
+pub struct Document<S = Selection> {
+    title: String,
+    path: Option<PathBuf>,
+    hltas: HLTAS,
+    pub line_states: S,
+    pub undo_redo_handler: UndoRedoHandler,
+    modified: bool,
+    /// Goes up with every edit, so anything computed from the hltas knows when it's outdated
+    revision: u64,
+    /// Modified time of the file the last time it was read or written
+    disk_modified_time: Option<SystemTime>,
+}
+
+impl<'a, S: LineStates + Default> Document<S> {
+    pub fn open_path(path: &Path, file_content: &'a str) -> Result<Self, hltas::read::Error<'a>> {
+        let hltas = HLTAS::from_str(file_content)?;
+
+        let mut line_states = S::default();
+        line_states.reset_lines(&hltas);
+
+        Ok(Self {
+            title: path.file_name().unwrap().to_string_lossy().to_string(),
+            path: Some(path.to_path_buf()),
+            hltas,
+            line_states,
+            disk_modified_time: read_disk_modified_time(path),
+            ..Default::default()
+        })
+    }
+
+    pub fn new_file(lang: &LanguageIdentifier) -> Self {
+        Self {
+            title: default_title(lang),
+            ..Default::default()
+        }
+    }
+
+    /// Document that isn't saved anywhere yet, so it's marked as modified
+    ///
+    /// * `path` is where the document would be saved to
+    pub fn from_hltas(title: String, path: Option<PathBuf>, hltas: HLTAS) -> Self {
+        let mut line_states = S::default();
+        line_states.reset_lines(&hltas);
+
+        Self {
+            title,
+            disk_modified_time: path.as_ref().and_then(|path| read_disk_modified_time(path)),
+            path,
+            hltas,
+            line_states,
+            modified: true,
+            ..Default::default()
+        }
+    }
+}
+
+impl<S: LineStates> Document<S> {
+    pub fn title(&self) -> &str {
+        self.title.as_ref()
+    }
+
+    pub fn path(&self) -> Option<&PathBuf> {
+        self.path.as_ref()
+    }
+
+    pub fn disk_modified_time(&self) -> Option<SystemTime> {
+        self.disk_modified_time
+    }
+
+    pub fn set_disk_modified_time(&mut self, disk_modified_time: Option<SystemTime>) {
+        self.disk_modified_time = disk_modified_time;
+    }
+
+    pub fn got_modified(&mut self) {
+        self.modified = true;
+        self.revision = self.revision.wrapping_add(1);
+    }
+
+    pub fn saved_modified(&mut self) {
+        self.modified = false;
+    }
+
+    pub fn is_modified(&self) -> bool {
+        self.modified
+    }
+
+    pub fn revision(&self) -> u64 {
+        self.revision
+    }
+
+    /// Writes the hltas to `path`, which becomes the path of the document
+    pub fn save_to_path(&mut self, path: &Path) -> Result<(), io::Error> {
+        fs::write(path, hltas_to_str(&self.hltas))?;
+
+        if let Some(file_name) = path.file_name() {
+            self.title = file_name.to_string_lossy().to_string();
+        }
+        self.path = Some(path.to_path_buf());
+        self.disk_modified_time = read_disk_modified_time(path);
+        self.saved_modified();
+
+        Ok(())
+    }
+
+    pub fn hltas(&self) -> &HLTAS {
+        &self.hltas
+    }
+
+    pub fn hltas_properties(&self) -> &Properties {
+        &self.hltas.properties
+    }
+
+    pub fn hltas_properties_mut(&mut self) -> &mut Properties {
+        &mut self.hltas.properties
+    }
+
+    pub fn hltas_lines(&self) -> &[Line] {
+        &self.hltas.lines
+    }
+
+    pub fn split_fields_mut(
+        &mut self,
+    ) -> (&mut [Line], &mut Properties, &mut S, &mut UndoRedoHandler) {
+        (
+            &mut self.hltas.lines,
+            &mut self.hltas.properties,
+            &mut self.line_states,
+            &mut self.undo_redo_handler,
+        )
+    }
+
+    pub fn insert_line(&mut self, index: usize, line: Line) {
+        self.line_states.insert_line(index, &line);
+        self.hltas.lines.insert(index, line);
+        self.got_modified();
+    }
+
+    pub fn push_line(&mut self, line: Line) {
+        self.line_states.insert_line(self.hltas.lines.len(), &line);
+        self.hltas.lines.push(line);
+        self.got_modified();
+    }
+
+    pub fn remove_line_at_index(&mut self, index: usize) {
+        self.hltas.lines.remove(index);
+        self.line_states.remove_line(index);
+        self.got_modified();
+    }
+
+    pub fn hltas_cleaner_fn<F>(&mut self, cleaner: F)
+    where
+        F: FnOnce(&mut HLTAS) -> CleanerResult,
+    {
+        let lines_before = self.hltas.lines.clone();
+        let cleaner_result = cleaner(&mut self.hltas);
+
+        let mut lines_removed = cleaner_result.lines_removed;
+        lines_removed.sort_unstable();
+
+        // cleaners can also edit the lines that are kept
+        let mut lines_after = self.hltas.lines.iter();
+        let edited_lines = lines_before
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| lines_removed.binary_search(i).is_err())
+            .filter_map(|(i, line_before)| match lines_after.next() {
+                Some(line_after) if line_after != line_before => Some((i, line_before.to_owned())),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+
+        self.undo_redo_handler.begin_group();
+        self.undo_redo_handler.edit_lines(edited_lines.clone());
+        self.undo_redo_handler.delete_lines(
+            lines_removed
+                .iter()
+                .map(|i| (*i, lines_before[*i].to_owned()))
+                .collect(),
+        );
+        self.undo_redo_handler.end_group();
+
+        for i in lines_removed.iter().rev() {
+            self.line_states.remove_line(*i);
+        }
+        for (i, _) in edited_lines {
+            let new_index = i - lines_removed.iter().filter(|removed| **removed < i).count();
+            self.line_states
+                .update_line(new_index, &self.hltas.lines[new_index]);
+        }
+
+        self.got_modified();
+    }
+
+    /// Replaces lines at the indexes as a single undo point
+    pub fn replace_lines(&mut self, indexes_and_lines: Vec<(usize, Line)>) {
+        if indexes_and_lines.is_empty() {
+            return;
+        }
+
+        self.undo_redo_handler.edit_lines(
+            indexes_and_lines
+                .iter()
+                .map(|(i, _)| (*i, self.hltas.lines[*i].to_owned()))
+                .collect(),
+        );
+
+        for (i, line) in indexes_and_lines {
+            self.line_states.update_line(i, &line);
+            self.hltas.lines[i] = line;
+        }
+
+        self.got_modified();
+    }
+
+    /// Applies the quick fixes to the lines at the indexes as a single undo point
+    pub fn apply_quick_fixes(&mut self, fixes: &[(usize, QuickFix)], defaults: &FixDefaults) {
+        let mut fixed = self.hltas.to_owned();
+        for (index, quick_fix) in fixes {
+            quick_fix.apply(&mut fixed, *index, defaults);
+        }
+
+        let edited_lines = fixed
+            .lines
+            .into_iter()
+            .enumerate()
+            .filter(|(i, line)| *line != self.hltas.lines[*i])
+            .collect::<Vec<_>>();
+
+        self.undo_redo_handler.begin_group();
+        if fixed.properties != self.hltas.properties {
+            let properties = std::mem::replace(&mut self.hltas.properties, fixed.properties);
+            self.undo_redo_handler.edit_properties(properties);
+            self.got_modified();
+        }
+        self.replace_lines(edited_lines);
+        self.undo_redo_handler.end_group();
+    }
+
+    /// Splits the framebulk at `index` so a new framebulk starts at `frame`, as a single undo point
+    ///
+    /// * Returns false if there is no framebulk to split
+    pub fn split_framebulk_at(&mut self, index: usize, frame: u32) -> bool {
+        let (first, second) = match &self.hltas.lines[index] {
+            Line::FrameBulk(framebulk) => match split_framebulk(framebulk, frame) {
+                Some(split) => split,
+                None => return false,
+            },
+            _ => return false,
+        };
+
+        self.undo_redo_handler.begin_group();
+        self.replace_lines(vec![(index, Line::FrameBulk(first))]);
+        self.insert_line(index + 1, Line::FrameBulk(second));
+        self.undo_redo_handler.add_lines(vec![index + 1]);
+        self.undo_redo_handler.end_group();
+
+        true
+    }
+
+    /// Checks if the framebulk at `index` can be merged with the line after it
+    pub fn can_merge_framebulk_at(&self, index: usize) -> bool {
+        match (self.hltas.lines.get(index), self.hltas.lines.get(index + 1)) {
+            (Some(Line::FrameBulk(first)), Some(Line::FrameBulk(second))) => {
+                merge_framebulks(first, second).is_some()
+            }
+            _ => false,
+        }
+    }
+
+    /// Merges the framebulk at `index` with the following framebulks that only differ in frame count
+    ///
+    /// * Recorded as a single undo point
+    /// * Returns false if nothing could be merged
+    pub fn merge_framebulks_at(&mut self, index: usize) -> bool {
+        let mut merged = match self.hltas.lines.get(index) {
+            Some(Line::FrameBulk(framebulk)) => framebulk.to_owned(),
+            _ => return false,
+        };
+
+        let mut end = index + 1;
+        while let Some(Line::FrameBulk(next)) = self.hltas.lines.get(end) {
+            match merge_framebulks(&merged, next) {
+                Some(next_merged) => merged = next_merged,
+                None => break,
+            }
+            end += 1;
+        }
+
+        if end == index + 1 {
+            return false;
+        }
+
+        self.undo_redo_handler.begin_group();
+        self.replace_lines(vec![(index, Line::FrameBulk(merged))]);
+        self.undo_redo_handler.delete_lines(
+            (index + 1..end)
+                .map(|i| (i, self.hltas.lines[i].to_owned()))
+                .collect(),
+        );
+        for i in (index + 1..end).rev() {
+            self.remove_line_at_index(i);
+        }
+        self.undo_redo_handler.end_group();
+
+        true
+    }
+
+    /// Replaces the whole hltas as a single undo point
+    pub fn replace_hltas(&mut self, hltas: HLTAS) {
+        self.undo_redo_handler.begin_group();
+        self.undo_redo_handler.delete_lines(
+            self.hltas
+                .lines
+                .iter()
+                .cloned()
+                .enumerate()
+                .collect::<Vec<_>>(),
+        );
+        self.undo_redo_handler
+            .add_lines((0..hltas.lines.len()).collect::<Vec<_>>());
+        if hltas.properties != self.hltas.properties {
+            self.undo_redo_handler
+                .edit_properties(self.hltas.properties.to_owned());
+        }
+        self.undo_redo_handler.end_group();
+
+        self.line_states.reset_lines(&hltas);
+        self.hltas = hltas;
+        self.got_modified();
+    }
+
+    /// Replaces the hltas with the one from the file as an undo point, the document counts as saved after
+    pub fn reload_hltas(&mut self, hltas: HLTAS) {
+        if hltas_to_str(&hltas) != hltas_to_str(&self.hltas) {
+            self.replace_hltas(hltas);
+        }
+
+        self.saved_modified();
+    }
+
+    pub fn undo_hltas(&mut self) {
+        if self
+            .undo_redo_handler
+            .undo(&mut self.hltas, &mut self.line_states)
+        {
+            self.got_modified();
+        }
+    }
+
+    pub fn redo_hltas(&mut self) {
+        if self
+            .undo_redo_handler
+            .redo(&mut self.hltas, &mut self.line_states)
+        {
+            self.got_modified();
+        }
+    }
+
+    pub fn select_all_lines(&mut self) {
+        self.line_states.selection_mut().select_all();
+    }
+
+    pub fn get_selected_lines(&self) -> Vec<&Line> {
+        self.line_states
+            .selection()
+            .indexes()
+            .into_iter()
+            .map(|i| &self.hltas.lines[i])
+            .collect()
+    }
+
+    pub fn remove_selected_lines(&mut self) {
+        for index in self.line_states.selection().indexes().iter().rev() {
+            self.remove_line_at_index(*index);
+        }
+    }
+
+    /// Removes the selected lines as a single undo point
+    pub fn delete_selected_lines(&mut self) {
+        let lines_to_delete = self
+            .line_states
+            .selection()
+            .indexes()
+            .into_iter()
+            .map(|i| (i, self.hltas.lines[i].to_owned()))
+            .collect();
+
+        self.undo_redo_handler.begin_group();
+        self.undo_redo_handler.delete_lines(lines_to_delete);
+        self.remove_selected_lines();
+        self.undo_redo_handler.end_group();
+    }
+
+    /// Selected lines in the clipboard text format, which is the lines as written in the hltas file
+    pub fn copy_selected_lines(&self) -> String {
+        lines_to_str(self.get_selected_lines().into_iter().cloned().collect())
+    }
+
+    /// Copies then deletes the selected lines as a single undo point
+    pub fn cut_selected_lines(&mut self) -> String {
+        let text = self.copy_selected_lines();
+        self.delete_selected_lines();
+        text
+    }
+
+    /// Inserts lines from the clipboard text as a single undo point
+    ///
+    /// * The lines get inserted at the last selected line, or added if the document is empty
+    /// * Returns false if the text isn't valid hltas lines
+    pub fn paste_lines(&mut self, text: &str) -> bool {
+        let lines = match str_to_lines(text) {
+            Some(lines) => lines,
+            None => return false,
+        };
+
+        let insert_index = match self.line_states.selection().indexes().last() {
+            Some(last_selected_index) => *last_selected_index,
+            None if self.hltas.lines.is_empty() => 0,
+            None => return true,
+        };
+
+        self.undo_redo_handler.begin_group();
+        self.undo_redo_handler
+            .add_lines((insert_index..insert_index + lines.len()).collect());
+        for (i, line) in lines.into_iter().enumerate() {
+            self.insert_line(insert_index + i, line);
+        }
+        self.undo_redo_handler.end_group();
+
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn document(lines: &str) -> Document {
+        let hltas = HLTAS::from_str(&format!("version 1\nframes\n{}", lines)).unwrap();
+        Document::from_hltas("test".to_string(), None, hltas)
+    }
+
+    fn lines(document: &Document) -> String {
+        lines_to_str(document.hltas_lines().to_vec())
+    }
+
+    #[test]
+    fn undo_redo_restores_lines_and_states() {
+        let mut document = document("// a\n// b\n// c");
+        document.line_states.set(2, true);
+
+        document.remove_line_at_index(0);
+        document
+            .undo_redo_handler
+            .delete_lines(vec![(0, Line::Comment(" a".to_string()))]);
+        assert_eq!(lines(&document), "// b\n// c");
+        assert_eq!(document.line_states.indexes(), vec![1]);
+
+        document.undo_hltas();
+        assert_eq!(lines(&document), "// a\n// b\n// c");
+        assert_eq!(document.line_states.len(), 3);
+
+        document.redo_hltas();
+        assert_eq!(lines(&document), "// b\n// c");
+        assert_eq!(document.line_states.len(), 2);
+    }
+
+    #[test]
+    fn edits_change_the_revision() {
+        let mut document = document("// a\n// b");
+        let revision = document.revision();
+
+        document.select_all_lines();
+        document.undo_hltas();
+        assert_eq!(document.revision(), revision);
+
+        document.replace_lines(vec![(0, Line::Comment(" x".to_string()))]);
+        let edited_revision = document.revision();
+        assert_ne!(edited_revision, revision);
+
+        document.undo_hltas();
+        assert_ne!(document.revision(), edited_revision);
+    }
+
+    #[test]
+    fn replace_lines_is_one_undo_point() {
+        let mut document = document("// a\n// b");
+
+        document.replace_lines(vec![
+            (0, Line::Comment(" x".to_string())),
+            (1, Line::Comment(" y".to_string())),
+        ]);
+        assert_eq!(lines(&document), "// x\n// y");
+        assert_eq!(document.undo_redo_handler.undo_stack().len(), 1);
+
+        document.undo_hltas();
+        assert_eq!(lines(&document), "// a\n// b");
+    }
+
+    #[test]
+    fn cut_and_paste() {
+        let mut document = document("// a\n// b\n// c");
+        document.line_states.set(0, true);
+        document.line_states.set(1, true);
+
+        let text = document.cut_selected_lines();
+        assert_eq!(text, "// a\n// b");
+        assert_eq!(lines(&document), "// c");
+
+        document.line_states.set(0, true);
+        assert!(document.paste_lines(&text));
+        assert_eq!(lines(&document), "// a\n// b\n// c");
+
+        // both the cut and the paste are single undo points
+        document.undo_hltas();
+        assert_eq!(lines(&document), "// c");
+        document.undo_hltas();
+        assert_eq!(lines(&document), "// a\n// b\n// c");
+    }
+
+    #[test]
+    fn paste_into_empty_document() {
+        let mut document = document("");
+
+        assert!(!document.paste_lines("not a line"));
+        assert!(document.paste_lines("// a\n// b"));
+        assert_eq!(lines(&document), "// a\n// b");
+        assert_eq!(document.line_states.len(), 2);
+    }
+
+    #[test]
+    fn split_and_merge_framebulks() {
+        // the command only runs on the first part, so it can be merged back
+        let mut with_command = document("----------|------|------|0.001|-|-|10|echo a");
+        assert!(with_command.split_framebulk_at(0, 4));
+        assert_eq!(
+            lines(&with_command),
+            "----------|------|------|0.001|-|-|4|echo a\n----------|------|------|0.001|-|-|6"
+        );
+        assert!(with_command.merge_framebulks_at(0));
+        assert_eq!(
+            lines(&with_command),
+            "----------|------|------|0.001|-|-|10|echo a"
+        );
+
+        // merging would run the command of the second framebulk one time less
+        let both_commands = document(
+            "----------|------|------|0.001|-|-|4|echo a\n----------|------|------|0.001|-|-|6|echo a",
+        );
+        assert!(!both_commands.can_merge_framebulk_at(0));
+
+        let mut document = document("----------|------|------|0.001|-|-|10");
+
+        assert!(document.split_framebulk_at(0, 4));
+        assert_eq!(
+            lines(&document),
+            "----------|------|------|0.001|-|-|4\n----------|------|------|0.001|-|-|6"
+        );
+
+        assert!(document.can_merge_framebulk_at(0));
+        assert!(document.merge_framebulks_at(0));
+        assert_eq!(lines(&document), "----------|------|------|0.001|-|-|10");
+
+        document.undo_hltas();
+        assert_eq!(
+            lines(&document),
+            "----------|------|------|0.001|-|-|4\n----------|------|------|0.001|-|-|6"
+        );
+        assert_eq!(document.line_states.len(), 2);
+
+        document.undo_hltas();
+        assert_eq!(lines(&document), "----------|------|------|0.001|-|-|10");
+        assert_eq!(document.line_states.len(), 1);
+    }
+
+    #[test]
+    fn replace_hltas_keeps_properties_undo() {
+        let mut document = document("// a");
+        let hltas = HLTAS::from_str("version 1\ndemo test\nframes\n// b\n// c").unwrap();
+
+        document.replace_hltas(hltas);
+        assert_eq!(lines(&document), "// b\n// c");
+        assert_eq!(document.line_states.len(), 2);
+
+        document.undo_hltas();
+        assert_eq!(lines(&document), "// a");
+        assert_eq!(document.hltas_properties().demo, None);
+    }
+
+    #[test]
+    fn save_to_path_sets_path_and_title() {
+        let path = std::env::temp_dir().join("hltas-editor-core-document-test.hltas");
+        let mut document = document("// a");
+        assert!(document.is_modified());
+
+        document.save_to_path(&path).unwrap();
+        assert!(!document.is_modified());
+        assert_eq!(document.path(), Some(&path));
+        assert_eq!(document.title(), "hltas-editor-core-document-test.hltas");
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            hltas_to_str(document.hltas())
+        );
+
+        fs::remove_file(&path).ok();
+    }
+}
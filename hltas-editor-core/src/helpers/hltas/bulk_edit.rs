@@ -0,0 +1,430 @@
+use hltas::types::{
+    AutoMovement, FrameBulk, LeaveGroundActionSpeed, StrafeDir, StrafeSettings, StrafeType,
+};
+
+/// Value of a field across several framebulks
+#[derive(Clone, Debug, PartialEq)]
+pub enum FieldState<T> {
+    Same(T),
+    Mixed,
+}
+
+impl<T: PartialEq> FieldState<T> {
+    /// Returns `None` if there are no values
+    pub fn from_values<I: IntoIterator<Item = T>>(values: I) -> Option<Self> {
+        let mut values = values.into_iter();
+        let first = values.next()?;
+
+        for value in values {
+            if value != first {
+                return Some(Self::Mixed);
+            }
+        }
+
+        Some(Self::Same(first))
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum StrafeDirKind {
+    Left,
+    Right,
+    Best,
+    Yaw,
+    Line,
+}
+
+impl StrafeDirKind {
+    pub const ALL: [StrafeDirKind; 5] =
+        [Self::Left, Self::Right, Self::Best, Self::Yaw, Self::Line];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            StrafeDirKind::Left => "Left",
+            StrafeDirKind::Right => "Right",
+            StrafeDirKind::Best => "Best",
+            StrafeDirKind::Yaw => "Yaw",
+            StrafeDirKind::Line => "Line",
+        }
+    }
+
+    /// Returns `None` for directions that can't be picked from the bulk edit
+    pub fn from_dir(dir: &StrafeDir) -> Option<Self> {
+        match dir {
+            StrafeDir::Left => Some(Self::Left),
+            StrafeDir::Right => Some(Self::Right),
+            StrafeDir::Best => Some(Self::Best),
+            StrafeDir::Yaw(_) => Some(Self::Yaw),
+            StrafeDir::Line { .. } => Some(Self::Line),
+            _ => None,
+        }
+    }
+
+    fn to_dir(self, yaw: f32) -> StrafeDir {
+        match self {
+            StrafeDirKind::Left => StrafeDir::Left,
+            StrafeDirKind::Right => StrafeDir::Right,
+            StrafeDirKind::Best => StrafeDir::Best,
+            StrafeDirKind::Yaw => StrafeDir::Yaw(yaw),
+            StrafeDirKind::Line => StrafeDir::Line { yaw },
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ActionKey {
+    Jump,
+    Duck,
+    Use,
+    Attack1,
+    Attack2,
+    Reload,
+}
+
+impl ActionKey {
+    pub const ALL: [ActionKey; 6] = [
+        Self::Jump,
+        Self::Duck,
+        Self::Use,
+        Self::Attack1,
+        Self::Attack2,
+        Self::Reload,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            ActionKey::Jump => "jump",
+            ActionKey::Duck => "duck",
+            ActionKey::Use => "use",
+            ActionKey::Attack1 => "attack 1",
+            ActionKey::Attack2 => "attack 2",
+            ActionKey::Reload => "reload",
+        }
+    }
+
+    pub fn get(&self, framebulk: &FrameBulk) -> bool {
+        let action_keys = &framebulk.action_keys;
+        match self {
+            ActionKey::Jump => action_keys.jump,
+            ActionKey::Duck => action_keys.duck,
+            ActionKey::Use => action_keys.use_,
+            ActionKey::Attack1 => action_keys.attack_1,
+            ActionKey::Attack2 => action_keys.attack_2,
+            ActionKey::Reload => action_keys.reload,
+        }
+    }
+
+    fn get_mut<'a>(&self, framebulk: &'a mut FrameBulk) -> &'a mut bool {
+        let action_keys = &mut framebulk.action_keys;
+        match self {
+            ActionKey::Jump => &mut action_keys.jump,
+            ActionKey::Duck => &mut action_keys.duck,
+            ActionKey::Use => &mut action_keys.use_,
+            ActionKey::Attack1 => &mut action_keys.attack_1,
+            ActionKey::Attack2 => &mut action_keys.attack_2,
+            ActionKey::Reload => &mut action_keys.reload,
+        }
+    }
+}
+
+/// Yaw used by the framebulk, if it has one
+pub fn framebulk_yaw(framebulk: &FrameBulk) -> Option<f32> {
+    match &framebulk.auto_actions.movement {
+        Some(AutoMovement::SetYaw(yaw)) => Some(*yaw),
+        Some(AutoMovement::Strafe(strafe_settings)) => match strafe_settings.dir {
+            StrafeDir::Yaw(yaw) => Some(yaw),
+            StrafeDir::Line { yaw } => Some(yaw),
+            _ => None,
+        },
+        None => None,
+    }
+}
+
+/// Strafe type of the framebulk, `None` if it doesn't strafe
+pub fn framebulk_strafe_type(framebulk: &FrameBulk) -> Option<StrafeType> {
+    match &framebulk.auto_actions.movement {
+        Some(AutoMovement::Strafe(strafe_settings)) => Some(strafe_settings.type_),
+        _ => None,
+    }
+}
+
+/// Fields to set on every framebulk, `None` fields are left alone
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct BulkEdit {
+    pub frame_time: Option<String>,
+    /// `Some(None)` stops strafing
+    pub strafe_type: Option<Option<StrafeType>>,
+    /// Only applies to framebulks that strafe
+    pub strafe_dir: Option<StrafeDirKind>,
+    /// Framebulks strafing without a yaw are left alone
+    pub yaw: Option<f32>,
+    pub pitch: Option<f32>,
+    /// Only applies to framebulks with a jump or ducktap
+    pub lgagst_speed: Option<LeaveGroundActionSpeed>,
+    /// In the same order as `ActionKey::ALL`
+    pub action_keys: [Option<bool>; 6],
+    /// Appended after the console command, separated with `;`
+    pub append_console_command: String,
+}
+
+impl BulkEdit {
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// The frametime has to be a positive number, which is checked here as the hltas reader accepts `0` or `-1`
+    pub fn is_valid(&self) -> bool {
+        match &self.frame_time {
+            Some(frame_time) => frame_time
+                .parse::<f32>()
+                .is_ok_and(|frame_time| frame_time.is_finite() && frame_time > 0.0),
+            None => true,
+        }
+    }
+
+    /// Sets the fields on the framebulk, an invalid frametime is left out
+    pub fn apply(&self, framebulk: &mut FrameBulk) {
+        if let Some(frame_time) = &self.frame_time {
+            if self.is_valid() {
+                framebulk.frame_time = frame_time.to_owned();
+            }
+        }
+
+        if let Some(strafe_type) = self.strafe_type {
+            let prev_yaw = framebulk_yaw(framebulk);
+
+            framebulk.auto_actions.movement = match strafe_type {
+                Some(strafe_type) => Some(AutoMovement::Strafe(StrafeSettings {
+                    type_: strafe_type,
+                    dir: match &framebulk.auto_actions.movement {
+                        Some(AutoMovement::Strafe(strafe_settings)) => strafe_settings.dir,
+                        _ => match strafe_type {
+                            StrafeType::MaxDeccel => StrafeDir::Best,
+                            _ => StrafeDir::Yaw(prev_yaw.unwrap_or(0.0)),
+                        },
+                    },
+                })),
+                None => prev_yaw.map(AutoMovement::SetYaw),
+            };
+        }
+
+        if let Some(strafe_dir) = self.strafe_dir {
+            let prev_yaw = framebulk_yaw(framebulk).unwrap_or(0.0);
+
+            if let Some(AutoMovement::Strafe(strafe_settings)) =
+                &mut framebulk.auto_actions.movement
+            {
+                strafe_settings.dir = strafe_dir.to_dir(prev_yaw);
+            }
+        }
+
+        if let Some(new_yaw) = self.yaw {
+            match &mut framebulk.auto_actions.movement {
+                Some(AutoMovement::SetYaw(yaw)) => *yaw = new_yaw,
+                Some(AutoMovement::Strafe(strafe_settings)) => match &mut strafe_settings.dir {
+                    StrafeDir::Yaw(yaw) => *yaw = new_yaw,
+                    StrafeDir::Line { yaw } => *yaw = new_yaw,
+                    _ => (),
+                },
+                None => framebulk.auto_actions.movement = Some(AutoMovement::SetYaw(new_yaw)),
+            }
+        }
+
+        if let Some(pitch) = self.pitch {
+            framebulk.pitch = Some(pitch);
+        }
+
+        if let Some(lgagst_speed) = self.lgagst_speed {
+            if let Some(leave_ground_action) = &mut framebulk.auto_actions.leave_ground_action {
+                leave_ground_action.speed = lgagst_speed;
+            }
+        }
+
+        for (key, state) in ActionKey::ALL.iter().zip(self.action_keys.iter()) {
+            if let Some(state) = state {
+                *key.get_mut(framebulk) = *state;
+            }
+        }
+
+        if !self.append_console_command.is_empty() {
+            framebulk.console_command = Some(match &framebulk.console_command {
+                Some(console_command) => {
+                    format!("{};{}", console_command, self.append_console_command)
+                }
+                None => self.append_console_command.to_owned(),
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::num::NonZeroU32;
+
+    use hltas::types::Line;
+
+    use crate::helpers::hltas::{empty_framebulk, lines_to_str, str_to_lines};
+
+    /// Applies the edit to every framebulk of the lines
+    fn apply(edit: BulkEdit, lines: &str) -> String {
+        let mut lines = str_to_lines(lines).unwrap();
+        for line in &mut lines {
+            if let Line::FrameBulk(framebulk) = line {
+                edit.apply(framebulk);
+            }
+        }
+        lines_to_str(lines)
+    }
+
+    #[test]
+    fn field_states() {
+        assert_eq!(FieldState::from_values(Vec::<u32>::new()), None);
+        assert_eq!(
+            FieldState::from_values(vec![1, 1]),
+            Some(FieldState::Same(1))
+        );
+        assert_eq!(
+            FieldState::from_values(vec![1, 2, 1]),
+            Some(FieldState::Mixed)
+        );
+    }
+
+    #[test]
+    fn empty_edit_changes_nothing() {
+        let lines =
+            "s03-------|------|j-----|0.001|90|-|1|echo\n----------|------|------|0.01|-|10|2";
+        assert!(BulkEdit::default().is_empty());
+        assert_eq!(apply(BulkEdit::default(), lines), lines);
+    }
+
+    #[test]
+    fn strafe_type_keeps_the_yaw() {
+        // strafing, set yaw, no movement
+        let lines = "s03-------|------|------|0.001|90|-|1
+----------|------|------|0.001|45|-|1
+----------|------|------|0.001|-|-|1";
+
+        let edit = |strafe_type| BulkEdit {
+            strafe_type: Some(strafe_type),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            apply(edit(Some(StrafeType::MaxAngle)), lines),
+            "s13-------|------|------|0.001|90|-|1
+s13-------|------|------|0.001|45|-|1
+s13-------|------|------|0.001|0|-|1"
+        );
+        // max deccel doesn't need a direction
+        assert_eq!(
+            apply(edit(Some(StrafeType::MaxDeccel)), lines),
+            "s23-------|------|------|0.001|90|-|1
+s22-------|------|------|0.001|-|-|1
+s22-------|------|------|0.001|-|-|1"
+        );
+        assert_eq!(
+            apply(edit(None), lines),
+            "----------|------|------|0.001|90|-|1
+----------|------|------|0.001|45|-|1
+----------|------|------|0.001|-|-|1"
+        );
+    }
+
+    #[test]
+    fn strafe_dir_and_yaw() {
+        let lines = "s03-------|------|------|0.001|90|-|1
+s02-------|------|------|0.001|-|-|1
+----------|------|------|0.001|-|-|1";
+
+        assert_eq!(
+            apply(
+                BulkEdit {
+                    strafe_dir: Some(StrafeDirKind::Line),
+                    ..Default::default()
+                },
+                lines
+            ),
+            "s05-------|------|------|0.001|90|-|1
+s05-------|------|------|0.001|0|-|1
+----------|------|------|0.001|-|-|1"
+        );
+        // strafing without a yaw is left alone
+        assert_eq!(
+            apply(
+                BulkEdit {
+                    yaw: Some(30.0),
+                    ..Default::default()
+                },
+                lines
+            ),
+            "s03-------|------|------|0.001|30|-|1
+s02-------|------|------|0.001|-|-|1
+----------|------|------|0.001|30|-|1"
+        );
+    }
+
+    #[test]
+    fn pitch_lgagst_and_keys() {
+        let lines = "----j-----|------|jd----|0.001|-|-|1
+----------|------|------|0.001|-|10|1";
+
+        let mut action_keys = [None; 6];
+        action_keys[0] = Some(false);
+        action_keys[2] = Some(true);
+
+        assert_eq!(
+            apply(
+                BulkEdit {
+                    pitch: Some(-5.0),
+                    lgagst_speed: Some(LeaveGroundActionSpeed::Optimal),
+                    action_keys,
+                    ..Default::default()
+                },
+                lines
+            ),
+            "---lj-----|------|-du---|0.001|-|-5|1
+----------|------|--u---|0.001|-|-5|1"
+        );
+    }
+
+    #[test]
+    fn console_command_is_appended() {
+        assert_eq!(
+            apply(
+                BulkEdit {
+                    append_console_command: "echo b".to_string(),
+                    ..Default::default()
+                },
+                "----------|------|------|0.001|-|-|1|echo a
+----------|------|------|0.001|-|-|1"
+            ),
+            "----------|------|------|0.001|-|-|1|echo a;echo b
+----------|------|------|0.001|-|-|1|echo b"
+        );
+    }
+
+    #[test]
+    fn only_positive_frametimes_are_applied() {
+        let mut framebulk = empty_framebulk("0.001", NonZeroU32::new(1).unwrap());
+
+        for frame_time in ["", "fast", "0", "-0.001", "inf", "NaN"].iter() {
+            let edit = BulkEdit {
+                frame_time: Some(frame_time.to_string()),
+                ..Default::default()
+            };
+            assert!(!edit.is_valid());
+
+            edit.apply(&mut framebulk);
+            assert_eq!(framebulk.frame_time, "0.001");
+        }
+
+        let edit = BulkEdit {
+            frame_time: Some("0.004".to_string()),
+            ..Default::default()
+        };
+        assert!(edit.is_valid());
+        edit.apply(&mut framebulk);
+        assert_eq!(framebulk.frame_time, "0.004");
+    }
+}
@@ -0,0 +1,26 @@
+use hltas::HLTAS;
+use hltas_cleaner::{cleaners, CleanerResult};
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum Cleaner {
+    NoDupeFramebulks,
+    RemoveComments,
+}
+
+impl Cleaner {
+    pub const ALL: &'static [Cleaner] = &[Cleaner::NoDupeFramebulks, Cleaner::RemoveComments];
+
+    pub fn id(&self) -> &'static str {
+        match self {
+            Cleaner::NoDupeFramebulks => "no-dupe-framebulk",
+            Cleaner::RemoveComments => "no-comments",
+        }
+    }
+
+    pub fn cleaner_fn(&self) -> fn(&mut HLTAS) -> CleanerResult {
+        match self {
+            Cleaner::NoDupeFramebulks => cleaners::no_dupe_framebulks,
+            Cleaner::RemoveComments => cleaners::remove_comments,
+        }
+    }
+}
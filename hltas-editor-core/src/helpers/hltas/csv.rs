@@ -0,0 +1,491 @@
+use std::{error::Error, fmt::Display};
+
+use hltas::{
+    types::{AutoMovement, Line, Properties, StrafeDir, StrafeSettings, StrafeType},
+    HLTAS,
+};
+
+use super::{hltas_to_str, lines_to_str, str_to_lines};
+
+/// Columns of the csv, framebulk rows use all but `value`, other rows only use `type` and `value`
+pub const COLUMNS: &[&str] = &[
+    "type",
+    "frame_time",
+    "frame_count",
+    "strafe_type",
+    "strafe_dir",
+    "yaw",
+    "movement_keys",
+    "action_keys",
+    "auto_actions",
+    "pitch",
+    "command",
+    "value",
+];
+
+const TYPE: usize = 0;
+const FRAME_TIME: usize = 1;
+const FRAME_COUNT: usize = 2;
+const STRAFE_TYPE: usize = 3;
+const STRAFE_DIR: usize = 4;
+const YAW: usize = 5;
+const MOVEMENT_KEYS: usize = 6;
+const ACTION_KEYS: usize = 7;
+const AUTO_ACTIONS: usize = 8;
+const PITCH: usize = 9;
+const COMMAND: usize = 10;
+const VALUE: usize = 11;
+
+const PROPERTY_TYPE: &str = "property";
+const FRAMEBULK_TYPE: &str = "framebulk";
+
+const STRAFE_TYPES: &[(StrafeType, &str)] = &[
+    (StrafeType::MaxAccel, "max_accel"),
+    (StrafeType::MaxAngle, "max_angle"),
+    (StrafeType::MaxDeccel, "max_deccel"),
+    (StrafeType::ConstSpeed, "const_speed"),
+];
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum CsvError {
+    UnclosedQuote,
+    MissingTypeColumn,
+    UnknownType {
+        row: usize,
+        type_: String,
+    },
+    InvalidValue {
+        row: usize,
+        column: &'static str,
+        value: String,
+    },
+    /// The row doesn't make a valid hltas line
+    InvalidLine {
+        row: usize,
+        line: String,
+    },
+}
+
+impl Display for CsvError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CsvError::UnclosedQuote => write!(f, "unclosed quote"),
+            CsvError::MissingTypeColumn => write!(f, "missing `type` column"),
+            CsvError::UnknownType { row, type_ } => {
+                write!(f, "row {}: unknown type `{}`", row, type_)
+            }
+            CsvError::InvalidValue { row, column, value } => {
+                write!(f, "row {}: invalid {} `{}`", row, column, value)
+            }
+            CsvError::InvalidLine { row, line } => {
+                write!(f, "row {}: invalid hltas line `{}`", row, line)
+            }
+        }
+    }
+}
+
+impl Error for CsvError {}
+
+/// Writes the hltas as csv, with a row for each property and line
+pub fn hltas_to_csv(hltas: &HLTAS) -> String {
+    let mut rows = vec![COLUMNS.iter().map(|column| column.to_string()).collect()];
+
+    let properties = hltas_to_str(&HLTAS {
+        properties: hltas.properties.to_owned(),
+        ..Default::default()
+    });
+    // skip the `version` and `frames` lines around the properties
+    let property_lines = properties.lines().collect::<Vec<_>>();
+    for property in &property_lines[1..property_lines.len() - 1] {
+        rows.push(value_row(PROPERTY_TYPE, property));
+    }
+
+    for line in &hltas.lines {
+        rows.push(line_to_row(line));
+    }
+
+    rows.iter()
+        .map(|row: &Vec<String>| {
+            row.iter()
+                .map(|field| escape_field(field))
+                .collect::<Vec<_>>()
+                .join(",")
+        })
+        .map(|row| row + "\n")
+        .collect()
+}
+
+pub fn csv_to_hltas(csv: &str) -> Result<HLTAS, CsvError> {
+    let mut rows = parse_records(csv)?.into_iter().enumerate();
+
+    let header = match rows.next() {
+        Some((_, header)) => header,
+        None => return Err(CsvError::MissingTypeColumn),
+    };
+    let column_indexes = COLUMNS
+        .iter()
+        .map(|column| header.iter().position(|name| name.trim() == *column))
+        .collect::<Vec<_>>();
+    if column_indexes[TYPE].is_none() {
+        return Err(CsvError::MissingTypeColumn);
+    }
+
+    let mut properties = Properties::default();
+    let mut lines = Vec::new();
+
+    for (i, record) in rows {
+        let row = i + 1;
+        let field = |column: usize| match column_indexes[column] {
+            Some(index) => record.get(index).map(String::as_str).unwrap_or_default(),
+            None => "",
+        };
+
+        match field(TYPE).trim() {
+            // skips empty rows a spreadsheet might leave
+            "" if record.iter().all(|field| field.is_empty()) => (),
+            PROPERTY_TYPE => {
+                let property =
+                    match HLTAS::from_str(&format!("version 1\n{}\nframes\n", field(VALUE))) {
+                        Ok(hltas) => hltas.properties,
+                        Err(_) => {
+                            return Err(CsvError::InvalidLine {
+                                row,
+                                line: field(VALUE).to_string(),
+                            })
+                        }
+                    };
+
+                properties = Properties {
+                    demo: property.demo.or(properties.demo),
+                    save: property.save.or(properties.save),
+                    frametime_0ms: property.frametime_0ms.or(properties.frametime_0ms),
+                    seeds: property.seeds.or(properties.seeds),
+                    hlstrafe_version: property.hlstrafe_version.or(properties.hlstrafe_version),
+                    load_command: property.load_command.or(properties.load_command),
+                };
+            }
+            FRAMEBULK_TYPE => lines.push(framebulk_from_row(row, &field)?),
+            type_ => {
+                let line = match str_to_lines(field(VALUE)) {
+                    Some(mut lines) if lines.len() == 1 => lines.remove(0),
+                    _ => {
+                        return Err(CsvError::InvalidLine {
+                            row,
+                            line: field(VALUE).to_string(),
+                        })
+                    }
+                };
+
+                if line_type(&line) != type_ {
+                    return Err(CsvError::UnknownType {
+                        row,
+                        type_: type_.to_string(),
+                    });
+                }
+
+                lines.push(line);
+            }
+        }
+    }
+
+    Ok(HLTAS { properties, lines })
+}
+
+fn line_type(line: &Line) -> &'static str {
+    match line {
+        Line::FrameBulk(_) => FRAMEBULK_TYPE,
+        Line::Save(_) => "save",
+        Line::SharedSeed(_) => "shared_seed",
+        Line::Buttons(_) => "buttons",
+        Line::LGAGSTMinSpeed(_) => "lgagst_min_speed",
+        Line::Reset { .. } => "reset",
+        Line::Comment(_) => "comment",
+        Line::VectorialStrafing(_) => "vectorial_strafing",
+        Line::VectorialStrafingConstraints(_) => "vectorial_strafing_constraints",
+        Line::Change(_) => "change",
+        Line::TargetYawOverride(_) => "target_yaw_override",
+    }
+}
+
+fn value_row(type_: &str, value: &str) -> Vec<String> {
+    let mut row = vec![String::new(); COLUMNS.len()];
+    row[TYPE] = type_.to_string();
+    row[VALUE] = value.to_string();
+    row
+}
+
+fn line_to_row(line: &Line) -> Vec<String> {
+    let line_str = lines_to_str(vec![line.to_owned()]);
+
+    let framebulk = match line {
+        Line::FrameBulk(framebulk) => framebulk,
+        _ => return value_row(line_type(line), &line_str),
+    };
+
+    // the fields are taken from the hltas format so they are written the same way
+    let fields = line_str.splitn(8, '|').collect::<Vec<_>>();
+    let optional_field = |field: &str| match field {
+        "-" => String::new(),
+        _ => field.to_string(),
+    };
+
+    let (strafe_type, strafe_dir) = match &framebulk.auto_actions.movement {
+        Some(AutoMovement::Strafe(StrafeSettings { type_, dir })) => {
+            let strafe_type = STRAFE_TYPES
+                .iter()
+                .find(|(strafe_type, _)| strafe_type == type_)
+                .unwrap()
+                .1;
+            let strafe_dir = match dir {
+                StrafeDir::Left => "left",
+                StrafeDir::Right => "right",
+                StrafeDir::Best => "best",
+                StrafeDir::Yaw(_) => "yaw",
+                StrafeDir::Point { .. } => "point",
+                StrafeDir::Line { .. } => "line",
+            };
+            (strafe_type, strafe_dir)
+        }
+        _ => ("", ""),
+    };
+
+    let mut row = vec![String::new(); COLUMNS.len()];
+    row[TYPE] = FRAMEBULK_TYPE.to_string();
+    row[FRAME_TIME] = fields[3].to_string();
+    row[FRAME_COUNT] = fields[6].to_string();
+    row[STRAFE_TYPE] = strafe_type.to_string();
+    row[STRAFE_DIR] = strafe_dir.to_string();
+    row[YAW] = optional_field(fields[4]);
+    row[MOVEMENT_KEYS] = fields[1].to_string();
+    row[ACTION_KEYS] = fields[2].to_string();
+    // strafing is in its own columns
+    row[AUTO_ACTIONS] = fields[0][3..].to_string();
+    row[PITCH] = optional_field(fields[5]);
+    row[COMMAND] = fields
+        .get(7)
+        .map(|command| command.to_string())
+        .unwrap_or_default();
+    row
+}
+
+fn framebulk_from_row<'a>(row: usize, field: &dyn Fn(usize) -> &'a str) -> Result<Line, CsvError> {
+    let or_default = |column: usize, default: &'a str| match field(column).trim() {
+        "" => default,
+        value => value,
+    };
+    let invalid_value = |column: usize| CsvError::InvalidValue {
+        row,
+        column: COLUMNS[column],
+        value: field(column).to_string(),
+    };
+    // `inf` and `NaN` parse as floats but can't be written as a hltas yaw
+    let parse_yaw = || match field(YAW).trim().parse::<f32>() {
+        Ok(yaw) if yaw.is_finite() => Ok(yaw),
+        _ => Err(invalid_value(YAW)),
+    };
+
+    // strafing and yaw are filled in after parsing the rest in the hltas format
+    let mut line_str = format!(
+        "---{}|{}|{}|{}|-|{}|{}",
+        or_default(AUTO_ACTIONS, "-------"),
+        or_default(MOVEMENT_KEYS, "------"),
+        or_default(ACTION_KEYS, "------"),
+        field(FRAME_TIME).trim(),
+        or_default(PITCH, "-"),
+        field(FRAME_COUNT).trim(),
+    );
+    if !field(COMMAND).is_empty() {
+        line_str.push('|');
+        line_str.push_str(field(COMMAND));
+    }
+
+    let mut framebulk = match str_to_lines(&line_str).as_deref() {
+        Some([Line::FrameBulk(framebulk)]) => framebulk.to_owned(),
+        _ => {
+            return Err(CsvError::InvalidLine {
+                row,
+                line: line_str,
+            })
+        }
+    };
+
+    framebulk.auto_actions.movement = match field(STRAFE_TYPE).trim() {
+        "" if !field(STRAFE_DIR).trim().is_empty() => return Err(invalid_value(STRAFE_DIR)),
+        "" if field(YAW).trim().is_empty() => None,
+        "" => Some(AutoMovement::SetYaw(parse_yaw()?)),
+        strafe_type => {
+            let type_ = match STRAFE_TYPES.iter().find(|(_, name)| *name == strafe_type) {
+                Some((type_, _)) => *type_,
+                None => return Err(invalid_value(STRAFE_TYPE)),
+            };
+            let dir = match field(STRAFE_DIR).trim() {
+                "left" => StrafeDir::Left,
+                "right" => StrafeDir::Right,
+                "best" => StrafeDir::Best,
+                "yaw" => StrafeDir::Yaw(parse_yaw()?),
+                "line" => StrafeDir::Line { yaw: parse_yaw()? },
+                "point" => {
+                    let point = field(YAW)
+                        .split_whitespace()
+                        .map(|value| value.parse::<f32>())
+                        .collect::<Result<Vec<_>, _>>();
+                    match point.as_deref() {
+                        Ok([x, y]) if x.is_finite() && y.is_finite() => {
+                            StrafeDir::Point { x: *x, y: *y }
+                        }
+                        _ => return Err(invalid_value(YAW)),
+                    }
+                }
+                _ => return Err(invalid_value(STRAFE_DIR)),
+            };
+            Some(AutoMovement::Strafe(StrafeSettings { type_, dir }))
+        }
+    };
+
+    Ok(Line::FrameBulk(framebulk))
+}
+
+fn escape_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+/// Splits the csv into records of fields, quoted fields can contain separators, newlines and `""` as a quote
+fn parse_records(csv: &str) -> Result<Vec<Vec<String>>, CsvError> {
+    let mut records = Vec::new();
+    let mut record = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut chars = csv.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if in_quotes {
+            match c {
+                '"' if chars.peek() == Some(&'"') => {
+                    chars.next();
+                    field.push('"');
+                }
+                '"' => in_quotes = false,
+                _ => field.push(c),
+            }
+            continue;
+        }
+
+        match c {
+            '"' => in_quotes = true,
+            ',' => record.push(std::mem::take(&mut field)),
+            '\r' if chars.peek() == Some(&'\n') => (),
+            '\n' => {
+                record.push(std::mem::take(&mut field));
+                records.push(std::mem::take(&mut record));
+            }
+            _ => field.push(c),
+        }
+    }
+
+    if in_quotes {
+        return Err(CsvError::UnclosedQuote);
+    }
+    if !field.is_empty() || !record.is_empty() {
+        record.push(field);
+        records.push(record);
+    }
+
+    Ok(records)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers::hltas::tests::EVERY_LINE_TYPE;
+
+    #[test]
+    fn round_trip() {
+        let hltas = HLTAS::from_str(EVERY_LINE_TYPE).unwrap();
+        let imported = csv_to_hltas(&hltas_to_csv(&hltas)).unwrap();
+
+        assert_eq!(hltas_to_str(&imported), hltas_to_str(&hltas));
+        assert_eq!(imported, hltas);
+    }
+
+    #[test]
+    fn framebulk_row() {
+        let hltas = HLTAS::from_str(
+            "version 1\nframes\ns03lj-----|f-----|j-----|0.001|90|10|5|echo a, b\n",
+        )
+        .unwrap();
+        let csv = hltas_to_csv(&hltas);
+
+        assert_eq!(
+            csv.lines().nth(1).unwrap(),
+            "framebulk,0.001,5,max_accel,yaw,90,f-----,j-----,lj-----,10,\"echo a, b\","
+        );
+    }
+
+    #[test]
+    fn rows_from_a_spreadsheet() {
+        let csv = "value,type,frame_count,frame_time,strafe_type,strafe_dir,yaw\r\n\
+                   demo run,property,,,,,\r\n\
+                   ,framebulk,10,0.001,max_angle,point,\"1.5 -2\"\r\n\
+                   ,,,,,,\r\n\
+                   \"// a \"\"comment\"\"\",comment,,,,,\r\n";
+        let hltas = csv_to_hltas(csv).unwrap();
+
+        assert_eq!(
+            hltas_to_str(&hltas),
+            "version 1\ndemo run\nframes\ns14-------|------|------|0.001|1.5 -2|-|10\n// a \"comment\"\n"
+        );
+    }
+
+    #[test]
+    fn invalid_rows() {
+        let header = COLUMNS.join(",");
+        let import = |row: &str| csv_to_hltas(&format!("{}\n{}\n", header, row));
+
+        assert_eq!(import("\"framebulk"), Err(CsvError::UnclosedQuote));
+        assert_eq!(csv_to_hltas("a,b\n"), Err(CsvError::MissingTypeColumn));
+        assert_eq!(
+            import("property,,,,,,,,,,,frames"),
+            Err(CsvError::InvalidLine {
+                row: 2,
+                line: "frames".to_string()
+            })
+        );
+        assert_eq!(
+            import("comment,,,,,,,,,,,seed 1"),
+            Err(CsvError::UnknownType {
+                row: 2,
+                type_: "comment".to_string()
+            })
+        );
+        assert_eq!(
+            import("framebulk,0.001,1,max_accel,yaw,,,,,,,"),
+            Err(CsvError::InvalidValue {
+                row: 2,
+                column: "yaw",
+                value: String::new()
+            })
+        );
+        for yaw in ["inf", "-inf", "NaN"] {
+            assert_eq!(
+                import(&format!("framebulk,0.001,1,max_accel,yaw,{},,,,,,", yaw)),
+                Err(CsvError::InvalidValue {
+                    row: 2,
+                    column: "yaw",
+                    value: yaw.to_string()
+                })
+            );
+        }
+        assert!(matches!(
+            import("framebulk,0.001,1,max_accel,point,1 inf,,,,,,"),
+            Err(CsvError::InvalidValue { column: "yaw", .. })
+        ));
+        assert!(matches!(
+            import("framebulk,0.001,many,,,,,,,,,"),
+            Err(CsvError::InvalidLine { row: 2, .. })
+        ));
+    }
+}
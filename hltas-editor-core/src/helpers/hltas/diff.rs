@@ -0,0 +1,365 @@
+use std::{cmp::Reverse, mem::discriminant};
+
+use hltas::types::{FrameBulk, Line};
+
+/// A single step to turn the old lines into the new lines
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum DiffOp {
+    Equal { old_index: usize, new_index: usize },
+    Delete { old_index: usize },
+    Insert { new_index: usize },
+}
+
+/// Line diff using the longest common subsequence
+///
+/// * Common lines at the start and the end are skipped before doing the lcs
+/// * The lcs is found with Hirschberg's algorithm, which only keeps a row of lcs lengths in memory
+pub fn diff_lines(old: &[Line], new: &[Line]) -> Vec<DiffOp> {
+    let prefix_len = old
+        .iter()
+        .zip(new.iter())
+        .take_while(|(old_line, new_line)| old_line == new_line)
+        .count();
+    let suffix_len = old[prefix_len..]
+        .iter()
+        .rev()
+        .zip(new[prefix_len..].iter().rev())
+        .take_while(|(old_line, new_line)| old_line == new_line)
+        .count();
+
+    let mut diff = (0..prefix_len)
+        .map(|i| DiffOp::Equal {
+            old_index: i,
+            new_index: i,
+        })
+        .collect::<Vec<_>>();
+
+    diff_middle(
+        &old[prefix_len..old.len() - suffix_len],
+        &new[prefix_len..new.len() - suffix_len],
+        prefix_len,
+        prefix_len,
+        &mut diff,
+    );
+
+    let old_suffix_start = old.len() - suffix_len;
+    let new_suffix_start = new.len() - suffix_len;
+    diff.extend((0..suffix_len).map(|i| DiffOp::Equal {
+        old_index: old_suffix_start + i,
+        new_index: new_suffix_start + i,
+    }));
+
+    diff
+}
+
+/// Pushes the diff of the lines, the offsets are added to the indexes of the diff
+fn diff_middle(
+    old: &[Line],
+    new: &[Line],
+    old_offset: usize,
+    new_offset: usize,
+    diff: &mut Vec<DiffOp>,
+) {
+    let inserts = |range: std::ops::Range<usize>| {
+        range.map(move |j| DiffOp::Insert {
+            new_index: new_offset + j,
+        })
+    };
+
+    if old.is_empty() {
+        diff.extend(inserts(0..new.len()));
+        return;
+    }
+    if new.is_empty() {
+        diff.extend((0..old.len()).map(|i| DiffOp::Delete {
+            old_index: old_offset + i,
+        }));
+        return;
+    }
+    if old.len() == 1 {
+        match new.iter().position(|line| *line == old[0]) {
+            Some(j) => {
+                diff.extend(inserts(0..j));
+                diff.push(DiffOp::Equal {
+                    old_index: old_offset,
+                    new_index: new_offset + j,
+                });
+                diff.extend(inserts(j + 1..new.len()));
+            }
+            None => {
+                diff.push(DiffOp::Delete {
+                    old_index: old_offset,
+                });
+                diff.extend(inserts(0..new.len()));
+            }
+        }
+        return;
+    }
+
+    // new is split where the lcs of the old halves with the new halves is the longest
+    let old_mid = old.len() / 2;
+    let forward_lens = lcs_lens(old_mid, new.len(), |i, j| old[i] == new[j]);
+    let backward_lens = lcs_lens(old.len() - old_mid, new.len(), |i, j| {
+        old[old.len() - 1 - i] == new[new.len() - 1 - j]
+    });
+    let new_mid = (0..=new.len())
+        .max_by_key(|&j| (forward_lens[j] + backward_lens[new.len() - j], Reverse(j)))
+        .unwrap();
+
+    diff_middle(
+        &old[..old_mid],
+        &new[..new_mid],
+        old_offset,
+        new_offset,
+        diff,
+    );
+    diff_middle(
+        &old[old_mid..],
+        &new[new_mid..],
+        old_offset + old_mid,
+        new_offset + new_mid,
+        diff,
+    );
+}
+
+/// Lcs lengths of the first `old_len` old lines with every prefix of the new lines
+fn lcs_lens(old_len: usize, new_len: usize, is_equal: impl Fn(usize, usize) -> bool) -> Vec<usize> {
+    let mut lens = vec![0; new_len + 1];
+
+    for i in 0..old_len {
+        // lens[j] of the previous row
+        let mut prev_diagonal = 0;
+        for j in 0..new_len {
+            let prev_above = lens[j + 1];
+            lens[j + 1] = if is_equal(i, j) {
+                prev_diagonal + 1
+            } else {
+                lens[j + 1].max(lens[j])
+            };
+            prev_diagonal = prev_above;
+        }
+    }
+
+    lens
+}
+
+/// A row of a side by side diff
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum DiffRow {
+    Equal {
+        old_index: usize,
+        new_index: usize,
+    },
+    /// Line replaced by a line of the same type
+    Changed {
+        old_index: usize,
+        new_index: usize,
+    },
+    Delete {
+        old_index: usize,
+    },
+    Insert {
+        new_index: usize,
+    },
+}
+
+/// Aligns the diff into rows, pairing up deleted and inserted lines of the same type as changed lines
+pub fn diff_rows(old: &[Line], new: &[Line], diff: &[DiffOp]) -> Vec<DiffRow> {
+    let mut rows = Vec::with_capacity(diff.len());
+    let mut deleted = Vec::new();
+    let mut inserted = Vec::new();
+
+    let flush = |rows: &mut Vec<DiffRow>, deleted: &mut Vec<usize>, inserted: &mut Vec<usize>| {
+        for k in 0..deleted.len().max(inserted.len()) {
+            match (deleted.get(k), inserted.get(k)) {
+                (Some(&old_index), Some(&new_index))
+                    if discriminant(&old[old_index]) == discriminant(&new[new_index]) =>
+                {
+                    rows.push(DiffRow::Changed {
+                        old_index,
+                        new_index,
+                    })
+                }
+                (old_index, new_index) => {
+                    if let Some(&old_index) = old_index {
+                        rows.push(DiffRow::Delete { old_index });
+                    }
+                    if let Some(&new_index) = new_index {
+                        rows.push(DiffRow::Insert { new_index });
+                    }
+                }
+            }
+        }
+
+        deleted.clear();
+        inserted.clear();
+    };
+
+    for diff_op in diff {
+        match *diff_op {
+            DiffOp::Equal {
+                old_index,
+                new_index,
+            } => {
+                flush(&mut rows, &mut deleted, &mut inserted);
+                rows.push(DiffRow::Equal {
+                    old_index,
+                    new_index,
+                });
+            }
+            DiffOp::Delete { old_index } => deleted.push(old_index),
+            DiffOp::Insert { new_index } => inserted.push(new_index),
+        }
+    }
+    flush(&mut rows, &mut deleted, &mut inserted);
+
+    rows
+}
+
+/// Part of a framebulk that can differ
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum FramebulkField {
+    FrameCount,
+    FrameTime,
+    Strafe,
+    MovementKeys,
+    ActionKeys,
+    AutoActions,
+    Pitch,
+    ConsoleCommand,
+}
+
+impl FramebulkField {
+    pub const ALL: [FramebulkField; 8] = [
+        Self::FrameCount,
+        Self::FrameTime,
+        Self::Strafe,
+        Self::MovementKeys,
+        Self::ActionKeys,
+        Self::AutoActions,
+        Self::Pitch,
+        Self::ConsoleCommand,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            FramebulkField::FrameCount => "frames",
+            FramebulkField::FrameTime => "frametime",
+            FramebulkField::Strafe => "strafe",
+            FramebulkField::MovementKeys => "movement keys",
+            FramebulkField::ActionKeys => "action keys",
+            FramebulkField::AutoActions => "auto actions",
+            FramebulkField::Pitch => "pitch",
+            FramebulkField::ConsoleCommand => "commands",
+        }
+    }
+
+    pub fn differs(&self, a: &FrameBulk, b: &FrameBulk) -> bool {
+        match self {
+            FramebulkField::FrameCount => a.frame_count != b.frame_count,
+            FramebulkField::FrameTime => a.frame_time != b.frame_time,
+            FramebulkField::Strafe => a.auto_actions.movement != b.auto_actions.movement,
+            FramebulkField::MovementKeys => a.movement_keys != b.movement_keys,
+            FramebulkField::ActionKeys => a.action_keys != b.action_keys,
+            // everything except the strafe settings
+            FramebulkField::AutoActions => {
+                let mut a_auto_actions = a.auto_actions.to_owned();
+                a_auto_actions.movement = b.auto_actions.movement;
+                a_auto_actions != b.auto_actions
+            }
+            FramebulkField::Pitch => a.pitch != b.pitch,
+            FramebulkField::ConsoleCommand => a.console_command != b.console_command,
+        }
+    }
+
+    /// Copies the field from one framebulk to the other
+    pub fn copy_from(&self, from: &FrameBulk, to: &mut FrameBulk) {
+        match self {
+            FramebulkField::FrameCount => to.frame_count = from.frame_count,
+            FramebulkField::FrameTime => to.frame_time = from.frame_time.to_owned(),
+            FramebulkField::Strafe => to.auto_actions.movement = from.auto_actions.movement,
+            FramebulkField::MovementKeys => to.movement_keys = from.movement_keys.to_owned(),
+            FramebulkField::ActionKeys => to.action_keys = from.action_keys.to_owned(),
+            FramebulkField::AutoActions => {
+                let movement = to.auto_actions.movement;
+                to.auto_actions = from.auto_actions.to_owned();
+                to.auto_actions.movement = movement;
+            }
+            FramebulkField::Pitch => to.pitch = from.pitch,
+            FramebulkField::ConsoleCommand => to.console_command = from.console_command.to_owned(),
+        }
+    }
+}
+
+/// Fields that differ between the framebulks
+pub fn framebulk_field_diffs(old: &FrameBulk, new: &FrameBulk) -> Vec<FramebulkField> {
+    FramebulkField::ALL
+        .iter()
+        .copied()
+        .filter(|field| field.differs(old, new))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn comments(texts: &str) -> Vec<Line> {
+        texts
+            .chars()
+            .map(|c| Line::Comment(c.to_string()))
+            .collect()
+    }
+
+    /// Checks that the diff turns old into new, returning the number of equal lines
+    fn apply_diff(old: &[Line], new: &[Line], diff: &[DiffOp]) -> usize {
+        let (mut next_old, mut next_new, mut equal_count) = (0, 0, 0);
+
+        for diff_op in diff {
+            match *diff_op {
+                DiffOp::Equal {
+                    old_index,
+                    new_index,
+                } => {
+                    assert_eq!((old_index, new_index), (next_old, next_new));
+                    assert_eq!(old[old_index], new[new_index]);
+                    next_old += 1;
+                    next_new += 1;
+                    equal_count += 1;
+                }
+                DiffOp::Delete { old_index } => {
+                    assert_eq!(old_index, next_old);
+                    next_old += 1;
+                }
+                DiffOp::Insert { new_index } => {
+                    assert_eq!(new_index, next_new);
+                    next_new += 1;
+                }
+            }
+        }
+
+        assert_eq!((next_old, next_new), (old.len(), new.len()));
+        equal_count
+    }
+
+    #[test]
+    fn diff_is_a_longest_common_subsequence() {
+        let cases = [
+            ("", "", 0),
+            ("abc", "", 0),
+            ("", "abc", 0),
+            ("abcdef", "abcdef", 6),
+            ("abcabba", "cbabac", 4),
+            ("xaybzc", "abc", 3),
+            ("abcdefgh", "hgfedcba", 1),
+            ("aaaaabbbbb", "bbbbbaaaaa", 5),
+        ];
+
+        for (old, new, lcs_len) in cases.iter() {
+            let (old, new) = (comments(old), comments(new));
+            let diff = diff_lines(&old, &new);
+
+            assert_eq!(apply_diff(&old, &new, &diff), *lcs_len);
+        }
+    }
+}
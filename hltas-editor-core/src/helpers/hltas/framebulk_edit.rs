@@ -0,0 +1,44 @@
+use std::num::NonZeroU32;
+
+use hltas::types::FrameBulk;
+
+/// Splits the framebulk so the second part starts at `frame`
+///
+/// * The console command only stays on the first part so it doesn't run twice
+/// * Returns `None` if `frame` isn't inside the framebulk
+pub fn split_framebulk(framebulk: &FrameBulk, frame: u32) -> Option<(FrameBulk, FrameBulk)> {
+    let first_frame_count = NonZeroU32::new(frame)?;
+    let second_frame_count = NonZeroU32::new(framebulk.frame_count.get().checked_sub(frame)?)?;
+
+    let mut first = framebulk.to_owned();
+    first.frame_count = first_frame_count;
+
+    let mut second = framebulk.to_owned();
+    second.frame_count = second_frame_count;
+    second.console_command = None;
+
+    Some((first, second))
+}
+
+/// Merges two framebulks that are the same except for the frame count, undoing `split_framebulk`
+///
+/// * The console command of the first framebulk is kept, the second can't have one since it would run one time less
+/// * Returns `None` if the framebulks differ or the summed frame count overflows
+pub fn merge_framebulks(first: &FrameBulk, second: &FrameBulk) -> Option<FrameBulk> {
+    if second.console_command.is_some() {
+        return None;
+    }
+    let frame_count = first.frame_count.checked_add(second.frame_count.get())?;
+
+    let mut second = second.to_owned();
+    second.frame_count = first.frame_count;
+    second.console_command = first.console_command.to_owned();
+    if *first != second {
+        return None;
+    }
+
+    let mut merged = first.to_owned();
+    merged.frame_count = frame_count;
+
+    Some(merged)
+}
@@ -0,0 +1,766 @@
+use std::num::NonZeroU32;
+
+use hltas::{
+    types::{
+        ActionKeys, AutoActions, AutoMovement, Button, Buttons, Change, ChangeTarget,
+        DuckBeforeCollision, DuckBeforeGround, DuckWhenJump, FrameBulk, JumpBug, LeaveGroundAction,
+        LeaveGroundActionSpeed, LeaveGroundActionType, Line, MovementKeys, Properties, Seeds,
+        StrafeDir, StrafeSettings, StrafeType, Times, VectorialStrafingConstraints,
+    },
+    HLTAS,
+};
+use serde::{Deserialize, Serialize};
+
+/// Writes the hltas as pretty printed json
+pub fn hltas_to_json(hltas: &HLTAS) -> String {
+    serde_json::to_string_pretty(&HLTASData::from(hltas.to_owned())).unwrap()
+}
+
+/// Reads a hltas from json, which isn't checked to be a valid hltas, see `check_hltas`
+pub fn json_to_hltas(json: &str) -> Result<HLTAS, serde_json::Error> {
+    Ok(serde_json::from_str::<HLTASData>(json)?.into())
+}
+
+/// Serializable form of `HLTAS`
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct HLTASData {
+    pub properties: PropertiesData,
+    pub lines: Vec<LineData>,
+}
+
+impl From<HLTAS> for HLTASData {
+    fn from(hltas: HLTAS) -> Self {
+        Self {
+            properties: hltas.properties.into(),
+            lines: hltas.lines.into_iter().map(LineData::from).collect(),
+        }
+    }
+}
+
+impl From<HLTASData> for HLTAS {
+    fn from(hltas_data: HLTASData) -> Self {
+        Self {
+            properties: hltas_data.properties.into(),
+            lines: hltas_data.lines.into_iter().map(Line::from).collect(),
+        }
+    }
+}
+
+/// Serializable form of `Properties`
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PropertiesData {
+    pub demo: Option<String>,
+    pub save: Option<String>,
+    pub frametime_0ms: Option<String>,
+    pub seeds: Option<SeedsData>,
+    pub hlstrafe_version: Option<NonZeroU32>,
+    pub load_command: Option<String>,
+}
+
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+pub struct SeedsData {
+    pub shared: u32,
+    pub non_shared: i64,
+}
+
+impl From<Properties> for PropertiesData {
+    fn from(properties: Properties) -> Self {
+        Self {
+            demo: properties.demo,
+            save: properties.save,
+            frametime_0ms: properties.frametime_0ms,
+            seeds: properties.seeds.map(|seeds| SeedsData {
+                shared: seeds.shared,
+                non_shared: seeds.non_shared,
+            }),
+            hlstrafe_version: properties.hlstrafe_version,
+            load_command: properties.load_command,
+        }
+    }
+}
+
+impl From<PropertiesData> for Properties {
+    fn from(properties: PropertiesData) -> Self {
+        Self {
+            demo: properties.demo,
+            save: properties.save,
+            frametime_0ms: properties.frametime_0ms,
+            seeds: properties.seeds.map(|seeds| Seeds {
+                shared: seeds.shared,
+                non_shared: seeds.non_shared,
+            }),
+            hlstrafe_version: properties.hlstrafe_version,
+            load_command: properties.load_command,
+        }
+    }
+}
+
+/// Serializable form of `Line`, tagged by the line type
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum LineData {
+    FrameBulk(FrameBulkData),
+    Save {
+        name: String,
+    },
+    SharedSeed {
+        seed: u32,
+    },
+    Buttons {
+        buttons: Option<ButtonsData>,
+    },
+    #[serde(rename = "lgagst_min_speed")]
+    LGAGSTMinSpeed {
+        speed: f32,
+    },
+    Reset {
+        non_shared_seed: i64,
+    },
+    Comment {
+        text: String,
+    },
+    VectorialStrafing {
+        enabled: bool,
+    },
+    VectorialStrafingConstraints {
+        constraints: VectorialStrafingConstraintsData,
+    },
+    Change {
+        target: ChangeTargetData,
+        final_value: f32,
+        over: f32,
+    },
+    TargetYawOverride {
+        yaws: Vec<f32>,
+    },
+}
+
+impl From<Line> for LineData {
+    fn from(line: Line) -> Self {
+        match line {
+            Line::FrameBulk(framebulk) => LineData::FrameBulk(framebulk.into()),
+            Line::Save(name) => LineData::Save { name },
+            Line::SharedSeed(seed) => LineData::SharedSeed { seed },
+            Line::Buttons(buttons) => LineData::Buttons {
+                buttons: match buttons {
+                    Buttons::Reset => None,
+                    Buttons::Set {
+                        air_left,
+                        air_right,
+                        ground_left,
+                        ground_right,
+                    } => Some(ButtonsData {
+                        air_left: air_left.into(),
+                        air_right: air_right.into(),
+                        ground_left: ground_left.into(),
+                        ground_right: ground_right.into(),
+                    }),
+                },
+            },
+            Line::LGAGSTMinSpeed(speed) => LineData::LGAGSTMinSpeed { speed },
+            Line::Reset { non_shared_seed } => LineData::Reset { non_shared_seed },
+            Line::Comment(text) => LineData::Comment { text },
+            Line::VectorialStrafing(enabled) => LineData::VectorialStrafing { enabled },
+            Line::VectorialStrafingConstraints(constraints) => {
+                LineData::VectorialStrafingConstraints {
+                    constraints: constraints.into(),
+                }
+            }
+            Line::Change(change) => LineData::Change {
+                target: change.target.into(),
+                final_value: change.final_value,
+                over: change.over,
+            },
+            Line::TargetYawOverride(yaws) => LineData::TargetYawOverride { yaws },
+        }
+    }
+}
+
+impl From<LineData> for Line {
+    fn from(line: LineData) -> Self {
+        match line {
+            LineData::FrameBulk(framebulk) => Line::FrameBulk(framebulk.into()),
+            LineData::Save { name } => Line::Save(name),
+            LineData::SharedSeed { seed } => Line::SharedSeed(seed),
+            LineData::Buttons { buttons } => Line::Buttons(match buttons {
+                Some(buttons) => Buttons::Set {
+                    air_left: buttons.air_left.into(),
+                    air_right: buttons.air_right.into(),
+                    ground_left: buttons.ground_left.into(),
+                    ground_right: buttons.ground_right.into(),
+                },
+                None => Buttons::Reset,
+            }),
+            LineData::LGAGSTMinSpeed { speed } => Line::LGAGSTMinSpeed(speed),
+            LineData::Reset { non_shared_seed } => Line::Reset { non_shared_seed },
+            LineData::Comment { text } => Line::Comment(text),
+            LineData::VectorialStrafing { enabled } => Line::VectorialStrafing(enabled),
+            LineData::VectorialStrafingConstraints { constraints } => {
+                Line::VectorialStrafingConstraints(constraints.into())
+            }
+            LineData::Change {
+                target,
+                final_value,
+                over,
+            } => Line::Change(Change {
+                target: target.into(),
+                final_value,
+                over,
+            }),
+            LineData::TargetYawOverride { yaws } => Line::TargetYawOverride(yaws),
+        }
+    }
+}
+
+/// Strafing buttons, `None` in `LineData::Buttons` resets them
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+pub struct ButtonsData {
+    pub air_left: ButtonData,
+    pub air_right: ButtonData,
+    pub ground_left: ButtonData,
+    pub ground_right: ButtonData,
+}
+
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ButtonData {
+    Forward,
+    ForwardLeft,
+    Left,
+    BackLeft,
+    Back,
+    BackRight,
+    Right,
+    ForwardRight,
+}
+
+impl From<Button> for ButtonData {
+    fn from(button: Button) -> Self {
+        match button {
+            Button::Forward => ButtonData::Forward,
+            Button::ForwardLeft => ButtonData::ForwardLeft,
+            Button::Left => ButtonData::Left,
+            Button::BackLeft => ButtonData::BackLeft,
+            Button::Back => ButtonData::Back,
+            Button::BackRight => ButtonData::BackRight,
+            Button::Right => ButtonData::Right,
+            Button::ForwardRight => ButtonData::ForwardRight,
+        }
+    }
+}
+
+impl From<ButtonData> for Button {
+    fn from(button: ButtonData) -> Self {
+        match button {
+            ButtonData::Forward => Button::Forward,
+            ButtonData::ForwardLeft => Button::ForwardLeft,
+            ButtonData::Left => Button::Left,
+            ButtonData::BackLeft => Button::BackLeft,
+            ButtonData::Back => Button::Back,
+            ButtonData::BackRight => Button::BackRight,
+            ButtonData::Right => Button::Right,
+            ButtonData::ForwardRight => Button::ForwardRight,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum VectorialStrafingConstraintsData {
+    VelocityYaw { tolerance: f32 },
+    AvgVelocityYaw { tolerance: f32 },
+    VelocityYawLocking { tolerance: f32 },
+    Yaw { yaw: f32, tolerance: f32 },
+    YawRange { from: f32, to: f32 },
+}
+
+impl From<VectorialStrafingConstraints> for VectorialStrafingConstraintsData {
+    fn from(constraints: VectorialStrafingConstraints) -> Self {
+        use VectorialStrafingConstraints::*;
+        match constraints {
+            VelocityYaw { tolerance } => Self::VelocityYaw { tolerance },
+            AvgVelocityYaw { tolerance } => Self::AvgVelocityYaw { tolerance },
+            VelocityYawLocking { tolerance } => Self::VelocityYawLocking { tolerance },
+            Yaw { yaw, tolerance } => Self::Yaw { yaw, tolerance },
+            YawRange { from, to } => Self::YawRange { from, to },
+        }
+    }
+}
+
+impl From<VectorialStrafingConstraintsData> for VectorialStrafingConstraints {
+    fn from(constraints: VectorialStrafingConstraintsData) -> Self {
+        use VectorialStrafingConstraintsData::*;
+        match constraints {
+            VelocityYaw { tolerance } => Self::VelocityYaw { tolerance },
+            AvgVelocityYaw { tolerance } => Self::AvgVelocityYaw { tolerance },
+            VelocityYawLocking { tolerance } => Self::VelocityYawLocking { tolerance },
+            Yaw { yaw, tolerance } => Self::Yaw { yaw, tolerance },
+            YawRange { from, to } => Self::YawRange { from, to },
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChangeTargetData {
+    Yaw,
+    Pitch,
+    VectorialStrafingYaw,
+}
+
+impl From<ChangeTarget> for ChangeTargetData {
+    fn from(target: ChangeTarget) -> Self {
+        match target {
+            ChangeTarget::Yaw => ChangeTargetData::Yaw,
+            ChangeTarget::Pitch => ChangeTargetData::Pitch,
+            ChangeTarget::VectorialStrafingYaw => ChangeTargetData::VectorialStrafingYaw,
+        }
+    }
+}
+
+impl From<ChangeTargetData> for ChangeTarget {
+    fn from(target: ChangeTargetData) -> Self {
+        match target {
+            ChangeTargetData::Yaw => ChangeTarget::Yaw,
+            ChangeTargetData::Pitch => ChangeTarget::Pitch,
+            ChangeTargetData::VectorialStrafingYaw => ChangeTarget::VectorialStrafingYaw,
+        }
+    }
+}
+
+/// Serializable form of `FrameBulk`, keys that aren't pressed and auto actions that aren't used can be left out
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct FrameBulkData {
+    pub frame_time: String,
+    pub frame_count: NonZeroU32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub movement: Option<AutoMovementData>,
+    #[serde(default)]
+    pub movement_keys: Vec<MovementKeyData>,
+    #[serde(default)]
+    pub action_keys: Vec<ActionKeyData>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub leave_ground_action: Option<LeaveGroundActionData>,
+    /// Times of the jumpbug
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub jump_bug: Option<TimesData>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub duck_before_collision: Option<DuckBeforeCollisionData>,
+    /// Times of the duck before ground
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub duck_before_ground: Option<TimesData>,
+    /// Times of the duck when jump
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub duck_when_jump: Option<TimesData>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pitch: Option<f32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub console_command: Option<String>,
+}
+
+impl From<FrameBulk> for FrameBulkData {
+    fn from(framebulk: FrameBulk) -> Self {
+        let auto_actions = framebulk.auto_actions;
+
+        Self {
+            frame_time: framebulk.frame_time,
+            frame_count: framebulk.frame_count,
+            movement: auto_actions.movement.map(AutoMovementData::from),
+            movement_keys: MovementKeyData::from_keys(framebulk.movement_keys),
+            action_keys: ActionKeyData::from_keys(framebulk.action_keys),
+            leave_ground_action: auto_actions
+                .leave_ground_action
+                .map(LeaveGroundActionData::from),
+            jump_bug: auto_actions.jump_bug.map(|jump_bug| jump_bug.times.into()),
+            duck_before_collision: auto_actions.duck_before_collision.map(
+                |duck_before_collision| DuckBeforeCollisionData {
+                    times: duck_before_collision.times.into(),
+                    including_ceilings: duck_before_collision.including_ceilings,
+                },
+            ),
+            duck_before_ground: auto_actions
+                .duck_before_ground
+                .map(|duck_before_ground| duck_before_ground.times.into()),
+            duck_when_jump: auto_actions
+                .duck_when_jump
+                .map(|duck_when_jump| duck_when_jump.times.into()),
+            pitch: framebulk.pitch,
+            console_command: framebulk.console_command,
+        }
+    }
+}
+
+impl From<FrameBulkData> for FrameBulk {
+    fn from(framebulk: FrameBulkData) -> Self {
+        Self {
+            auto_actions: AutoActions {
+                movement: framebulk.movement.map(AutoMovement::from),
+                leave_ground_action: framebulk.leave_ground_action.map(LeaveGroundAction::from),
+                jump_bug: framebulk.jump_bug.map(|times| JumpBug {
+                    times: times.into(),
+                }),
+                duck_before_collision: framebulk.duck_before_collision.map(
+                    |duck_before_collision| DuckBeforeCollision {
+                        times: duck_before_collision.times.into(),
+                        including_ceilings: duck_before_collision.including_ceilings,
+                    },
+                ),
+                duck_before_ground: framebulk.duck_before_ground.map(|times| DuckBeforeGround {
+                    times: times.into(),
+                }),
+                duck_when_jump: framebulk.duck_when_jump.map(|times| DuckWhenJump {
+                    times: times.into(),
+                }),
+            },
+            movement_keys: MovementKeyData::to_keys(&framebulk.movement_keys),
+            action_keys: ActionKeyData::to_keys(&framebulk.action_keys),
+            frame_time: framebulk.frame_time,
+            pitch: framebulk.pitch,
+            frame_count: framebulk.frame_count,
+            console_command: framebulk.console_command,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MovementKeyData {
+    Forward,
+    Left,
+    Right,
+    Back,
+    Up,
+    Down,
+}
+
+impl MovementKeyData {
+    fn from_keys(keys: MovementKeys) -> Vec<Self> {
+        [
+            (keys.forward, MovementKeyData::Forward),
+            (keys.left, MovementKeyData::Left),
+            (keys.right, MovementKeyData::Right),
+            (keys.back, MovementKeyData::Back),
+            (keys.up, MovementKeyData::Up),
+            (keys.down, MovementKeyData::Down),
+        ]
+        .iter()
+        .filter_map(|(pressed, key)| if *pressed { Some(*key) } else { None })
+        .collect()
+    }
+
+    fn to_keys(keys: &[Self]) -> MovementKeys {
+        MovementKeys {
+            forward: keys.contains(&MovementKeyData::Forward),
+            left: keys.contains(&MovementKeyData::Left),
+            right: keys.contains(&MovementKeyData::Right),
+            back: keys.contains(&MovementKeyData::Back),
+            up: keys.contains(&MovementKeyData::Up),
+            down: keys.contains(&MovementKeyData::Down),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ActionKeyData {
+    Jump,
+    Duck,
+    Use,
+    Attack1,
+    Attack2,
+    Reload,
+}
+
+impl ActionKeyData {
+    fn from_keys(keys: ActionKeys) -> Vec<Self> {
+        [
+            (keys.jump, ActionKeyData::Jump),
+            (keys.duck, ActionKeyData::Duck),
+            (keys.use_, ActionKeyData::Use),
+            (keys.attack_1, ActionKeyData::Attack1),
+            (keys.attack_2, ActionKeyData::Attack2),
+            (keys.reload, ActionKeyData::Reload),
+        ]
+        .iter()
+        .filter_map(|(pressed, key)| if *pressed { Some(*key) } else { None })
+        .collect()
+    }
+
+    fn to_keys(keys: &[Self]) -> ActionKeys {
+        ActionKeys {
+            jump: keys.contains(&ActionKeyData::Jump),
+            duck: keys.contains(&ActionKeyData::Duck),
+            use_: keys.contains(&ActionKeyData::Use),
+            attack_1: keys.contains(&ActionKeyData::Attack1),
+            attack_2: keys.contains(&ActionKeyData::Attack2),
+            reload: keys.contains(&ActionKeyData::Reload),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum AutoMovementData {
+    SetYaw {
+        yaw: f32,
+    },
+    Strafe {
+        strafe_type: StrafeTypeData,
+        dir: StrafeDirData,
+    },
+}
+
+impl From<AutoMovement> for AutoMovementData {
+    fn from(movement: AutoMovement) -> Self {
+        match movement {
+            AutoMovement::SetYaw(yaw) => AutoMovementData::SetYaw { yaw },
+            AutoMovement::Strafe(strafe_settings) => AutoMovementData::Strafe {
+                strafe_type: strafe_settings.type_.into(),
+                dir: strafe_settings.dir.into(),
+            },
+        }
+    }
+}
+
+impl From<AutoMovementData> for AutoMovement {
+    fn from(movement: AutoMovementData) -> Self {
+        match movement {
+            AutoMovementData::SetYaw { yaw } => AutoMovement::SetYaw(yaw),
+            AutoMovementData::Strafe { strafe_type, dir } => AutoMovement::Strafe(StrafeSettings {
+                type_: strafe_type.into(),
+                dir: dir.into(),
+            }),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StrafeTypeData {
+    MaxAccel,
+    MaxAngle,
+    MaxDeccel,
+    ConstSpeed,
+}
+
+impl From<StrafeType> for StrafeTypeData {
+    fn from(strafe_type: StrafeType) -> Self {
+        match strafe_type {
+            StrafeType::MaxAccel => StrafeTypeData::MaxAccel,
+            StrafeType::MaxAngle => StrafeTypeData::MaxAngle,
+            StrafeType::MaxDeccel => StrafeTypeData::MaxDeccel,
+            StrafeType::ConstSpeed => StrafeTypeData::ConstSpeed,
+        }
+    }
+}
+
+impl From<StrafeTypeData> for StrafeType {
+    fn from(strafe_type: StrafeTypeData) -> Self {
+        match strafe_type {
+            StrafeTypeData::MaxAccel => StrafeType::MaxAccel,
+            StrafeTypeData::MaxAngle => StrafeType::MaxAngle,
+            StrafeTypeData::MaxDeccel => StrafeType::MaxDeccel,
+            StrafeTypeData::ConstSpeed => StrafeType::ConstSpeed,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum StrafeDirData {
+    Left,
+    Right,
+    Best,
+    Yaw { yaw: f32 },
+    Point { x: f32, y: f32 },
+    Line { yaw: f32 },
+}
+
+impl From<StrafeDir> for StrafeDirData {
+    fn from(dir: StrafeDir) -> Self {
+        match dir {
+            StrafeDir::Left => StrafeDirData::Left,
+            StrafeDir::Right => StrafeDirData::Right,
+            StrafeDir::Best => StrafeDirData::Best,
+            StrafeDir::Yaw(yaw) => StrafeDirData::Yaw { yaw },
+            StrafeDir::Point { x, y } => StrafeDirData::Point { x, y },
+            StrafeDir::Line { yaw } => StrafeDirData::Line { yaw },
+        }
+    }
+}
+
+impl From<StrafeDirData> for StrafeDir {
+    fn from(dir: StrafeDirData) -> Self {
+        match dir {
+            StrafeDirData::Left => StrafeDir::Left,
+            StrafeDirData::Right => StrafeDir::Right,
+            StrafeDirData::Best => StrafeDir::Best,
+            StrafeDirData::Yaw { yaw } => StrafeDir::Yaw(yaw),
+            StrafeDirData::Point { x, y } => StrafeDir::Point { x, y },
+            StrafeDirData::Line { yaw } => StrafeDir::Line { yaw },
+        }
+    }
+}
+
+/// Number of times an auto action is done, `None` is unlimited within the framebulk
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+pub struct TimesData {
+    pub times: Option<NonZeroU32>,
+}
+
+impl From<Times> for TimesData {
+    fn from(times: Times) -> Self {
+        Self {
+            times: match times {
+                Times::UnlimitedWithinFrameBulk => None,
+                Times::Limited(times) => Some(times),
+            },
+        }
+    }
+}
+
+impl From<TimesData> for Times {
+    fn from(times: TimesData) -> Self {
+        match times.times {
+            Some(times) => Times::Limited(times),
+            None => Times::UnlimitedWithinFrameBulk,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+pub struct LeaveGroundActionData {
+    pub speed: LeaveGroundActionSpeedData,
+    pub times: TimesData,
+    pub ducktap: bool,
+    /// Only used with `ducktap`
+    #[serde(default)]
+    pub zero_ms: bool,
+}
+
+impl From<LeaveGroundAction> for LeaveGroundActionData {
+    fn from(leave_ground_action: LeaveGroundAction) -> Self {
+        let (ducktap, zero_ms) = match leave_ground_action.type_ {
+            LeaveGroundActionType::Jump => (false, false),
+            LeaveGroundActionType::DuckTap { zero_ms } => (true, zero_ms),
+        };
+
+        Self {
+            speed: leave_ground_action.speed.into(),
+            times: leave_ground_action.times.into(),
+            ducktap,
+            zero_ms,
+        }
+    }
+}
+
+impl From<LeaveGroundActionData> for LeaveGroundAction {
+    fn from(leave_ground_action: LeaveGroundActionData) -> Self {
+        Self {
+            speed: leave_ground_action.speed.into(),
+            times: leave_ground_action.times.into(),
+            type_: if leave_ground_action.ducktap {
+                LeaveGroundActionType::DuckTap {
+                    zero_ms: leave_ground_action.zero_ms,
+                }
+            } else {
+                LeaveGroundActionType::Jump
+            },
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LeaveGroundActionSpeedData {
+    Any,
+    Optimal,
+    OptimalWithFullMaxspeed,
+}
+
+impl From<LeaveGroundActionSpeed> for LeaveGroundActionSpeedData {
+    fn from(speed: LeaveGroundActionSpeed) -> Self {
+        match speed {
+            LeaveGroundActionSpeed::Any => LeaveGroundActionSpeedData::Any,
+            LeaveGroundActionSpeed::Optimal => LeaveGroundActionSpeedData::Optimal,
+            LeaveGroundActionSpeed::OptimalWithFullMaxspeed => {
+                LeaveGroundActionSpeedData::OptimalWithFullMaxspeed
+            }
+        }
+    }
+}
+
+impl From<LeaveGroundActionSpeedData> for LeaveGroundActionSpeed {
+    fn from(speed: LeaveGroundActionSpeedData) -> Self {
+        match speed {
+            LeaveGroundActionSpeedData::Any => LeaveGroundActionSpeed::Any,
+            LeaveGroundActionSpeedData::Optimal => LeaveGroundActionSpeed::Optimal,
+            LeaveGroundActionSpeedData::OptimalWithFullMaxspeed => {
+                LeaveGroundActionSpeed::OptimalWithFullMaxspeed
+            }
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+pub struct DuckBeforeCollisionData {
+    pub times: TimesData,
+    pub including_ceilings: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers::hltas::{check_hltas, hltas_to_str, tests::EVERY_LINE_TYPE};
+
+    #[test]
+    fn round_trip() {
+        let hltas = HLTAS::from_str(EVERY_LINE_TYPE).unwrap();
+        let imported = json_to_hltas(&hltas_to_json(&hltas)).unwrap();
+
+        assert_eq!(hltas_to_str(&imported), hltas_to_str(&hltas));
+        assert_eq!(imported, hltas);
+    }
+
+    #[test]
+    fn unused_fields_can_be_left_out() {
+        let json = r#"{
+            "properties": { "demo": "run" },
+            "lines": [
+                { "type": "frame_bulk", "frame_time": "0.001", "frame_count": 10,
+                  "movement_keys": ["forward"] },
+                { "type": "comment", "text": " done" }
+            ]
+        }"#;
+        let hltas = json_to_hltas(json).unwrap();
+
+        assert_eq!(
+            hltas_to_str(&hltas),
+            "version 1\ndemo run\nframes\n----------|f-----|------|0.001|-|-|10\n// done\n"
+        );
+    }
+
+    #[test]
+    fn invalid_json() {
+        assert!(json_to_hltas("{}").is_err());
+        assert!(json_to_hltas(r#"{ "properties": {}, "lines": [{ "type": "jump" }] }"#).is_err());
+    }
+
+    #[test]
+    fn invalid_hltas_fails_the_check() {
+        let json = r#"{
+            "properties": {},
+            "lines": [{ "type": "frame_bulk", "frame_time": "fast", "frame_count": 1 }]
+        }"#;
+        let hltas = json_to_hltas(json).unwrap();
+
+        assert!(check_hltas(&hltas).is_err());
+        assert_eq!(
+            check_hltas(
+                &json_to_hltas(&hltas_to_json(&HLTAS::from_str(EVERY_LINE_TYPE).unwrap())).unwrap()
+            ),
+            Ok(())
+        );
+    }
+}
@@ -0,0 +1,486 @@
+use hltas::{
+    types::{AutoMovement, Buttons, FrameBulk, LeaveGroundActionType, Line, Properties},
+    HLTAS,
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    Warning,
+    Error,
+}
+
+/// What the diagnostic is about, each kind has a single message
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DiagnosticKind {
+    /// Jump key held while auto jump or ducktap is on
+    JumpWithLeaveGroundAction,
+    /// Jump or duck key held while jumpbug is on
+    KeysWithJumpBug,
+    ZeroMsDuckTapWithoutFrametime,
+    InvalidFrametime,
+    /// Change line before any strafing or target yaw override
+    ChangeWithoutTarget,
+    /// Strafing buttons that are replaced or reach the end without any strafing
+    ButtonsWithoutStrafing,
+    EmptyConsoleCommand,
+}
+
+impl DiagnosticKind {
+    pub const ALL: [DiagnosticKind; 7] = [
+        DiagnosticKind::JumpWithLeaveGroundAction,
+        DiagnosticKind::KeysWithJumpBug,
+        DiagnosticKind::ZeroMsDuckTapWithoutFrametime,
+        DiagnosticKind::InvalidFrametime,
+        DiagnosticKind::ChangeWithoutTarget,
+        DiagnosticKind::ButtonsWithoutStrafing,
+        DiagnosticKind::EmptyConsoleCommand,
+    ];
+
+    pub fn severity(&self) -> Severity {
+        match self {
+            DiagnosticKind::InvalidFrametime => Severity::Error,
+            _ => Severity::Warning,
+        }
+    }
+
+    /// Locale id of the message
+    pub fn id(&self) -> &'static str {
+        match self {
+            DiagnosticKind::JumpWithLeaveGroundAction => "lint-jump-with-leave-ground-action",
+            DiagnosticKind::KeysWithJumpBug => "lint-keys-with-jumpbug",
+            DiagnosticKind::ZeroMsDuckTapWithoutFrametime => "lint-zero-ms-without-frametime",
+            DiagnosticKind::InvalidFrametime => "lint-invalid-frametime",
+            DiagnosticKind::ChangeWithoutTarget => "lint-change-without-target",
+            DiagnosticKind::ButtonsWithoutStrafing => "lint-buttons-without-strafing",
+            DiagnosticKind::EmptyConsoleCommand => "lint-empty-console-command",
+        }
+    }
+
+    /// Fixes that can be applied to diagnostics of this kind
+    pub fn quick_fixes(&self) -> &'static [QuickFix] {
+        match self {
+            DiagnosticKind::JumpWithLeaveGroundAction => {
+                &[QuickFix::DropJumpKey, QuickFix::DropLeaveGroundAction]
+            }
+            DiagnosticKind::KeysWithJumpBug => &[QuickFix::DropJumpAndDuckKeys],
+            DiagnosticKind::ZeroMsDuckTapWithoutFrametime => {
+                &[QuickFix::EnableZeroMsFrametime, QuickFix::DisableZeroMs]
+            }
+            DiagnosticKind::InvalidFrametime => &[QuickFix::NormalizeFrametime],
+            DiagnosticKind::ChangeWithoutTarget | DiagnosticKind::ButtonsWithoutStrafing => &[],
+            DiagnosticKind::EmptyConsoleCommand => &[QuickFix::RemoveConsoleCommand],
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Diagnostic {
+    pub line_index: usize,
+    pub kind: DiagnosticKind,
+}
+
+impl Diagnostic {
+    pub fn severity(&self) -> Severity {
+        self.kind.severity()
+    }
+}
+
+/// Values filled in by the quick fixes
+#[derive(Clone, Debug, PartialEq)]
+pub struct FixDefaults {
+    pub frametime: String,
+    pub frametime_0ms: String,
+}
+
+/// Automated fix for a diagnostic
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum QuickFix {
+    DropJumpKey,
+    DropLeaveGroundAction,
+    DropJumpAndDuckKeys,
+    /// Sets the 0ms frametime property, which applies to the whole file
+    EnableZeroMsFrametime,
+    DisableZeroMs,
+    NormalizeFrametime,
+    RemoveConsoleCommand,
+}
+
+impl QuickFix {
+    /// Locale id of the fix
+    pub fn id(&self) -> &'static str {
+        match self {
+            QuickFix::DropJumpKey => "quick-fix-drop-jump-key",
+            QuickFix::DropLeaveGroundAction => "quick-fix-drop-leave-ground-action",
+            QuickFix::DropJumpAndDuckKeys => "quick-fix-drop-jump-duck-keys",
+            QuickFix::EnableZeroMsFrametime => "quick-fix-enable-zero-ms-frametime",
+            QuickFix::DisableZeroMs => "quick-fix-disable-zero-ms",
+            QuickFix::NormalizeFrametime => "quick-fix-normalize-frametime",
+            QuickFix::RemoveConsoleCommand => "quick-fix-remove-console-command",
+        }
+    }
+
+    /// Applies the fix for the line at `line_index`, does nothing if the line isn't a framebulk
+    pub fn apply(&self, hltas: &mut HLTAS, line_index: usize, defaults: &FixDefaults) {
+        if let QuickFix::EnableZeroMsFrametime = self {
+            if hltas.properties.frametime_0ms.is_none() {
+                hltas.properties.frametime_0ms = Some(defaults.frametime_0ms.to_owned());
+            }
+            return;
+        }
+
+        let framebulk = match hltas.lines.get_mut(line_index) {
+            Some(Line::FrameBulk(framebulk)) => framebulk,
+            _ => return,
+        };
+
+        match self {
+            QuickFix::DropJumpKey => framebulk.action_keys.jump = false,
+            QuickFix::DropLeaveGroundAction => framebulk.auto_actions.leave_ground_action = None,
+            QuickFix::DropJumpAndDuckKeys => {
+                framebulk.action_keys.jump = false;
+                framebulk.action_keys.duck = false;
+            }
+            QuickFix::EnableZeroMsFrametime => (),
+            QuickFix::DisableZeroMs => {
+                if let Some(leave_ground_action) = &mut framebulk.auto_actions.leave_ground_action {
+                    if let LeaveGroundActionType::DuckTap { zero_ms } =
+                        &mut leave_ground_action.type_
+                    {
+                        *zero_ms = false;
+                    }
+                }
+            }
+            QuickFix::NormalizeFrametime => framebulk.frame_time = defaults.frametime.to_owned(),
+            QuickFix::RemoveConsoleCommand => framebulk.console_command = None,
+        }
+    }
+}
+
+fn is_strafing(framebulk: &FrameBulk) -> bool {
+    matches!(
+        framebulk.auto_actions.movement,
+        Some(AutoMovement::Strafe(_))
+    )
+}
+
+/// Checks that only need the framebulk itself
+fn framebulk_diagnostics(framebulk: &FrameBulk, properties: &Properties) -> Vec<DiagnosticKind> {
+    let mut kinds = Vec::new();
+    let auto_actions = &framebulk.auto_actions;
+    let action_keys = &framebulk.action_keys;
+
+    if action_keys.jump && auto_actions.leave_ground_action.is_some() {
+        kinds.push(DiagnosticKind::JumpWithLeaveGroundAction);
+    }
+
+    if (action_keys.jump || action_keys.duck) && auto_actions.jump_bug.is_some() {
+        kinds.push(DiagnosticKind::KeysWithJumpBug);
+    }
+
+    if let Some(leave_ground_action) = auto_actions.leave_ground_action {
+        if leave_ground_action.type_ == (LeaveGroundActionType::DuckTap { zero_ms: true })
+            && properties.frametime_0ms.is_none()
+        {
+            kinds.push(DiagnosticKind::ZeroMsDuckTapWithoutFrametime);
+        }
+    }
+
+    match framebulk.frame_time.parse::<f32>() {
+        Ok(frametime) if frametime.is_finite() && frametime > 0.0 => (),
+        _ => kinds.push(DiagnosticKind::InvalidFrametime),
+    }
+
+    if let Some(console_command) = &framebulk.console_command {
+        if console_command.trim().is_empty() {
+            kinds.push(DiagnosticKind::EmptyConsoleCommand);
+        }
+    }
+
+    kinds
+}
+
+/// Finds contradictory or suspicious lines, sorted by line index
+pub fn lint(hltas: &HLTAS) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+    // change lines need something to change before them
+    let mut has_change_target = false;
+    // buttons line that hasn't been used by a strafing framebulk yet
+    let mut unused_buttons = None;
+
+    for (line_index, line) in hltas.lines.iter().enumerate() {
+        match line {
+            Line::FrameBulk(framebulk) => {
+                diagnostics.extend(
+                    framebulk_diagnostics(framebulk, &hltas.properties)
+                        .into_iter()
+                        .map(|kind| Diagnostic { line_index, kind }),
+                );
+
+                if is_strafing(framebulk) {
+                    has_change_target = true;
+                    unused_buttons = None;
+                }
+            }
+            Line::TargetYawOverride(_) => has_change_target = true,
+            Line::Change(_) if !has_change_target => diagnostics.push(Diagnostic {
+                line_index,
+                kind: DiagnosticKind::ChangeWithoutTarget,
+            }),
+            Line::Buttons(buttons) => {
+                if let Some(buttons_index) = unused_buttons.take() {
+                    diagnostics.push(Diagnostic {
+                        line_index: buttons_index,
+                        kind: DiagnosticKind::ButtonsWithoutStrafing,
+                    });
+                }
+                if let Buttons::Set { .. } = buttons {
+                    unused_buttons = Some(line_index);
+                }
+            }
+            _ => (),
+        }
+    }
+
+    if let Some(buttons_index) = unused_buttons {
+        diagnostics.push(Diagnostic {
+            line_index: buttons_index,
+            kind: DiagnosticKind::ButtonsWithoutStrafing,
+        });
+    }
+
+    // buttons diagnostics are found after the lines that follow them
+    diagnostics.sort_by_key(|diagnostic| diagnostic.line_index);
+
+    diagnostics
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hltas(lines: &str) -> HLTAS {
+        HLTAS::from_str(&format!("version 1\nframes\n{}", lines)).unwrap()
+    }
+
+    fn diagnostics(lines: &str) -> Vec<(usize, DiagnosticKind)> {
+        lint(&hltas(lines))
+            .into_iter()
+            .map(|diagnostic| (diagnostic.line_index, diagnostic.kind))
+            .collect()
+    }
+
+    fn defaults() -> FixDefaults {
+        FixDefaults {
+            frametime: "0.001".to_owned(),
+            frametime_0ms: "0.0000000001".to_owned(),
+        }
+    }
+
+    /// Applies the fix to the first line
+    fn fixed(fix: QuickFix, lines: &str) -> HLTAS {
+        let mut hltas = hltas(lines);
+        fix.apply(&mut hltas, 0, &defaults());
+        hltas
+    }
+
+    #[test]
+    fn clean_script() {
+        assert!(diagnostics(
+            "s02-------|------|------|0.001|-|-|1|echo\nbuttons 0 1 2 3\nchange yaw to 0 over 1 s\ns02-------|------|------|0.001|-|-|1\n"
+        )
+        .is_empty());
+    }
+
+    #[test]
+    fn jump_with_leave_ground_action() {
+        assert_eq!(
+            diagnostics("----j-----|------|j-----|0.001|-|-|1\n"),
+            vec![(0, DiagnosticKind::JumpWithLeaveGroundAction)]
+        );
+    }
+
+    #[test]
+    fn keys_with_jumpbug() {
+        assert_eq!(
+            diagnostics("------b---|------|-d----|0.001|-|-|1\n"),
+            vec![(0, DiagnosticKind::KeysWithJumpBug)]
+        );
+    }
+
+    #[test]
+    fn zero_ms_ducktap_without_frametime() {
+        let line = "-----D----|------|------|0.001|-|-|1\n";
+        assert_eq!(
+            diagnostics(line),
+            vec![(0, DiagnosticKind::ZeroMsDuckTapWithoutFrametime)]
+        );
+
+        let mut hltas = hltas(line);
+        hltas.properties.frametime_0ms = Some("0.0000000001".to_owned());
+        assert!(lint(&hltas).is_empty());
+    }
+
+    #[test]
+    fn invalid_frametime() {
+        let mut hltas = hltas("----------|------|------|0.001|-|-|1\n");
+
+        // the parser only accepts numbers, the rest comes from editing
+        for frametime in ["0", "-0.001", "nan", "inf", "abc"] {
+            if let Line::FrameBulk(framebulk) = &mut hltas.lines[0] {
+                framebulk.frame_time = frametime.to_owned();
+            }
+            assert_eq!(
+                lint(&hltas),
+                vec![Diagnostic {
+                    line_index: 0,
+                    kind: DiagnosticKind::InvalidFrametime,
+                }],
+                "{}",
+                frametime
+            );
+        }
+    }
+
+    #[test]
+    fn change_without_target() {
+        assert_eq!(
+            diagnostics(
+                "change yaw to 0 over 1 s\ntarget_yaw_override 1\nchange yaw to 0 over 1 s\n"
+            ),
+            vec![(0, DiagnosticKind::ChangeWithoutTarget)]
+        );
+        assert!(
+            diagnostics("s02-------|------|------|0.001|-|-|1\nchange pitch to 0 over 1 s\n")
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn buttons_without_strafing() {
+        // replaced by another buttons line, then reaching the end
+        assert_eq!(
+            diagnostics("buttons 0 1 2 3\nbuttons 3 2 1 0\n----------|------|------|0.001|-|-|1\n"),
+            vec![
+                (0, DiagnosticKind::ButtonsWithoutStrafing),
+                (1, DiagnosticKind::ButtonsWithoutStrafing),
+            ]
+        );
+        assert!(
+            diagnostics("buttons 0 1 2 3\ns02-------|------|------|0.001|-|-|1\nbuttons\n")
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn empty_console_command() {
+        let mut hltas = hltas("----------|------|------|0.001|-|-|1\n");
+        if let Line::FrameBulk(framebulk) = &mut hltas.lines[0] {
+            framebulk.console_command = Some(" ".to_owned());
+        }
+        assert_eq!(
+            lint(&hltas),
+            vec![Diagnostic {
+                line_index: 0,
+                kind: DiagnosticKind::EmptyConsoleCommand,
+            }]
+        );
+    }
+
+    #[test]
+    fn drop_jump_key() {
+        assert_eq!(
+            fixed(
+                QuickFix::DropJumpKey,
+                "----j-----|------|jd----|0.001|-|-|1\n"
+            ),
+            hltas("----j-----|------|-d----|0.001|-|-|1\n")
+        );
+    }
+
+    #[test]
+    fn drop_leave_ground_action() {
+        assert_eq!(
+            fixed(
+                QuickFix::DropLeaveGroundAction,
+                "----j-----|------|j-----|0.001|-|-|1\n"
+            ),
+            hltas("----------|------|j-----|0.001|-|-|1\n")
+        );
+    }
+
+    #[test]
+    fn drop_jump_and_duck_keys() {
+        assert_eq!(
+            fixed(
+                QuickFix::DropJumpAndDuckKeys,
+                "------b---|------|jdu---|0.001|-|-|1\n"
+            ),
+            hltas("------b---|------|--u---|0.001|-|-|1\n")
+        );
+    }
+
+    #[test]
+    fn enable_zero_ms_frametime() {
+        let line = "-----D----|------|------|0.001|-|-|1\n";
+        let hltas = fixed(QuickFix::EnableZeroMsFrametime, line);
+        assert_eq!(
+            hltas.properties.frametime_0ms.as_deref(),
+            Some("0.0000000001")
+        );
+        assert!(lint(&hltas).is_empty());
+
+        // an existing frametime is kept
+        let mut hltas = hltas.to_owned();
+        hltas.properties.frametime_0ms = Some("0.000001".to_owned());
+        QuickFix::EnableZeroMsFrametime.apply(&mut hltas, 0, &defaults());
+        assert_eq!(hltas.properties.frametime_0ms.as_deref(), Some("0.000001"));
+    }
+
+    #[test]
+    fn disable_zero_ms() {
+        assert_eq!(
+            fixed(
+                QuickFix::DisableZeroMs,
+                "-----D----|------|------|0.001|-|-|1\n"
+            ),
+            hltas("-----d----|------|------|0.001|-|-|1\n")
+        );
+    }
+
+    #[test]
+    fn normalize_frametime() {
+        assert_eq!(
+            fixed(
+                QuickFix::NormalizeFrametime,
+                "----------|------|------|0|-|-|1\n"
+            ),
+            hltas("----------|------|------|0.001|-|-|1\n")
+        );
+    }
+
+    #[test]
+    fn remove_console_command() {
+        assert_eq!(
+            fixed(
+                QuickFix::RemoveConsoleCommand,
+                "----------|------|------|0.001|-|-|1|echo\n"
+            ),
+            hltas("----------|------|------|0.001|-|-|1\n")
+        );
+    }
+
+    #[test]
+    fn fixes_only_apply_to_framebulks() {
+        let lines = "// comment\n";
+        for fix in [
+            QuickFix::DropJumpKey,
+            QuickFix::DropLeaveGroundAction,
+            QuickFix::DropJumpAndDuckKeys,
+            QuickFix::DisableZeroMs,
+            QuickFix::NormalizeFrametime,
+            QuickFix::RemoveConsoleCommand,
+        ] {
+            assert_eq!(fixed(fix, lines), hltas(lines));
+        }
+    }
+}
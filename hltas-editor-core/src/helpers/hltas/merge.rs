@@ -0,0 +1,502 @@
+use hltas::{
+    types::{FrameBulk, Line, Properties},
+    HLTAS,
+};
+
+use super::diff::{diff_lines, DiffOp, FramebulkField};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Side {
+    Ours,
+    Theirs,
+}
+
+/// Replaces the conflicting lines as a whole
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ConflictResolution {
+    Ours,
+    Theirs,
+    OursThenTheirs,
+    TheirsThenOurs,
+}
+
+/// Framebulk field changed differently by both sides
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct FieldConflict {
+    /// Framebulk index inside the conflict
+    pub index: usize,
+    pub field: FramebulkField,
+    pub pick: Option<Side>,
+}
+
+/// Lines changed differently by both sides
+#[derive(Clone, Debug, PartialEq)]
+pub struct Conflict {
+    pub base: Vec<Line>,
+    pub ours: Vec<Line>,
+    pub theirs: Vec<Line>,
+    /// Index of the first line in our file
+    pub ours_index: usize,
+    /// Index of the first line in their file
+    pub theirs_index: usize,
+    /// Set if every side has the same amount of framebulks and nothing else
+    ///
+    /// * Fields changed by only one side are already merged, conflicting fields are ours until picked
+    pub merged_framebulks: Option<Vec<FrameBulk>>,
+    pub field_conflicts: Vec<FieldConflict>,
+    /// Overrides the field picks
+    pub resolution: Option<ConflictResolution>,
+}
+
+impl Conflict {
+    /// The lines that replace the conflict, `None` if not resolved yet
+    pub fn resolved_lines(&self) -> Option<Vec<Line>> {
+        let concat = |first: &[Line], second: &[Line]| [first, second].concat();
+
+        match self.resolution {
+            Some(ConflictResolution::Ours) => Some(self.ours.to_owned()),
+            Some(ConflictResolution::Theirs) => Some(self.theirs.to_owned()),
+            Some(ConflictResolution::OursThenTheirs) => Some(concat(&self.ours, &self.theirs)),
+            Some(ConflictResolution::TheirsThenOurs) => Some(concat(&self.theirs, &self.ours)),
+            None => {
+                let mut merged = self.merged_framebulks.to_owned()?;
+
+                for field_conflict in &self.field_conflicts {
+                    let picked_line = match field_conflict.pick? {
+                        Side::Ours => &self.ours[field_conflict.index],
+                        Side::Theirs => &self.theirs[field_conflict.index],
+                    };
+                    if let Line::FrameBulk(picked) = picked_line {
+                        field_conflict
+                            .field
+                            .copy_from(picked, &mut merged[field_conflict.index]);
+                    }
+                }
+
+                Some(merged.into_iter().map(Line::FrameBulk).collect())
+            }
+        }
+    }
+
+    pub fn is_resolved(&self) -> bool {
+        self.resolution.is_some()
+            || (self.merged_framebulks.is_some()
+                && self
+                    .field_conflicts
+                    .iter()
+                    .all(|field_conflict| field_conflict.pick.is_some()))
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum MergeChunk {
+    Resolved(Vec<Line>),
+    Conflict(Conflict),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum PropertiesMerge {
+    Resolved(Properties),
+    Conflict {
+        ours: Properties,
+        theirs: Properties,
+        pick: Option<Side>,
+    },
+}
+
+/// Picks the side that changed, `None` if both changed it differently
+fn merge_value<T: PartialEq + Clone>(base: &T, ours: &T, theirs: &T) -> Option<T> {
+    if ours == theirs || theirs == base {
+        Some(ours.to_owned())
+    } else if ours == base {
+        Some(theirs.to_owned())
+    } else {
+        None
+    }
+}
+
+/// Sets both sides to the merged value if it doesn't conflict
+fn merge_field<T: PartialEq + Clone>(base: &T, ours: &mut T, theirs: &mut T) {
+    if let Some(value) = merge_value(base, ours, theirs) {
+        *ours = value.to_owned();
+        *theirs = value;
+    }
+}
+
+/// Merges the properties field by field, the sides of a conflict only differ by the conflicting fields
+fn merge_properties(base: &Properties, ours: &Properties, theirs: &Properties) -> PropertiesMerge {
+    let mut ours = ours.to_owned();
+    let mut theirs = theirs.to_owned();
+
+    merge_field(&base.demo, &mut ours.demo, &mut theirs.demo);
+    merge_field(&base.save, &mut ours.save, &mut theirs.save);
+    merge_field(
+        &base.frametime_0ms,
+        &mut ours.frametime_0ms,
+        &mut theirs.frametime_0ms,
+    );
+    merge_field(&base.seeds, &mut ours.seeds, &mut theirs.seeds);
+    merge_field(
+        &base.hlstrafe_version,
+        &mut ours.hlstrafe_version,
+        &mut theirs.hlstrafe_version,
+    );
+    merge_field(
+        &base.load_command,
+        &mut ours.load_command,
+        &mut theirs.load_command,
+    );
+
+    if ours == theirs {
+        PropertiesMerge::Resolved(ours)
+    } else {
+        PropertiesMerge::Conflict {
+            ours,
+            theirs,
+            pick: None,
+        }
+    }
+}
+
+/// Index of the matching line on the other side for each base line
+fn matching_indexes(diff: &[DiffOp], base_len: usize) -> Vec<Option<usize>> {
+    let mut matching = vec![None; base_len];
+    for diff_op in diff {
+        if let DiffOp::Equal {
+            old_index,
+            new_index,
+        } = *diff_op
+        {
+            matching[old_index] = Some(new_index);
+        }
+    }
+    matching
+}
+
+fn push_resolved(chunks: &mut Vec<MergeChunk>, lines: &[Line]) {
+    if lines.is_empty() {
+        return;
+    }
+
+    match chunks.last_mut() {
+        Some(MergeChunk::Resolved(resolved)) => resolved.extend_from_slice(lines),
+        _ => chunks.push(MergeChunk::Resolved(lines.to_vec())),
+    }
+}
+
+/// Merges the framebulks field by field, returns the merged framebulks and the conflicting fields
+fn merge_framebulks(
+    base: &[Line],
+    ours: &[Line],
+    theirs: &[Line],
+) -> Option<(Vec<FrameBulk>, Vec<FieldConflict>)> {
+    if base.len() != ours.len() || base.len() != theirs.len() {
+        return None;
+    }
+
+    let mut merged = Vec::with_capacity(base.len());
+    let mut field_conflicts = Vec::new();
+
+    for (index, lines) in base.iter().zip(ours).zip(theirs).enumerate() {
+        let (base, ours, theirs) = match lines {
+            ((Line::FrameBulk(base), Line::FrameBulk(ours)), Line::FrameBulk(theirs)) => {
+                (base, ours, theirs)
+            }
+            _ => return None,
+        };
+
+        let mut framebulk = ours.to_owned();
+        for field in FramebulkField::ALL.iter() {
+            let ours_changed = field.differs(base, ours);
+            let theirs_changed = field.differs(base, theirs);
+
+            if theirs_changed && !ours_changed {
+                field.copy_from(theirs, &mut framebulk);
+            } else if theirs_changed && ours_changed && field.differs(ours, theirs) {
+                field_conflicts.push(FieldConflict {
+                    index,
+                    field: *field,
+                    pick: None,
+                });
+            }
+        }
+        merged.push(framebulk);
+    }
+
+    Some((merged, field_conflicts))
+}
+
+fn push_chunk(
+    chunks: &mut Vec<MergeChunk>,
+    base: &[Line],
+    (ours, ours_index): (&[Line], usize),
+    (theirs, theirs_index): (&[Line], usize),
+) {
+    if let Some(lines) = merge_value(&base, &ours, &theirs) {
+        push_resolved(chunks, lines);
+        return;
+    }
+
+    let (merged_framebulks, field_conflicts) = match merge_framebulks(base, ours, theirs) {
+        Some((merged, field_conflicts)) if field_conflicts.is_empty() => {
+            let merged = merged.into_iter().map(Line::FrameBulk).collect::<Vec<_>>();
+            push_resolved(chunks, &merged);
+            return;
+        }
+        Some((merged, field_conflicts)) => (Some(merged), field_conflicts),
+        None => (None, Vec::new()),
+    };
+
+    chunks.push(MergeChunk::Conflict(Conflict {
+        base: base.to_vec(),
+        ours: ours.to_vec(),
+        theirs: theirs.to_vec(),
+        ours_index,
+        theirs_index,
+        merged_framebulks,
+        field_conflicts,
+        resolution: None,
+    }));
+}
+
+/// Three-way merge of hltas files by lines
+///
+/// * Changes that don't overlap are merged automatically
+/// * Overlapping framebulk changes are merged by field if the framebulks line up
+#[derive(Clone, Debug, PartialEq)]
+pub struct Merge {
+    pub properties: PropertiesMerge,
+    pub chunks: Vec<MergeChunk>,
+}
+
+impl Merge {
+    pub fn new(base: &HLTAS, ours: &HLTAS, theirs: &HLTAS) -> Self {
+        let properties = merge_properties(&base.properties, &ours.properties, &theirs.properties);
+
+        let (base, ours, theirs) = (&base.lines, &ours.lines, &theirs.lines);
+        let base_to_ours = matching_indexes(&diff_lines(base, ours), base.len());
+        let base_to_theirs = matching_indexes(&diff_lines(base, theirs), base.len());
+
+        let mut chunks = Vec::new();
+        let (mut base_start, mut ours_start, mut theirs_start) = (0, 0, 0);
+
+        loop {
+            // next line both sides kept
+            let stable =
+                (base_start..base.len()).find_map(|i| match (base_to_ours[i], base_to_theirs[i]) {
+                    (Some(ours_index), Some(theirs_index)) => Some((i, ours_index, theirs_index)),
+                    _ => None,
+                });
+            let (base_end, ours_end, theirs_end) =
+                stable.unwrap_or((base.len(), ours.len(), theirs.len()));
+
+            push_chunk(
+                &mut chunks,
+                &base[base_start..base_end],
+                (&ours[ours_start..ours_end], ours_start),
+                (&theirs[theirs_start..theirs_end], theirs_start),
+            );
+
+            match stable {
+                Some((base_index, ours_index, theirs_index)) => {
+                    push_resolved(&mut chunks, &base[base_index..=base_index]);
+                    base_start = base_index + 1;
+                    ours_start = ours_index + 1;
+                    theirs_start = theirs_index + 1;
+                }
+                None => break,
+            }
+        }
+
+        Self { properties, chunks }
+    }
+
+    pub fn conflicts(&self) -> impl Iterator<Item = &Conflict> {
+        self.chunks.iter().filter_map(|chunk| match chunk {
+            MergeChunk::Conflict(conflict) => Some(conflict),
+            MergeChunk::Resolved(_) => None,
+        })
+    }
+
+    pub fn conflicts_mut(&mut self) -> impl Iterator<Item = &mut Conflict> {
+        self.chunks.iter_mut().filter_map(|chunk| match chunk {
+            MergeChunk::Conflict(conflict) => Some(conflict),
+            MergeChunk::Resolved(_) => None,
+        })
+    }
+
+    /// Conflicts left to resolve, including the properties
+    pub fn unresolved_count(&self) -> usize {
+        let properties_unresolved = matches!(
+            self.properties,
+            PropertiesMerge::Conflict { pick: None, .. }
+        );
+
+        self.conflicts()
+            .filter(|conflict| !conflict.is_resolved())
+            .count()
+            + properties_unresolved as usize
+    }
+
+    /// The merged hltas, `None` if there are unresolved conflicts
+    pub fn result(&self) -> Option<HLTAS> {
+        let properties = match &self.properties {
+            PropertiesMerge::Resolved(properties) => properties.to_owned(),
+            PropertiesMerge::Conflict { ours, theirs, pick } => match (*pick)? {
+                Side::Ours => ours.to_owned(),
+                Side::Theirs => theirs.to_owned(),
+            },
+        };
+
+        let mut lines = Vec::new();
+        for chunk in &self.chunks {
+            match chunk {
+                MergeChunk::Resolved(resolved) => lines.extend_from_slice(resolved),
+                MergeChunk::Conflict(conflict) => lines.extend(conflict.resolved_lines()?),
+            }
+        }
+
+        Some(HLTAS { properties, lines })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use hltas::types::Seeds;
+
+    use super::*;
+
+    fn hltas(lines: &str) -> HLTAS {
+        HLTAS::from_str(&format!("version 1\nframes\n{}", lines)).unwrap()
+    }
+
+    fn merge(base: &str, ours: &str, theirs: &str) -> Merge {
+        Merge::new(&hltas(base), &hltas(ours), &hltas(theirs))
+    }
+
+    fn only_conflict(merge: &mut Merge) -> &mut Conflict {
+        assert_eq!(merge.conflicts().count(), 1);
+        merge.conflicts_mut().next().unwrap()
+    }
+
+    #[test]
+    fn non_overlapping_edits() {
+        let merge = merge(
+            "// a\n// b\n// c\n// d\n",
+            "// a ours\n// b\n// c\n// d\n",
+            "// a\n// b\n// c\n// d\n// e\n",
+        );
+
+        assert_eq!(merge.unresolved_count(), 0);
+        assert_eq!(
+            merge.result(),
+            Some(hltas("// a ours\n// b\n// c\n// d\n// e\n"))
+        );
+    }
+
+    #[test]
+    fn identical_edits() {
+        let edited = "// a\n// b both\n// inserted\n";
+        let merge = merge("// a\n// b\n", edited, edited);
+
+        assert_eq!(merge.unresolved_count(), 0);
+        assert_eq!(merge.result(), Some(hltas(edited)));
+    }
+
+    #[test]
+    fn framebulk_field_conflicts() {
+        let mut merge = merge(
+            "----------|------|------|0.001|-|-|1\n",
+            "----------|------|------|0.002|-|-|2\n",
+            "----------|------|------|0.003|-|10|1\n",
+        );
+
+        let conflict = only_conflict(&mut merge);
+        assert_eq!(
+            conflict.field_conflicts,
+            vec![FieldConflict {
+                index: 0,
+                field: FramebulkField::FrameTime,
+                pick: None,
+            }]
+        );
+        assert!(!conflict.is_resolved());
+        assert_eq!(conflict.resolved_lines(), None);
+
+        conflict.field_conflicts[0].pick = Some(Side::Theirs);
+        assert!(conflict.is_resolved());
+        assert_eq!(
+            conflict.resolved_lines(),
+            Some(hltas("----------|------|------|0.003|-|10|2\n").lines)
+        );
+
+        conflict.resolution = Some(ConflictResolution::Ours);
+        assert_eq!(
+            conflict.resolved_lines(),
+            Some(hltas("----------|------|------|0.002|-|-|2\n").lines)
+        );
+    }
+
+    #[test]
+    fn insertions_at_same_line() {
+        let mut merge = merge(
+            "// a\n// c\n",
+            "// a\n// b ours\n// c\n",
+            "// a\n// b theirs\n// c\n",
+        );
+
+        let conflict = only_conflict(&mut merge);
+        assert!(conflict.base.is_empty());
+        assert_eq!(conflict.merged_framebulks, None);
+        assert_eq!((conflict.ours_index, conflict.theirs_index), (1, 1));
+
+        conflict.resolution = Some(ConflictResolution::OursThenTheirs);
+        assert_eq!(
+            merge.result(),
+            Some(hltas("// a\n// b ours\n// b theirs\n// c\n"))
+        );
+    }
+
+    #[test]
+    fn result_needs_every_conflict_resolved() {
+        let mut merge = merge("// a\n", "// ours\n", "// theirs\n");
+
+        assert_eq!(merge.unresolved_count(), 1);
+        assert_eq!(merge.result(), None);
+
+        only_conflict(&mut merge).resolution = Some(ConflictResolution::Theirs);
+        assert_eq!(merge.unresolved_count(), 0);
+        assert_eq!(merge.result(), Some(hltas("// theirs\n")));
+    }
+
+    #[test]
+    fn properties_merge_by_field() {
+        let base = hltas("");
+        let mut ours = base.to_owned();
+        let mut theirs = base.to_owned();
+        ours.properties.seeds = Some(Seeds {
+            shared: 1,
+            non_shared: 2,
+        });
+        theirs.properties.frametime_0ms = Some("0.0000000001".to_owned());
+
+        let merge = Merge::new(&base, &ours, &theirs);
+        let mut merged = ours.properties.to_owned();
+        merged.frametime_0ms = theirs.properties.frametime_0ms.to_owned();
+        assert_eq!(merge.properties, PropertiesMerge::Resolved(merged));
+
+        theirs.properties.demo = Some("theirs".to_owned());
+        ours.properties.demo = Some("ours".to_owned());
+        let mut merge = Merge::new(&base, &ours, &theirs);
+        assert_eq!(merge.unresolved_count(), 1);
+        assert_eq!(merge.result(), None);
+
+        if let PropertiesMerge::Conflict { pick, .. } = &mut merge.properties {
+            *pick = Some(Side::Theirs);
+        }
+        let properties = merge.result().unwrap().properties;
+        assert_eq!(properties.demo.as_deref(), Some("theirs"));
+        assert_eq!(properties.seeds, ours.properties.seeds);
+        assert_eq!(properties.frametime_0ms, theirs.properties.frametime_0ms);
+    }
+}
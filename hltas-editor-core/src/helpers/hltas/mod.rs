@@ -1,7 +1,20 @@
+pub mod bulk_edit;
+pub mod cleaner;
+pub mod csv;
+pub mod diff;
 pub mod fps;
+pub mod framebulk_edit;
 pub mod frametime;
+pub mod json;
+pub mod lint;
+pub mod merge;
+pub mod query;
+pub mod search;
+pub mod stats;
+pub mod timing;
+pub mod transform;
 
-use std::num::NonZeroU32;
+use std::{error::Error, fmt::Display, num::NonZeroU32};
 
 use hltas::{
     types::{Button, FrameBulk, Line},
@@ -47,11 +60,7 @@ pub fn lines_to_str(lines: Vec<Line>) -> String {
 pub fn str_to_lines(lines: &str) -> Option<Vec<Line>> {
     let version1_text = "version 1";
 
-    if lines.len() < version1_text.len() {
-        return None;
-    }
-
-    let lines = if lines[..version1_text.len()].starts_with(version1_text) {
+    let lines = if lines.starts_with(version1_text) {
         // parse as whole hltas file
         lines.to_string()
     } else {
@@ -65,6 +74,26 @@ pub fn str_to_lines(lines: &str) -> Option<Vec<Line>> {
     }
 }
 
+/// Hltas that fails to be read back after being written, such as an import with a frametime of `fast`
+#[derive(Clone, Debug, PartialEq)]
+pub struct InvalidHLTAS(pub String);
+
+impl Display for InvalidHLTAS {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "invalid hltas: {}", self.0)
+    }
+}
+
+impl Error for InvalidHLTAS {}
+
+/// Checks a hltas that wasn't made by the parser can be written and read back
+pub fn check_hltas(hltas: &HLTAS) -> Result<(), InvalidHLTAS> {
+    match HLTAS::from_str(&hltas_to_str(hltas)) {
+        Ok(_) => Ok(()),
+        Err(err) => Err(InvalidHLTAS(err.to_string())),
+    }
+}
+
 pub fn button_to_str(button: &Button) -> &str {
     match button {
         Button::Forward => "forward",
@@ -110,3 +139,64 @@ pub fn empty_framebulk(frametime: &str, frame_count: NonZeroU32) -> FrameBulk {
         console_command: None,
     }
 }
+
+#[cfg(test)]
+pub(super) mod tests {
+    use super::*;
+
+    /// Script using every line type and property, for testing conversions
+    pub(in crate::helpers::hltas) const EVERY_LINE_TYPE: &str = "version 1
+demo run
+save end
+frametime0ms 0.0000000001
+seed 1337 -42
+hlstrafe_version 3
+load_command map c1a0, \"quoted\"
+frames
+----------|------|------|0.001|-|-|1|sensitivity 0;bxt_timer_reset
+s03l2j-b3C4g5w6|flrbud|jdu12r|0.001|170.5|-12.25|400|echo \"a, b\"
+s10--D2-c--|f-----|-d----|0.0000000001|-|-|3
+s21L-d----|------|------|0.010000001|-|-|5
+s32-------|-l----|------|0.001|-|89|2
+s04-j-----|------|------|0.001|100.5 -200|-|6
+s05-------|------|------|0.001|45|-|7
+----------|------|------|0.001|90|-|8
+save quick, save
+seed 1234
+buttons 0 1 2 3
+buttons
+lgagstminspeed 30.5
+reset 3
+// comment, with \"quotes\"
+strafing vectorial
+strafing yaw
+target_yaw velocity +-1
+target_yaw velocity_avg
+target_yaw velocity_lock +-2
+target_yaw 90 +-0.5
+target_yaw from 35 to 60
+change target_yaw to 50 over 0.25 s
+change yaw to 0 over 1 s
+change pitch to -90 over 2 s
+target_yaw_override 1 2 4.5 -1.5
+";
+
+    #[test]
+    fn clipboard_text_round_trip() {
+        let text = "// comment\n----------|------|------|0.001|-|-|10\nseed 1337";
+        let lines = str_to_lines(text).unwrap();
+
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines_to_str(lines), text);
+    }
+
+    #[test]
+    fn clipboard_text_from_whole_file() {
+        let hltas = HLTAS::from_str("version 1\ndemo test\nframes\n// a\n// b\n").unwrap();
+        let lines = str_to_lines(&hltas_to_str(&hltas)).unwrap();
+
+        assert_eq!(lines, hltas.lines);
+        assert_eq!(lines_to_str(Vec::new()), "");
+        assert_eq!(str_to_lines("not a line"), None);
+    }
+}
@@ -0,0 +1,775 @@
+//! Small query language to filter hltas lines
+//!
+//! A query is a list of terms separated by whitespace, a line matches if all terms match.
+//! A term can be negated with a leading `!`.
+//!
+//! * Flags: `jump`, `duck`, `use`, `attack1`, `attack2`, `reload`, `forward`, `left`, `right`,
+//!   `back`, `up`, `down`, `autojump`, `ducktap`, `zero_ms` / `0ms`, `lgagst`, `jumpbug`, `dbc`,
+//!   `dbg`, `dwj`, `strafe`, `pitch`, `cmd`
+//! * `type=framebulk` and other line types, `strafe=maxangle`, `dir=best`
+//! * Numbers compared with `=`, `!=`, `<`, `<=`, `>`, `>=`: `frametime`, `frames`, `yaw`, `pitch`
+//! * Text compared with `=` or contains with `~`: `cmd`, `comment`, `save`, values can be quoted
+//!
+//! Example: `strafe=maxangle frametime<0.004 !ducktap cmd~"+attack"`
+
+use std::{error::Error, fmt::Display};
+
+use hltas::types::{AutoMovement, LeaveGroundActionSpeed, LeaveGroundActionType, Line, StrafeDir};
+use hltas::types::{FrameBulk, StrafeType};
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum QueryError {
+    UnknownKey(String),
+    UnknownValue { key: String, value: String },
+    InvalidOperator { key: String, operator: String },
+    MissingValue(String),
+    InvalidNumber(String),
+    UnclosedQuote,
+}
+
+impl Display for QueryError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            QueryError::UnknownKey(key) => write!(f, "unknown key `{}`", key),
+            QueryError::UnknownValue { key, value } => {
+                write!(f, "unknown value `{}` for `{}`", value, key)
+            }
+            QueryError::InvalidOperator { key, operator } => {
+                write!(f, "operator `{}` can't be used with `{}`", operator, key)
+            }
+            QueryError::MissingValue(key) => write!(f, "missing value for `{}`", key),
+            QueryError::InvalidNumber(value) => write!(f, "`{}` is not a number", value),
+            QueryError::UnclosedQuote => write!(f, "unclosed quote"),
+        }
+    }
+}
+
+impl Error for QueryError {}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Operator {
+    Equal,
+    NotEqual,
+    Less,
+    LessEqual,
+    Greater,
+    GreaterEqual,
+    Contains,
+}
+
+impl Operator {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Operator::Equal => "=",
+            Operator::NotEqual => "!=",
+            Operator::Less => "<",
+            Operator::LessEqual => "<=",
+            Operator::Greater => ">",
+            Operator::GreaterEqual => ">=",
+            Operator::Contains => "~",
+        }
+    }
+
+    fn compare(&self, lhs: f32, rhs: f32) -> bool {
+        match self {
+            Operator::Equal => lhs == rhs,
+            Operator::NotEqual => lhs != rhs,
+            Operator::Less => lhs < rhs,
+            Operator::LessEqual => lhs <= rhs,
+            Operator::Greater => lhs > rhs,
+            Operator::GreaterEqual => lhs >= rhs,
+            Operator::Contains => false,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Flag {
+    Jump,
+    Duck,
+    Use,
+    Attack1,
+    Attack2,
+    Reload,
+    Forward,
+    Left,
+    Right,
+    Back,
+    Up,
+    Down,
+    AutoJump,
+    DuckTap,
+    ZeroMs,
+    Lgagst,
+    JumpBug,
+    DuckBeforeCollision,
+    DuckBeforeGround,
+    DuckWhenJump,
+    Strafe,
+    Pitch,
+    Command,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum LineType {
+    FrameBulk,
+    Save,
+    SharedSeed,
+    Buttons,
+    LGAGSTMinSpeed,
+    Reset,
+    Comment,
+    VectorialStrafing,
+    VectorialStrafingConstraints,
+    Change,
+    TargetYawOverride,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum DirKind {
+    Left,
+    Right,
+    Best,
+    Yaw,
+    Line,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum NumberField {
+    Frametime,
+    Frames,
+    Yaw,
+    Pitch,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum TextField {
+    Command,
+    Comment,
+    Save,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Condition {
+    Flag(Flag),
+    Type(LineType),
+    /// None is a framebulk without strafing
+    Strafe(Option<StrafeType>),
+    Dir(DirKind),
+    Number {
+        field: NumberField,
+        operator: Operator,
+        value: f32,
+    },
+    Text {
+        field: TextField,
+        operator: Operator,
+        value: String,
+    },
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct Term {
+    negated: bool,
+    condition: Condition,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Query {
+    terms: Vec<Term>,
+}
+
+impl Query {
+    pub fn parse(query: &str) -> Result<Self, QueryError> {
+        let mut terms = Vec::new();
+        let mut chars = query.chars().peekable();
+
+        loop {
+            while chars.peek().is_some_and(|c| c.is_whitespace()) {
+                chars.next();
+            }
+            if chars.peek().is_none() {
+                break;
+            }
+
+            let mut negated = chars.peek() == Some(&'!');
+            if negated {
+                chars.next();
+            }
+
+            let mut key = String::new();
+            while let Some(c) = chars.peek() {
+                if c.is_alphanumeric() || *c == '_' {
+                    key.push(*c);
+                    chars.next();
+                } else {
+                    break;
+                }
+            }
+            let key = key.to_lowercase();
+
+            let mut operator = String::new();
+            while let Some(c) = chars.peek() {
+                if matches!(c, '=' | '!' | '<' | '>' | '~') {
+                    operator.push(*c);
+                    chars.next();
+                } else {
+                    break;
+                }
+            }
+
+            let mut value = String::new();
+            if chars.peek() == Some(&'"') {
+                chars.next();
+                loop {
+                    match chars.next() {
+                        Some('"') => break,
+                        Some(c) => value.push(c),
+                        None => return Err(QueryError::UnclosedQuote),
+                    }
+                }
+            } else {
+                while let Some(c) = chars.peek() {
+                    if c.is_whitespace() {
+                        break;
+                    }
+                    value.push(*c);
+                    chars.next();
+                }
+            }
+
+            let condition = if operator.is_empty() {
+                if !value.is_empty() {
+                    return Err(QueryError::UnknownKey(format!("{}{}", key, value)));
+                }
+                Condition::Flag(parse_flag(&key)?)
+            } else {
+                let operator = match operator.as_str() {
+                    "=" | "==" => Operator::Equal,
+                    "!=" => Operator::NotEqual,
+                    "<" => Operator::Less,
+                    "<=" => Operator::LessEqual,
+                    ">" => Operator::Greater,
+                    ">=" => Operator::GreaterEqual,
+                    "~" => Operator::Contains,
+                    _ => return Err(QueryError::InvalidOperator { key, operator }),
+                };
+                if value.is_empty() && operator != Operator::Equal {
+                    return Err(QueryError::MissingValue(key));
+                }
+
+                let (condition, negate) = parse_comparison(key, operator, value)?;
+                // `key!=value` is the same as `!key=value`
+                negated ^= negate;
+                condition
+            };
+
+            terms.push(Term { negated, condition });
+        }
+
+        Ok(Self { terms })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.terms.is_empty()
+    }
+
+    pub fn matches(&self, line: &Line) -> bool {
+        self.terms
+            .iter()
+            .all(|term| term.condition.matches(line) != term.negated)
+    }
+
+    /// Gets indexes of all lines that match
+    pub fn find_matches(&self, lines: &[Line]) -> Vec<usize> {
+        lines
+            .iter()
+            .enumerate()
+            .filter_map(|(i, line)| if self.matches(line) { Some(i) } else { None })
+            .collect()
+    }
+}
+
+fn parse_flag(key: &str) -> Result<Flag, QueryError> {
+    Ok(match key {
+        "jump" => Flag::Jump,
+        "duck" => Flag::Duck,
+        "use" => Flag::Use,
+        "attack1" => Flag::Attack1,
+        "attack2" => Flag::Attack2,
+        "reload" => Flag::Reload,
+        "forward" => Flag::Forward,
+        "left" => Flag::Left,
+        "right" => Flag::Right,
+        "back" => Flag::Back,
+        "up" => Flag::Up,
+        "down" => Flag::Down,
+        "autojump" => Flag::AutoJump,
+        "ducktap" => Flag::DuckTap,
+        "zero_ms" | "0ms" => Flag::ZeroMs,
+        "lgagst" => Flag::Lgagst,
+        "jumpbug" => Flag::JumpBug,
+        "dbc" => Flag::DuckBeforeCollision,
+        "dbg" => Flag::DuckBeforeGround,
+        "dwj" => Flag::DuckWhenJump,
+        "strafe" => Flag::Strafe,
+        "pitch" => Flag::Pitch,
+        "cmd" => Flag::Command,
+        _ => return Err(QueryError::UnknownKey(key.to_string())),
+    })
+}
+
+fn parse_comparison(
+    key: String,
+    operator: Operator,
+    value: String,
+) -> Result<(Condition, bool), QueryError> {
+    let unknown_value = |key: String, value: String| Err(QueryError::UnknownValue { key, value });
+    let invalid_operator = |key: String, operator: Operator| {
+        Err(QueryError::InvalidOperator {
+            key,
+            operator: operator.as_str().to_string(),
+        })
+    };
+
+    let number_field = match key.as_str() {
+        "frametime" => Some(NumberField::Frametime),
+        "frames" => Some(NumberField::Frames),
+        "yaw" => Some(NumberField::Yaw),
+        "pitch" => Some(NumberField::Pitch),
+        _ => None,
+    };
+    if let Some(field) = number_field {
+        if operator == Operator::Contains {
+            return invalid_operator(key, operator);
+        }
+        let value = value
+            .parse::<f32>()
+            .map_err(|_| QueryError::InvalidNumber(value))?;
+
+        return Ok((
+            Condition::Number {
+                field,
+                operator,
+                value,
+            },
+            false,
+        ));
+    }
+
+    let text_field = match key.as_str() {
+        "cmd" => Some(TextField::Command),
+        "comment" => Some(TextField::Comment),
+        "save" => Some(TextField::Save),
+        _ => None,
+    };
+    if let Some(field) = text_field {
+        if !matches!(
+            operator,
+            Operator::Equal | Operator::NotEqual | Operator::Contains
+        ) {
+            return invalid_operator(key, operator);
+        }
+
+        return Ok((
+            Condition::Text {
+                field,
+                operator,
+                value,
+            },
+            false,
+        ));
+    }
+
+    if !matches!(key.as_str(), "type" | "strafe" | "dir") {
+        return Err(QueryError::UnknownKey(key));
+    }
+
+    // the rest only make sense with equality
+    let negate = match operator {
+        Operator::Equal => false,
+        Operator::NotEqual => true,
+        _ => return invalid_operator(key, operator),
+    };
+    let condition = match key.as_str() {
+        "type" => Condition::Type(match value.to_lowercase().as_str() {
+            "framebulk" => LineType::FrameBulk,
+            "save" => LineType::Save,
+            "sharedseed" | "seed" => LineType::SharedSeed,
+            "buttons" => LineType::Buttons,
+            "lgagstminspeed" => LineType::LGAGSTMinSpeed,
+            "reset" => LineType::Reset,
+            "comment" => LineType::Comment,
+            "vectorialstrafing" => LineType::VectorialStrafing,
+            "vectorialstrafingconstraints" | "targetyaw" => LineType::VectorialStrafingConstraints,
+            "change" => LineType::Change,
+            "targetyawoverride" => LineType::TargetYawOverride,
+            _ => return unknown_value(key, value),
+        }),
+        "strafe" => Condition::Strafe(match value.to_lowercase().as_str() {
+            "maxaccel" => Some(StrafeType::MaxAccel),
+            "maxangle" => Some(StrafeType::MaxAngle),
+            "maxdeccel" => Some(StrafeType::MaxDeccel),
+            "constspeed" => Some(StrafeType::ConstSpeed),
+            "none" => None,
+            _ => return unknown_value(key, value),
+        }),
+        "dir" => Condition::Dir(match value.to_lowercase().as_str() {
+            "left" => DirKind::Left,
+            "right" => DirKind::Right,
+            "best" => DirKind::Best,
+            "yaw" => DirKind::Yaw,
+            "line" => DirKind::Line,
+            _ => return unknown_value(key, value),
+        }),
+        _ => return Err(QueryError::UnknownKey(key)),
+    };
+
+    Ok((condition, negate))
+}
+
+impl Condition {
+    fn matches(&self, line: &Line) -> bool {
+        match self {
+            Condition::Type(line_type) => line_type_of(line) == *line_type,
+            Condition::Flag(flag) => framebulk_of(line).is_some_and(|f| flag_matches(f, *flag)),
+            Condition::Strafe(strafe_type) => framebulk_of(line).is_some_and(|framebulk| {
+                let framebulk_strafe_type = match &framebulk.auto_actions.movement {
+                    Some(AutoMovement::Strafe(strafe_settings)) => Some(strafe_settings.type_),
+                    _ => None,
+                };
+                framebulk_strafe_type == *strafe_type
+            }),
+            Condition::Dir(dir_kind) => {
+                framebulk_of(line).is_some_and(|framebulk| match &framebulk.auto_actions.movement {
+                    Some(AutoMovement::Strafe(strafe_settings)) => match dir_kind {
+                        DirKind::Left => matches!(strafe_settings.dir, StrafeDir::Left),
+                        DirKind::Right => matches!(strafe_settings.dir, StrafeDir::Right),
+                        DirKind::Best => matches!(strafe_settings.dir, StrafeDir::Best),
+                        DirKind::Yaw => matches!(strafe_settings.dir, StrafeDir::Yaw(_)),
+                        DirKind::Line => matches!(strafe_settings.dir, StrafeDir::Line { .. }),
+                    },
+                    _ => false,
+                })
+            }
+            Condition::Number {
+                field,
+                operator,
+                value,
+            } => framebulk_of(line).is_some_and(|framebulk| {
+                let number = match field {
+                    NumberField::Frametime => framebulk.frame_time.parse::<f32>().ok(),
+                    NumberField::Frames => Some(framebulk.frame_count.get() as f32),
+                    NumberField::Yaw => match &framebulk.auto_actions.movement {
+                        Some(AutoMovement::SetYaw(yaw)) => Some(*yaw),
+                        Some(AutoMovement::Strafe(strafe_settings)) => match strafe_settings.dir {
+                            StrafeDir::Yaw(yaw) => Some(yaw),
+                            StrafeDir::Line { yaw } => Some(yaw),
+                            _ => None,
+                        },
+                        None => None,
+                    },
+                    NumberField::Pitch => framebulk.pitch,
+                };
+                number.is_some_and(|number| operator.compare(number, *value))
+            }),
+            Condition::Text {
+                field,
+                operator,
+                value,
+            } => {
+                let text = match (field, line) {
+                    (TextField::Command, Line::FrameBulk(framebulk)) => {
+                        framebulk.console_command.as_deref()
+                    }
+                    (TextField::Comment, Line::Comment(comment)) => Some(comment.as_str()),
+                    (TextField::Save, Line::Save(save)) => Some(save.as_str()),
+                    _ => None,
+                };
+                text.is_some_and(|text| match operator {
+                    Operator::Equal => text == value,
+                    Operator::NotEqual => text != value,
+                    Operator::Contains => text.contains(value.as_str()),
+                    _ => false,
+                })
+            }
+        }
+    }
+}
+
+fn framebulk_of(line: &Line) -> Option<&FrameBulk> {
+    match line {
+        Line::FrameBulk(framebulk) => Some(framebulk),
+        _ => None,
+    }
+}
+
+fn line_type_of(line: &Line) -> LineType {
+    match line {
+        Line::FrameBulk(_) => LineType::FrameBulk,
+        Line::Save(_) => LineType::Save,
+        Line::SharedSeed(_) => LineType::SharedSeed,
+        Line::Buttons(_) => LineType::Buttons,
+        Line::LGAGSTMinSpeed(_) => LineType::LGAGSTMinSpeed,
+        Line::Reset { .. } => LineType::Reset,
+        Line::Comment(_) => LineType::Comment,
+        Line::VectorialStrafing(_) => LineType::VectorialStrafing,
+        Line::VectorialStrafingConstraints(_) => LineType::VectorialStrafingConstraints,
+        Line::Change(_) => LineType::Change,
+        Line::TargetYawOverride(_) => LineType::TargetYawOverride,
+    }
+}
+
+fn flag_matches(framebulk: &FrameBulk, flag: Flag) -> bool {
+    let auto_actions = &framebulk.auto_actions;
+    let movement_keys = &framebulk.movement_keys;
+    let action_keys = &framebulk.action_keys;
+
+    match flag {
+        Flag::Jump => action_keys.jump,
+        Flag::Duck => action_keys.duck,
+        Flag::Use => action_keys.use_,
+        Flag::Attack1 => action_keys.attack_1,
+        Flag::Attack2 => action_keys.attack_2,
+        Flag::Reload => action_keys.reload,
+        Flag::Forward => movement_keys.forward,
+        Flag::Left => movement_keys.left,
+        Flag::Right => movement_keys.right,
+        Flag::Back => movement_keys.back,
+        Flag::Up => movement_keys.up,
+        Flag::Down => movement_keys.down,
+        Flag::AutoJump => matches!(
+            auto_actions
+                .leave_ground_action
+                .as_ref()
+                .map(|action| action.type_),
+            Some(LeaveGroundActionType::Jump)
+        ),
+        Flag::DuckTap => matches!(
+            auto_actions
+                .leave_ground_action
+                .as_ref()
+                .map(|action| action.type_),
+            Some(LeaveGroundActionType::DuckTap { .. })
+        ),
+        Flag::ZeroMs => matches!(
+            auto_actions
+                .leave_ground_action
+                .as_ref()
+                .map(|action| action.type_),
+            Some(LeaveGroundActionType::DuckTap { zero_ms: true })
+        ),
+        Flag::Lgagst => matches!(
+            auto_actions
+                .leave_ground_action
+                .as_ref()
+                .map(|action| action.speed),
+            Some(LeaveGroundActionSpeed::Optimal)
+                | Some(LeaveGroundActionSpeed::OptimalWithFullMaxspeed)
+        ),
+        Flag::JumpBug => auto_actions.jump_bug.is_some(),
+        Flag::DuckBeforeCollision => auto_actions.duck_before_collision.is_some(),
+        Flag::DuckBeforeGround => auto_actions.duck_before_ground.is_some(),
+        Flag::DuckWhenJump => auto_actions.duck_when_jump.is_some(),
+        Flag::Strafe => matches!(auto_actions.movement, Some(AutoMovement::Strafe(_))),
+        Flag::Pitch => framebulk.pitch.is_some(),
+        Flag::Command => framebulk.console_command.is_some(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use hltas::HLTAS;
+
+    use super::*;
+
+    const LINES: &str = "s12-------|------|------|0.001|-|-|1
+s02-------|------|j-----|0.01|-|-|5|+attack;wait
+-----D----|f-----|------|0.001|-|10|3
+// a comment
+save end
+s13-------|------|------|0.001|90|-|1
+";
+
+    fn term(negated: bool, condition: Condition) -> Term {
+        Term { negated, condition }
+    }
+
+    fn parse(query: &str) -> Vec<Term> {
+        Query::parse(query).unwrap().terms
+    }
+
+    fn matches(query: &str) -> Vec<usize> {
+        let hltas = HLTAS::from_str(&format!("version 1\nframes\n{}", LINES)).unwrap();
+        Query::parse(query).unwrap().find_matches(&hltas.lines)
+    }
+
+    #[test]
+    fn parse_examples() {
+        assert_eq!(
+            parse("strafe=maxangle"),
+            vec![term(false, Condition::Strafe(Some(StrafeType::MaxAngle)))]
+        );
+        assert_eq!(
+            parse("frametime<0.004"),
+            vec![term(
+                false,
+                Condition::Number {
+                    field: NumberField::Frametime,
+                    operator: Operator::Less,
+                    value: 0.004,
+                }
+            )]
+        );
+        assert_eq!(
+            parse("jump"),
+            vec![term(false, Condition::Flag(Flag::Jump))]
+        );
+        assert_eq!(
+            parse("ducktap zero_ms"),
+            vec![
+                term(false, Condition::Flag(Flag::DuckTap)),
+                term(false, Condition::Flag(Flag::ZeroMs)),
+            ]
+        );
+        assert_eq!(
+            parse("cmd~\"+attack\""),
+            vec![term(
+                false,
+                Condition::Text {
+                    field: TextField::Command,
+                    operator: Operator::Contains,
+                    value: "+attack".to_string(),
+                }
+            )]
+        );
+        assert_eq!(
+            parse("type=comment"),
+            vec![term(false, Condition::Type(LineType::Comment))]
+        );
+        assert!(Query::parse("  ").unwrap().is_empty());
+    }
+
+    #[test]
+    fn parse_negation() {
+        assert_eq!(
+            parse("!ducktap  DIR!=best"),
+            vec![
+                term(true, Condition::Flag(Flag::DuckTap)),
+                term(true, Condition::Dir(DirKind::Best)),
+            ]
+        );
+        // both negations cancel out
+        assert_eq!(
+            parse("!strafe!=none"),
+            vec![term(false, Condition::Strafe(None))]
+        );
+    }
+
+    #[test]
+    fn parse_quoted_values() {
+        assert_eq!(
+            parse("comment=\"two words\" cmd~\"a=b\""),
+            vec![
+                term(
+                    false,
+                    Condition::Text {
+                        field: TextField::Comment,
+                        operator: Operator::Equal,
+                        value: "two words".to_string(),
+                    }
+                ),
+                term(
+                    false,
+                    Condition::Text {
+                        field: TextField::Command,
+                        operator: Operator::Contains,
+                        value: "a=b".to_string(),
+                    }
+                ),
+            ]
+        );
+        // an empty value is only allowed for equality
+        assert_eq!(
+            parse("cmd=\"\""),
+            vec![term(
+                false,
+                Condition::Text {
+                    field: TextField::Command,
+                    operator: Operator::Equal,
+                    value: String::new(),
+                }
+            )]
+        );
+    }
+
+    #[test]
+    fn parse_errors() {
+        let err = |query| Query::parse(query).unwrap_err();
+
+        assert_eq!(err("speed"), QueryError::UnknownKey("speed".to_string()));
+        assert_eq!(err("speed>1"), QueryError::UnknownKey("speed".to_string()));
+        assert_eq!(
+            err("strafe=fast"),
+            QueryError::UnknownValue {
+                key: "strafe".to_string(),
+                value: "fast".to_string(),
+            }
+        );
+        assert_eq!(
+            err("frames~1"),
+            QueryError::InvalidOperator {
+                key: "frames".to_string(),
+                operator: "~".to_string(),
+            }
+        );
+        assert_eq!(
+            err("type<comment"),
+            QueryError::InvalidOperator {
+                key: "type".to_string(),
+                operator: "<".to_string(),
+            }
+        );
+        assert_eq!(
+            err("frames=>1"),
+            QueryError::InvalidOperator {
+                key: "frames".to_string(),
+                operator: "=>".to_string(),
+            }
+        );
+        assert_eq!(err("yaw<"), QueryError::MissingValue("yaw".to_string()));
+        assert_eq!(
+            err("frametime<fast"),
+            QueryError::InvalidNumber("fast".to_string())
+        );
+        assert_eq!(err("cmd~\"+attack"), QueryError::UnclosedQuote);
+    }
+
+    #[test]
+    fn match_examples() {
+        assert_eq!(matches("strafe=maxangle"), vec![0, 5]);
+        assert_eq!(matches("frametime<0.004"), vec![0, 2, 5]);
+        assert_eq!(matches("jump"), vec![1]);
+        assert_eq!(matches("ducktap zero_ms"), vec![2]);
+        assert_eq!(matches("cmd~\"+attack\""), vec![1]);
+        assert_eq!(matches("type=comment"), vec![3]);
+    }
+
+    #[test]
+    fn match_fields() {
+        assert_eq!(matches("type=framebulk strafe!=maxangle"), vec![1, 2]);
+        assert_eq!(matches("strafe=none"), vec![2]);
+        assert_eq!(matches("dir=yaw yaw>=90"), vec![5]);
+        assert_eq!(matches("frames>=3 pitch=10 forward"), vec![2]);
+        assert_eq!(matches("frames!=1 !cmd"), vec![2]);
+        assert_eq!(matches("save=end"), vec![4]);
+        assert_eq!(matches("comment~\"a com\""), vec![3]);
+        assert_eq!(matches("cmd=\"+attack;wait\""), vec![1]);
+    }
+
+    #[test]
+    fn negated_terms_match_other_line_types() {
+        assert_eq!(matches("!strafe"), vec![2, 3, 4]);
+        assert_eq!(matches("type!=framebulk"), vec![3, 4]);
+    }
+}
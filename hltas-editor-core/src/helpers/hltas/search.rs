@@ -0,0 +1,248 @@
+use hltas::types::Line;
+
+use super::{lines_to_str, str_to_lines};
+
+/// What part of a line gets searched
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum SearchTarget {
+    /// The whole line as it appears in the hltas file
+    #[default]
+    Line,
+    Comment,
+    Command,
+    Save,
+    Frametime,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct LineSearch {
+    pub find: String,
+    pub replace: String,
+    pub target: SearchTarget,
+    pub match_case: bool,
+}
+
+impl LineSearch {
+    pub fn line_matches(&self, line: &Line) -> bool {
+        if self.find.is_empty() {
+            return false;
+        }
+
+        match searched_text(line, self.target) {
+            Some(text) => find_str(&text, &self.find, self.match_case).is_some(),
+            None => false,
+        }
+    }
+
+    /// Gets indexes of all lines that match
+    pub fn find_matches(&self, lines: &[Line]) -> Vec<usize> {
+        lines
+            .iter()
+            .enumerate()
+            .filter_map(|(i, line)| {
+                if self.line_matches(line) {
+                    Some(i)
+                } else {
+                    None
+                }
+            })
+            .collect()
+    }
+
+    /// Replaces all occurrences in the searched part of the line
+    ///
+    /// * Returns None if nothing was found or if the replaced line would be invalid
+    pub fn replace_in_line(&self, line: &Line) -> Option<Line> {
+        if self.find.is_empty() {
+            return None;
+        }
+
+        let text = searched_text(line, self.target)?;
+        let replaced = replace_str(&text, &self.find, &self.replace, self.match_case)?;
+
+        // everything is a single line in the file
+        if replaced.contains('\n') {
+            return None;
+        }
+
+        if self.target == SearchTarget::Line {
+            let mut lines = str_to_lines(&replaced)?;
+            if lines.len() != 1 {
+                return None;
+            }
+            return Some(lines.remove(0));
+        }
+
+        let mut line = line.to_owned();
+        match (&mut line, self.target) {
+            (Line::Comment(comment), SearchTarget::Comment) => *comment = replaced,
+            (Line::FrameBulk(framebulk), SearchTarget::Command) => {
+                framebulk.console_command = Some(replaced)
+            }
+            (Line::Save(save), SearchTarget::Save) => {
+                if replaced.is_empty() || replaced.contains(char::is_whitespace) {
+                    return None;
+                }
+                *save = replaced;
+            }
+            (Line::FrameBulk(framebulk), SearchTarget::Frametime) => {
+                framebulk.frame_time = replaced
+            }
+            _ => return None,
+        }
+
+        // the line has to read back the same, otherwise the file can't be opened again
+        if str_to_lines(&lines_to_str(vec![line.to_owned()])) != Some(vec![line.to_owned()]) {
+            return None;
+        }
+
+        Some(line)
+    }
+}
+
+fn searched_text(line: &Line, target: SearchTarget) -> Option<String> {
+    match (line, target) {
+        (_, SearchTarget::Line) => Some(lines_to_str(vec![line.to_owned()])),
+        (Line::Comment(comment), SearchTarget::Comment) => Some(comment.to_owned()),
+        (Line::FrameBulk(framebulk), SearchTarget::Command) => framebulk.console_command.clone(),
+        (Line::Save(save), SearchTarget::Save) => Some(save.to_owned()),
+        (Line::FrameBulk(framebulk), SearchTarget::Frametime) => {
+            Some(framebulk.frame_time.to_owned())
+        }
+        _ => None,
+    }
+}
+
+/// Finds the byte index of the first occurrence, case insensitive search only ignores ascii case
+fn find_str(haystack: &str, needle: &str, match_case: bool) -> Option<usize> {
+    if match_case {
+        haystack.find(needle)
+    } else {
+        // ascii lowercase keeps the byte indexes the same
+        haystack
+            .to_ascii_lowercase()
+            .find(&needle.to_ascii_lowercase())
+    }
+}
+
+fn replace_str(haystack: &str, find: &str, replace: &str, match_case: bool) -> Option<String> {
+    let mut replaced = String::new();
+    let mut rest = haystack;
+    let mut found = false;
+
+    while let Some(index) = find_str(rest, find, match_case) {
+        found = true;
+        replaced.push_str(&rest[..index]);
+        replaced.push_str(replace);
+        rest = &rest[index + find.len()..];
+    }
+
+    if !found {
+        return None;
+    }
+
+    replaced.push_str(rest);
+    Some(replaced)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn line(line: &str) -> Line {
+        str_to_lines(line).unwrap().remove(0)
+    }
+
+    fn search(find: &str, replace: &str, target: SearchTarget) -> LineSearch {
+        LineSearch {
+            find: find.to_owned(),
+            replace: replace.to_owned(),
+            target,
+            match_case: false,
+        }
+    }
+
+    #[test]
+    fn find_matches() {
+        let lines = str_to_lines(
+            "// Jump here\n----------|------|------|0.001|-|-|1|echo jump\nsave jump\n",
+        )
+        .unwrap();
+
+        assert_eq!(
+            search("jump", "", SearchTarget::Line).find_matches(&lines),
+            vec![0, 1, 2]
+        );
+        assert_eq!(
+            search("jump", "", SearchTarget::Command).find_matches(&lines),
+            vec![1]
+        );
+        assert_eq!(
+            search("0.001", "", SearchTarget::Frametime).find_matches(&lines),
+            vec![1]
+        );
+        assert!(search("", "", SearchTarget::Line)
+            .find_matches(&lines)
+            .is_empty());
+
+        let mut search = search("jump", "", SearchTarget::Comment);
+        assert_eq!(search.find_matches(&lines), vec![0]);
+        search.match_case = true;
+        assert!(search.find_matches(&lines).is_empty());
+    }
+
+    #[test]
+    fn replace_in_line() {
+        assert_eq!(
+            search("a", "b", SearchTarget::Comment).replace_in_line(&line("// aAa\n")),
+            Some(line("// bbb\n"))
+        );
+        assert_eq!(
+            search("echo", "say", SearchTarget::Command).replace_in_line(&line(
+                "----------|------|------|0.001|-|-|1|echo a;echo b\n"
+            )),
+            Some(line("----------|------|------|0.001|-|-|1|say a;say b\n"))
+        );
+        assert_eq!(
+            search("0.001", "0.004", SearchTarget::Frametime)
+                .replace_in_line(&line("----------|------|------|0.001|-|-|1\n")),
+            Some(line("----------|------|------|0.004|-|-|1\n"))
+        );
+        assert_eq!(
+            search("|1", "|2", SearchTarget::Line)
+                .replace_in_line(&line("----------|------|------|0.001|-|-|1\n")),
+            Some(line("----------|------|------|0.001|-|-|2\n"))
+        );
+        // nothing found
+        assert_eq!(
+            search("b", "c", SearchTarget::Comment).replace_in_line(&line("// a\n")),
+            None
+        );
+    }
+
+    #[test]
+    fn invalid_replacements_are_rejected() {
+        let framebulk = line("----------|------|------|0.001|-|-|1\n");
+        for frametime in ["inf", "NaN", "fast", ""] {
+            assert_eq!(
+                search("0.001", frametime, SearchTarget::Frametime).replace_in_line(&framebulk),
+                None,
+                "{}",
+                frametime
+            );
+        }
+
+        assert_eq!(
+            search("end", "end game", SearchTarget::Save).replace_in_line(&line("save end\n")),
+            None
+        );
+        assert_eq!(
+            search("a", "a\nb", SearchTarget::Comment).replace_in_line(&line("// a\n")),
+            None
+        );
+        assert_eq!(
+            search("|1", "|x", SearchTarget::Line).replace_in_line(&framebulk),
+            None
+        );
+    }
+}
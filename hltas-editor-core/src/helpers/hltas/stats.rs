@@ -0,0 +1,308 @@
+use std::fmt::Display;
+
+use hltas::{
+    types::{AutoMovement, LeaveGroundActionType, Line, StrafeType},
+    HLTAS,
+};
+
+use super::timing::line_timings;
+
+/// Frames and time between two comments
+#[derive(Clone, Debug, PartialEq)]
+pub struct SectionStats {
+    /// Comment starting the section, `None` for the lines before the first comment
+    pub name: Option<String>,
+    pub frames: u64,
+    pub time: f64,
+}
+
+/// Statistics of a whole script
+#[derive(Clone, Debug, PartialEq)]
+pub struct ScriptStats {
+    pub total_frames: u64,
+    pub zero_ms_frames: u64,
+    pub total_time: f64,
+    pub sections: Vec<SectionStats>,
+    /// Line type and how many lines of that type exist
+    pub line_counts: Vec<(&'static str, usize)>,
+    /// Strafe type and how many frames use it, framebulks without strafing are under "none"
+    pub strafe_frames: Vec<(&'static str, u64)>,
+    pub jump_frames: u64,
+    pub ducktap_frames: u64,
+    pub jumpbug_frames: u64,
+    /// Frametimes in the order they are first used, with how many frames use them
+    pub frametimes: Vec<(String, u64)>,
+    pub save_count: usize,
+    pub console_command_count: usize,
+}
+
+const LINE_TYPES: &[&str] = &[
+    "framebulk",
+    "save",
+    "shared seed",
+    "buttons",
+    "lgagst min speed",
+    "reset",
+    "comment",
+    "vectorial strafing",
+    "vectorial strafing constraints",
+    "change",
+    "target yaw override",
+];
+
+const STRAFE_TYPES: &[&str] = &[
+    "max accel",
+    "max angle",
+    "max deccel",
+    "const speed",
+    "none",
+];
+
+fn line_type_index(line: &Line) -> usize {
+    match line {
+        Line::FrameBulk(_) => 0,
+        Line::Save(_) => 1,
+        Line::SharedSeed(_) => 2,
+        Line::Buttons(_) => 3,
+        Line::LGAGSTMinSpeed(_) => 4,
+        Line::Reset { .. } => 5,
+        Line::Comment(_) => 6,
+        Line::VectorialStrafing(_) => 7,
+        Line::VectorialStrafingConstraints(_) => 8,
+        Line::Change(_) => 9,
+        Line::TargetYawOverride(_) => 10,
+    }
+}
+
+fn strafe_type_index(movement: &Option<AutoMovement>) -> usize {
+    match movement {
+        Some(AutoMovement::Strafe(strafe_settings)) => match strafe_settings.type_ {
+            StrafeType::MaxAccel => 0,
+            StrafeType::MaxAngle => 1,
+            StrafeType::MaxDeccel => 2,
+            StrafeType::ConstSpeed => 3,
+        },
+        _ => 4,
+    }
+}
+
+impl ScriptStats {
+    pub fn new(hltas: &HLTAS) -> Self {
+        let timings = line_timings(hltas);
+        let end_timing = *timings.last().unwrap();
+
+        let mut sections = vec![SectionStats {
+            name: None,
+            frames: 0,
+            time: 0.0,
+        }];
+        let mut line_counts = vec![0; LINE_TYPES.len()];
+        let mut strafe_frames = vec![0; STRAFE_TYPES.len()];
+        let mut jump_frames = 0;
+        let mut ducktap_frames = 0;
+        let mut jumpbug_frames = 0;
+        let mut frametimes: Vec<(String, u64)> = Vec::new();
+        let mut save_count = 0;
+        let mut console_command_count = 0;
+
+        for (i, line) in hltas.lines.iter().enumerate() {
+            line_counts[line_type_index(line)] += 1;
+
+            let section = sections.last_mut().unwrap();
+            section.frames += timings[i + 1].frame - timings[i].frame;
+            section.time += timings[i + 1].time - timings[i].time;
+
+            match line {
+                Line::FrameBulk(framebulk) => {
+                    let frame_count = framebulk.frame_count.get() as u64;
+                    let auto_actions = &framebulk.auto_actions;
+
+                    strafe_frames[strafe_type_index(&auto_actions.movement)] += frame_count;
+
+                    match auto_actions
+                        .leave_ground_action
+                        .as_ref()
+                        .map(|action| action.type_)
+                    {
+                        Some(LeaveGroundActionType::Jump) => jump_frames += frame_count,
+                        Some(LeaveGroundActionType::DuckTap { .. }) => {
+                            ducktap_frames += frame_count
+                        }
+                        None => (),
+                    }
+                    if auto_actions.jump_bug.is_some() {
+                        jumpbug_frames += frame_count;
+                    }
+
+                    match frametimes
+                        .iter_mut()
+                        .find(|(frametime, _)| *frametime == framebulk.frame_time)
+                    {
+                        Some((_, frames)) => *frames += frame_count,
+                        None => frametimes.push((framebulk.frame_time.to_owned(), frame_count)),
+                    }
+
+                    if framebulk.console_command.is_some() {
+                        console_command_count += 1;
+                    }
+                }
+                Line::Save(_) => save_count += 1,
+                Line::Comment(comment) => sections.push(SectionStats {
+                    name: Some(comment.trim().to_string()),
+                    frames: 0,
+                    time: 0.0,
+                }),
+                _ => (),
+            }
+        }
+
+        // nothing comes before the first comment
+        if sections.len() > 1 && sections[0].frames == 0 {
+            sections.remove(0);
+        }
+
+        Self {
+            total_frames: end_timing.frame,
+            zero_ms_frames: end_timing.zero_ms_frames,
+            total_time: end_timing.time,
+            sections,
+            line_counts: LINE_TYPES.iter().copied().zip(line_counts).collect(),
+            strafe_frames: STRAFE_TYPES.iter().copied().zip(strafe_frames).collect(),
+            jump_frames,
+            ducktap_frames,
+            jumpbug_frames,
+            frametimes,
+            save_count,
+            console_command_count,
+        }
+    }
+}
+
+impl Display for ScriptStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "total frames: {}", self.total_frames)?;
+        if self.zero_ms_frames > 0 {
+            writeln!(f, "0ms frames: {}", self.zero_ms_frames)?;
+        }
+        writeln!(f, "total time: {:.3}s", self.total_time)?;
+
+        writeln!(f, "\nsections:")?;
+        for section in &self.sections {
+            writeln!(
+                f,
+                "  {}: {} frames, {:.3}s",
+                section.name.as_deref().unwrap_or("(start)"),
+                section.frames,
+                section.time
+            )?;
+        }
+
+        writeln!(f, "\nlines:")?;
+        for (line_type, count) in self.line_counts.iter().filter(|(_, count)| *count > 0) {
+            writeln!(f, "  {}: {}", line_type, count)?;
+        }
+
+        writeln!(f, "\nstrafe frames:")?;
+        for (strafe_type, frames) in self.strafe_frames.iter().filter(|(_, frames)| *frames > 0) {
+            writeln!(f, "  {}: {}", strafe_type, frames)?;
+        }
+
+        writeln!(f, "\nautoaction frames:")?;
+        writeln!(f, "  jump: {}", self.jump_frames)?;
+        writeln!(f, "  ducktap: {}", self.ducktap_frames)?;
+        writeln!(f, "  jumpbug: {}", self.jumpbug_frames)?;
+
+        writeln!(f, "\nframetimes:")?;
+        for (frametime, frames) in &self.frametimes {
+            writeln!(f, "  {}: {} frames", frametime, frames)?;
+        }
+
+        writeln!(f, "\nsaves: {}", self.save_count)?;
+        write!(f, "console commands: {}", self.console_command_count)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn script_stats(lines: &str) -> ScriptStats {
+        let hltas =
+            HLTAS::from_str(&format!("version 1\nframetime0ms 0.125\nframes\n{}", lines)).unwrap();
+        ScriptStats::new(&hltas)
+    }
+
+    fn section(name: Option<&str>, frames: u64, time: f64) -> SectionStats {
+        SectionStats {
+            name: name.map(|name| name.to_string()),
+            frames,
+            time,
+        }
+    }
+
+    fn count<T: Copy>(counts: &[(&str, T)], name: &str) -> T {
+        counts.iter().find(|(other, _)| *other == name).unwrap().1
+    }
+
+    #[test]
+    fn totals_with_zero_ms_frames() {
+        let stats = script_stats(
+            "----------|------|------|0.5|-|-|2
+// ducks
+-----D2----|------|------|0.25|-|-|4|echo
+save a
+s12-j-----|------|------|0.5|-|-|2
+",
+        );
+
+        assert_eq!(stats.total_frames, 8);
+        assert_eq!(stats.zero_ms_frames, 2);
+        assert_eq!(stats.total_time, 3.25);
+        assert_eq!(
+            stats.sections,
+            vec![section(None, 2, 1.0), section(Some("ducks"), 6, 2.25)]
+        );
+
+        assert_eq!(count(&stats.line_counts, "framebulk"), 3);
+        assert_eq!(count(&stats.line_counts, "comment"), 1);
+        assert_eq!(count(&stats.line_counts, "save"), 1);
+        assert_eq!(count(&stats.strafe_frames, "max angle"), 2);
+        assert_eq!(count(&stats.strafe_frames, "none"), 6);
+        assert_eq!(
+            (
+                stats.jump_frames,
+                stats.ducktap_frames,
+                stats.jumpbug_frames
+            ),
+            (2, 4, 0)
+        );
+        assert_eq!(
+            stats.frametimes,
+            vec![("0.5".to_string(), 4), ("0.25".to_string(), 4)]
+        );
+        assert_eq!((stats.save_count, stats.console_command_count), (1, 1));
+    }
+
+    #[test]
+    fn no_framebulks() {
+        let stats = script_stats("// only a comment\nsave a\n");
+
+        assert_eq!(
+            (stats.total_frames, stats.zero_ms_frames, stats.total_time),
+            (0, 0, 0.0)
+        );
+        assert_eq!(
+            stats.sections,
+            vec![section(Some("only a comment"), 0, 0.0)]
+        );
+        assert!(stats.frametimes.is_empty());
+        assert_eq!(count(&stats.strafe_frames, "none"), 0);
+        assert!(stats
+            .to_string()
+            .starts_with("total frames: 0\ntotal time: 0.000s"));
+
+        let empty_stats = script_stats("");
+        assert_eq!(empty_stats.sections, vec![section(None, 0, 0.0)]);
+        assert!(empty_stats.line_counts.iter().all(|(_, count)| *count == 0));
+    }
+}
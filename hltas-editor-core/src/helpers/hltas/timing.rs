@@ -0,0 +1,205 @@
+use hltas::{
+    types::{LeaveGroundActionType, Line, Times},
+    HLTAS,
+};
+
+/// Where a line starts in the script
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct LineTiming {
+    /// Frames played before the line, without 0ms ducktap frames
+    pub frame: u64,
+    /// 0ms ducktap frames played before the line
+    pub zero_ms_frames: u64,
+    /// In-game seconds before the line
+    pub time: f64,
+}
+
+/// Timing at the start of every line, with one extra entry for the end of the script
+///
+/// * Each 0ms ducktap adds a frame that uses `Properties::frametime_0ms`
+/// * Ducktaps without a limit depend on when the player lands, those 0ms frames can't be counted
+/// * Framebulks with a frametime that can't be parsed don't add any time
+pub fn line_timings(hltas: &HLTAS) -> Vec<LineTiming> {
+    let frametime_0ms = hltas
+        .properties
+        .frametime_0ms
+        .as_ref()
+        .and_then(|frametime| frametime.parse::<f64>().ok())
+        .unwrap_or(0.0);
+
+    let mut timing = LineTiming::default();
+    let mut timings = Vec::with_capacity(hltas.lines.len() + 1);
+
+    for line in &hltas.lines {
+        timings.push(timing);
+
+        if let Line::FrameBulk(framebulk) = line {
+            let frame_count = framebulk.frame_count.get() as u64;
+
+            timing.frame += frame_count;
+            if let Ok(frame_time) = framebulk.frame_time.parse::<f64>() {
+                timing.time += frame_time * frame_count as f64;
+            }
+
+            if let Some(leave_ground_action) = &framebulk.auto_actions.leave_ground_action {
+                if let (LeaveGroundActionType::DuckTap { zero_ms: true }, Times::Limited(times)) =
+                    (leave_ground_action.type_, leave_ground_action.times)
+                {
+                    let zero_ms_frames = (times.get() as u64).min(frame_count);
+
+                    timing.zero_ms_frames += zero_ms_frames;
+                    timing.time += frametime_0ms * zero_ms_frames as f64;
+                }
+            }
+        }
+    }
+
+    timings.push(timing);
+
+    timings
+}
+
+/// A frame inside a framebulk
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct FramePosition {
+    pub line_index: usize,
+    /// Frame counted from the start of the framebulk
+    pub frame_in_line: u32,
+}
+
+/// Finds the framebulk playing the absolute `frame`, counting from 0
+pub fn find_frame(hltas: &HLTAS, frame: u64) -> Option<FramePosition> {
+    let timings = line_timings(hltas);
+
+    hltas
+        .lines
+        .iter()
+        .enumerate()
+        .find(|(i, line)| {
+            matches!(line, Line::FrameBulk(_))
+                && timings[*i].frame <= frame
+                && frame < timings[*i + 1].frame
+        })
+        .map(|(i, _)| FramePosition {
+            line_index: i,
+            frame_in_line: (frame - timings[i].frame) as u32,
+        })
+}
+
+/// Finds the framebulk playing at the in-game `time` in seconds
+///
+/// * 0ms ducktap frames are ignored for the frame inside the framebulk
+pub fn find_time(hltas: &HLTAS, time: f64) -> Option<FramePosition> {
+    let timings = line_timings(hltas);
+
+    hltas
+        .lines
+        .iter()
+        .enumerate()
+        .find_map(|(i, line)| match line {
+            Line::FrameBulk(framebulk) if timings[i].time <= time && time < timings[i + 1].time => {
+                let frame_time = framebulk.frame_time.parse::<f64>().ok()?;
+                let frame_in_line = ((time - timings[i].time) / frame_time) as u32;
+
+                Some(FramePosition {
+                    line_index: i,
+                    frame_in_line: frame_in_line.min(framebulk.frame_count.get() - 1),
+                })
+            }
+            _ => None,
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 4 frames of 0.5s, a comment, 4 frames of 0.25s with two 0ms ducktaps of 0.125s, 2 frames of 0.5s
+    fn hltas() -> HLTAS {
+        HLTAS::from_str(
+            "version 1
+frametime0ms 0.125
+frames
+----------|------|------|0.5|-|-|4
+// comment
+-----D2----|------|------|0.25|-|-|4
+----------|------|------|0.5|-|-|2
+",
+        )
+        .unwrap()
+    }
+
+    fn timing(frame: u64, zero_ms_frames: u64, time: f64) -> LineTiming {
+        LineTiming {
+            frame,
+            zero_ms_frames,
+            time,
+        }
+    }
+
+    fn position(line_index: usize, frame_in_line: u32) -> Option<FramePosition> {
+        Some(FramePosition {
+            line_index,
+            frame_in_line,
+        })
+    }
+
+    #[test]
+    fn zero_ms_frames_use_their_frametime() {
+        assert_eq!(
+            line_timings(&hltas()),
+            vec![
+                timing(0, 0, 0.0),
+                timing(4, 0, 2.0),
+                timing(4, 0, 2.0),
+                timing(8, 2, 3.25),
+                timing(10, 2, 4.25),
+            ]
+        );
+    }
+
+    #[test]
+    fn uncountable_zero_ms_frames() {
+        // no limit, and more ducktaps than frames
+        let hltas = HLTAS::from_str(
+            "version 1
+frametime0ms 0.125
+frames
+-----D----|------|------|0.5|-|-|2
+-----D5----|------|------|0.5|-|-|2
+",
+        )
+        .unwrap();
+
+        assert_eq!(
+            line_timings(&hltas),
+            vec![timing(0, 0, 0.0), timing(2, 0, 1.0), timing(4, 2, 2.25)]
+        );
+    }
+
+    #[test]
+    fn find_frames() {
+        let hltas = hltas();
+
+        assert_eq!(find_frame(&hltas, 0), position(0, 0));
+        assert_eq!(find_frame(&hltas, 3), position(0, 3));
+        // the first frame of the next framebulk, skipping the comment
+        assert_eq!(find_frame(&hltas, 4), position(2, 0));
+        assert_eq!(find_frame(&hltas, 9), position(3, 1));
+        assert_eq!(find_frame(&hltas, 10), None);
+    }
+
+    #[test]
+    fn find_times() {
+        let hltas = hltas();
+
+        assert_eq!(find_time(&hltas, 0.0), position(0, 0));
+        assert_eq!(find_time(&hltas, 1.2), position(0, 2));
+        assert_eq!(find_time(&hltas, 2.0), position(2, 0));
+        assert_eq!(find_time(&hltas, 2.6), position(2, 2));
+        // inside the 0ms frames at the end of the framebulk
+        assert_eq!(find_time(&hltas, 3.1), position(2, 3));
+        assert_eq!(find_time(&hltas, 3.25), position(3, 0));
+        assert_eq!(find_time(&hltas, 4.25), None);
+    }
+}
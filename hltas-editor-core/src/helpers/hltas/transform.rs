@@ -0,0 +1,132 @@
+use std::num::NonZeroU32;
+
+use hltas::types::{AutoMovement, ChangeTarget, Line, StrafeDir, VectorialStrafingConstraints};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Rounding {
+    Nearest,
+    Down,
+    Up,
+}
+
+impl Rounding {
+    fn round(self, value: f64) -> f64 {
+        match self {
+            Rounding::Nearest => value.round(),
+            Rounding::Down => value.floor(),
+            Rounding::Up => value.ceil(),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum FrameCountChange {
+    Scale { ratio: f64, rounding: Rounding },
+    Offset(i64),
+}
+
+impl FrameCountChange {
+    /// New frame count, which is kept at least 1
+    pub fn apply(&self, frame_count: NonZeroU32) -> NonZeroU32 {
+        let frame_count = frame_count.get() as f64;
+        let new_frame_count = match *self {
+            FrameCountChange::Scale { ratio, rounding } => rounding.round(frame_count * ratio),
+            FrameCountChange::Offset(offset) => frame_count + offset as f64,
+        };
+
+        // a NaN ratio casts to 0
+        NonZeroU32::new((new_frame_count.clamp(1.0, u32::MAX as f64) as u32).max(1)).unwrap()
+    }
+}
+
+/// Offsets and scales applied to every line of a selection
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Transform {
+    /// Added to every absolute yaw, including vectorial strafing targets
+    pub yaw_offset: f32,
+    /// Added to pitches that are set, the result stays within -89 ~ 89
+    pub pitch_offset: f32,
+    pub frame_count: FrameCountChange,
+}
+
+fn offset_pitch(pitch: &mut f32, offset: f32) {
+    *pitch = (*pitch + offset).clamp(-89.0, 89.0);
+}
+
+impl Transform {
+    /// Applies the transform to the line, returns false if the line didn't change
+    pub fn apply(&self, line: &mut Line) -> bool {
+        let line_before = line.to_owned();
+        let yaw_offset = self.yaw_offset;
+
+        match line {
+            Line::FrameBulk(framebulk) => {
+                match &mut framebulk.auto_actions.movement {
+                    Some(AutoMovement::SetYaw(yaw)) => *yaw += yaw_offset,
+                    Some(AutoMovement::Strafe(strafe_settings)) => match &mut strafe_settings.dir {
+                        StrafeDir::Yaw(yaw) => *yaw += yaw_offset,
+                        StrafeDir::Line { yaw } => *yaw += yaw_offset,
+                        _ => (),
+                    },
+                    None => (),
+                }
+
+                if let Some(pitch) = &mut framebulk.pitch {
+                    offset_pitch(pitch, self.pitch_offset);
+                }
+
+                framebulk.frame_count = self.frame_count.apply(framebulk.frame_count);
+            }
+            Line::TargetYawOverride(yaws) => {
+                for yaw in yaws {
+                    *yaw += yaw_offset;
+                }
+            }
+            Line::VectorialStrafingConstraints(constraints) => match constraints {
+                VectorialStrafingConstraints::Yaw { yaw, .. } => *yaw += yaw_offset,
+                VectorialStrafingConstraints::YawRange { from, to } => {
+                    *from += yaw_offset;
+                    *to += yaw_offset;
+                }
+                _ => (),
+            },
+            Line::Change(change) => match change.target {
+                ChangeTarget::Yaw | ChangeTarget::VectorialStrafingYaw => {
+                    change.final_value += yaw_offset
+                }
+                ChangeTarget::Pitch => offset_pitch(&mut change.final_value, self.pitch_offset),
+            },
+            _ => (),
+        }
+
+        *line != line_before
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn frame_count_is_at_least_one() {
+        let frame_count = NonZeroU32::new(10).unwrap();
+        let one = NonZeroU32::new(1).unwrap();
+
+        for ratio in [0.0, -1.0, f64::NAN, f64::NEG_INFINITY] {
+            let change = FrameCountChange::Scale {
+                ratio,
+                rounding: Rounding::Nearest,
+            };
+            assert_eq!(change.apply(frame_count), one);
+        }
+        assert_eq!(FrameCountChange::Offset(-20).apply(frame_count), one);
+        assert_eq!(
+            FrameCountChange::Scale {
+                ratio: 0.25,
+                rounding: Rounding::Up,
+            }
+            .apply(frame_count),
+            NonZeroU32::new(3).unwrap()
+        );
+    }
+}
@@ -1,4 +1,6 @@
-use fluent_templates::{LanguageIdentifier, Loader};
+use std::collections::HashMap;
+
+use fluent_templates::{fluent_bundle::FluentValue, LanguageIdentifier, Loader};
 use locale_config::Locale;
 use serde::Serialize;
 
@@ -24,7 +26,7 @@ impl LocaleLang {
             Some(lang) => lang.to_owned(),
             // shouldn't error
             None => Locale::user_default()
-                .to_string()
+                .as_ref()
                 .split(',')
                 .collect::<Vec<_>>()[0]
                 .parse()
@@ -47,6 +49,14 @@ impl LocaleLang {
     pub fn get_string_from_id(&self, text_id: &str) -> String {
         LOCALES.lookup(&self.get_lang(), text_id)
     }
+
+    pub fn get_string_from_id_with_args(
+        &self,
+        text_id: &str,
+        args: &HashMap<&str, FluentValue>,
+    ) -> String {
+        LOCALES.lookup_with_args(&self.get_lang(), text_id, args)
+    }
 }
 
 impl Serialize for LocaleLang {
@@ -0,0 +1,2 @@
+pub mod hltas;
+pub mod locale;
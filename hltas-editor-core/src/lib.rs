@@ -0,0 +1,8 @@
+//! Document model of the editor without any gui dependencies, shared by the editor and the `hltas-editor` command line tool
+
+pub mod document;
+pub mod helpers;
+pub mod locale;
+pub mod options;
+pub mod selection;
+pub mod undo_redo_hltas;
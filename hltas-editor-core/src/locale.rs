@@ -2,6 +2,8 @@ fluent_templates::static_loader! {
     pub static LOCALES = {
         locales: "./locales",
         fallback_language: "en-US",
+        // imgui can't render the unicode isolation marks around arguments
+        customise: |bundle| bundle.set_use_isolating(false),
         // core_locales: "./locales/core.ftl",
     };
 }
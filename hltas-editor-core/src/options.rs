@@ -0,0 +1,281 @@
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+};
+
+use hltas::types::LeaveGroundActionSpeed;
+use home::home_dir;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+use crate::helpers::{hltas::frametime, locale::locale_lang::LocaleLang};
+
+/// Options of the editor, saved in `options.json` of the save directory
+///
+/// * `F` are the options only a front-end has, which are stored next to the other options
+#[derive(Clone, Serialize, Deserialize)]
+pub struct AppOptions<F> {
+    pub copy_previous_framebulk: bool,
+    pub jump_lgagst_option: LgagstOption,
+    pub ducktap_lgagst_option: LgagstOption,
+    pub recent_path_size: usize,
+    #[serde(skip_serializing, skip_deserializing)]
+    pub locale_lang: LocaleLang,
+    pub auto_switch_new_tab: bool,
+    pub default_comment: String,
+    pub comment_colour: [f32; 4],
+    pub lgagst_min_speed: f32,
+    pub lgagst_min_speed_grab_prev: bool,
+    pub default_0ms_frametime: f32,
+    pub default_frametime: f32,
+    pub save_buffer_name: String,
+    pub zero_ms_if_property_enabled: bool,
+    #[serde(default)]
+    pub persist_undo_history: bool,
+    /// Seconds between autosaves of unsaved tabs, 0 disables autosave
+    #[serde(default = "default_autosave_interval")]
+    pub autosave_interval: u64,
+    #[serde(flatten)]
+    pub front_end: F,
+}
+
+fn default_autosave_interval() -> u64 {
+    60
+}
+
+/// Directory the options and other editor states are saved in, created if it doesn't exist
+pub fn get_save_dir() -> Result<PathBuf, std::io::Error> {
+    let mut save_dir = match home_dir() {
+        Some(home_dir) => home_dir,
+        None => env::current_dir()?,
+    };
+
+    save_dir.push("hltas-editor");
+
+    if !save_dir.exists() {
+        fs::create_dir(&save_dir)?;
+    }
+
+    Ok(save_dir)
+}
+
+impl<F: Serialize + DeserializeOwned> AppOptions<F> {
+    pub fn option_path() -> Result<PathBuf, std::io::Error> {
+        Ok(get_save_dir()?.join("options.json"))
+    }
+
+    pub fn save_options(&self) -> Result<(), std::io::Error> {
+        self.save_options_to(&Self::option_path()?)
+    }
+
+    pub fn load_options() -> Result<Self, Box<dyn std::error::Error>> {
+        Self::load_options_from(&Self::option_path()?)
+    }
+
+    pub fn save_options_to(&self, path: &Path) -> Result<(), std::io::Error> {
+        let option_data = serde_json::to_string(self).unwrap();
+        fs::write(path, &option_data)?;
+        Ok(())
+    }
+
+    pub fn load_options_from(path: &Path) -> Result<Self, Box<dyn std::error::Error>> {
+        let option_data = fs::read_to_string(path)?;
+        Ok(serde_json::from_str(&option_data)?)
+    }
+}
+
+impl<F> AppOptions<F> {
+    /// Get a reference to the app options's lgagst min speed.
+    pub fn lgagst_min_speed(&self) -> f32 {
+        self.lgagst_min_speed
+    }
+
+    /// Get a reference to the app options's lgagst min speed grab prev.
+    pub fn lgagst_min_speed_grab_prev(&self) -> bool {
+        self.lgagst_min_speed_grab_prev
+    }
+
+    /// Get a reference to the app options's copy previous framebulk.
+    pub fn copy_previous_framebulk(&self) -> bool {
+        self.copy_previous_framebulk
+    }
+
+    /// Get a reference to the app options's default 0ms frametime.
+    pub fn default_0ms_frametime(&self) -> f32 {
+        self.default_0ms_frametime
+    }
+
+    /// Get a reference to the app options's default frametime.
+    pub fn default_frametime(&self) -> f32 {
+        self.default_frametime
+    }
+
+    /// Get a reference to the app options's save buffer name.
+    pub fn save_buffer_name(&self) -> &str {
+        self.save_buffer_name.as_ref()
+    }
+
+    /// Get a reference to the app options's zero ms if property enabled.
+    pub fn zero_ms_if_property_enabled(&self) -> bool {
+        self.zero_ms_if_property_enabled
+    }
+
+    /// Get a reference to the app options's persist undo history.
+    pub fn persist_undo_history(&self) -> bool {
+        self.persist_undo_history
+    }
+
+    /// Get a reference to the app options's autosave interval.
+    pub fn autosave_interval(&self) -> u64 {
+        self.autosave_interval
+    }
+
+    /// Get a reference to the app options's jump lgagst option.
+    pub fn jump_lgagst_option(&self) -> &LgagstOption {
+        &self.jump_lgagst_option
+    }
+
+    /// Get a reference to the app options's ducktap lgagst option.
+    pub fn ducktap_lgagst_option(&self) -> &LgagstOption {
+        &self.ducktap_lgagst_option
+    }
+
+    /// Get a reference to the app options's recent path size.
+    pub fn recent_path_size(&self) -> usize {
+        self.recent_path_size
+    }
+
+    /// Get a reference to the app options's locale lang.
+    pub fn locale_lang(&self) -> &LocaleLang {
+        &self.locale_lang
+    }
+
+    /// Get a reference to the app options's auto switch new tab.
+    pub fn auto_switch_new_tab(&self) -> bool {
+        self.auto_switch_new_tab
+    }
+
+    /// Get a reference to the app options's default comment.
+    pub fn default_comment(&self) -> &str {
+        &self.default_comment
+    }
+
+    /// Get a reference to the app options's comment colour.
+    pub fn comment_colour(&self) -> [f32; 4] {
+        self.comment_colour
+    }
+}
+
+impl<F: Default> Default for AppOptions<F> {
+    fn default() -> Self {
+        Self {
+            copy_previous_framebulk: true,
+            jump_lgagst_option: Default::default(),
+            ducktap_lgagst_option: Default::default(),
+            recent_path_size: 20,
+            locale_lang: LocaleLang::new(None),
+            auto_switch_new_tab: true,
+            default_comment: "".to_string(),
+            comment_colour: [0.0, 1.0, 0.0, 1.0],
+            lgagst_min_speed: 30.0,
+            lgagst_min_speed_grab_prev: true,
+            default_0ms_frametime: 0.0000000001,
+            default_frametime: frametime::MAX_STRAFE,
+            save_buffer_name: "buffer".to_string(),
+            zero_ms_if_property_enabled: true,
+            persist_undo_history: false,
+            autosave_interval: default_autosave_interval(),
+            front_end: F::default(),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(remote = "LeaveGroundActionSpeed")]
+enum LeaveGroundActionSpeedDef {
+    Any,
+    Optimal,
+    OptimalWithFullMaxspeed,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct LgagstOption {
+    #[serde(with = "LeaveGroundActionSpeedDef")]
+    pub default_selection: LeaveGroundActionSpeed,
+}
+
+impl Default for LgagstOption {
+    fn default() -> Self {
+        Self {
+            default_selection: LeaveGroundActionSpeed::Optimal,
+        }
+    }
+}
+
+impl LgagstOption {
+    /// Get a reference to the lgagst option's default selection.
+    pub fn default_selection(&self) -> LeaveGroundActionSpeed {
+        self.default_selection
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Clone, Default, PartialEq, Debug, Serialize, Deserialize)]
+    struct FrontEndOptions {
+        #[serde(default)]
+        theme: String,
+    }
+
+    #[test]
+    fn save_and_load() {
+        let path = std::env::temp_dir().join("hltas-editor-core-options-test.json");
+        let options = AppOptions {
+            default_comment: "comment".to_string(),
+            ducktap_lgagst_option: LgagstOption {
+                default_selection: LeaveGroundActionSpeed::Any,
+            },
+            front_end: FrontEndOptions {
+                theme: "dark".to_string(),
+            },
+            ..Default::default()
+        };
+
+        options.save_options_to(&path).unwrap();
+        let loaded = AppOptions::<FrontEndOptions>::load_options_from(&path).unwrap();
+        fs::remove_file(&path).ok();
+
+        assert_eq!(loaded.default_comment(), "comment");
+        assert_eq!(
+            loaded.ducktap_lgagst_option().default_selection(),
+            LeaveGroundActionSpeed::Any
+        );
+        assert_eq!(loaded.front_end, options.front_end);
+    }
+
+    #[test]
+    fn front_end_options_are_stored_next_to_the_others() {
+        let mut options = AppOptions::<FrontEndOptions>::default();
+        options.front_end.theme = "dark".to_string();
+
+        let option_data = serde_json::to_value(&options).unwrap();
+        assert_eq!(option_data["theme"], "dark");
+        assert_eq!(option_data["recent_path_size"], 20);
+    }
+
+    #[test]
+    fn missing_new_options_use_defaults() {
+        let mut option_data =
+            serde_json::to_value(AppOptions::<FrontEndOptions>::default()).unwrap();
+        let option_map = option_data.as_object_mut().unwrap();
+        option_map.remove("persist_undo_history");
+        option_map.remove("autosave_interval");
+        option_map.remove("theme");
+
+        let options: AppOptions<FrontEndOptions> = serde_json::from_value(option_data).unwrap();
+        assert!(!options.persist_undo_history());
+        assert_eq!(options.autosave_interval(), 60);
+        assert_eq!(options.front_end, FrontEndOptions::default());
+    }
+}
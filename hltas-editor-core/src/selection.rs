@@ -0,0 +1,132 @@
+use std::ops::Range;
+
+use hltas::{types::Line, HLTAS};
+
+use crate::document::LineStates;
+
+/// Selected state of each line
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Selection {
+    selected_indexes: Vec<bool>,
+}
+
+impl Selection {
+    /// Nothing selected for `line_count` lines
+    pub fn new(line_count: usize) -> Self {
+        Self {
+            selected_indexes: vec![false; line_count],
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.selected_indexes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.selected_indexes.is_empty()
+    }
+
+    pub fn is_selected(&self, index: usize) -> bool {
+        self.selected_indexes[index]
+    }
+
+    pub fn selected_indexes(&self) -> &[bool] {
+        &self.selected_indexes
+    }
+
+    /// Indexes of the selected lines in order
+    pub fn indexes(&self) -> Vec<usize> {
+        self.selected_indexes
+            .iter()
+            .enumerate()
+            .filter_map(|(i, is_selected)| if *is_selected { Some(i) } else { None })
+            .collect()
+    }
+
+    pub fn set(&mut self, index: usize, state: bool) {
+        self.selected_indexes[index] = state;
+    }
+
+    pub fn set_range(&mut self, range: Range<usize>, state: bool) {
+        self.selected_indexes[range]
+            .iter_mut()
+            .for_each(|is_selected| *is_selected = state);
+    }
+
+    pub fn clear(&mut self) {
+        self.set_range(0..self.len(), false);
+    }
+
+    pub fn select_all(&mut self) {
+        self.set_range(0..self.len(), true);
+    }
+}
+
+impl LineStates for Selection {
+    fn insert_line(&mut self, index: usize, _: &Line) {
+        self.selected_indexes.insert(index, false);
+    }
+
+    fn remove_line(&mut self, index: usize) {
+        self.selected_indexes.remove(index);
+    }
+
+    fn update_line(&mut self, _: usize, _: &Line) {}
+
+    fn reset_lines(&mut self, hltas: &HLTAS) {
+        *self = Self::new(hltas.lines.len());
+    }
+
+    fn selection(&self) -> &Selection {
+        self
+    }
+
+    fn selection_mut(&mut self) -> &mut Selection {
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn indexes_are_in_order() {
+        let mut selection = Selection::new(5);
+        selection.set(3, true);
+        selection.set(1, true);
+
+        assert_eq!(selection.indexes(), vec![1, 3]);
+        assert!(selection.is_selected(1));
+        assert!(!selection.is_selected(2));
+    }
+
+    #[test]
+    fn range_all_and_clear() {
+        let mut selection = Selection::new(4);
+        selection.set_range(1..3, true);
+        assert_eq!(selection.indexes(), vec![1, 2]);
+
+        selection.select_all();
+        assert_eq!(selection.indexes(), vec![0, 1, 2, 3]);
+
+        selection.clear();
+        assert!(selection.indexes().is_empty());
+        assert_eq!(selection.len(), 4);
+    }
+
+    #[test]
+    fn follows_inserted_and_removed_lines() {
+        let line = Line::Comment(String::new());
+        let mut selection = Selection::new(3);
+        selection.set(1, true);
+
+        selection.insert_line(0, &line);
+        assert_eq!(selection.indexes(), vec![2]);
+
+        selection.remove_line(0);
+        selection.remove_line(0);
+        assert_eq!(selection.indexes(), vec![0]);
+        assert_eq!(selection.len(), 2);
+    }
+}
@@ -0,0 +1,571 @@
+use std::{collections::HashMap, convert::TryFrom};
+
+use fluent_templates::fluent_bundle::FluentValue;
+use hltas::{
+    types::{Line, Properties},
+    HLTAS,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    document::LineStates,
+    helpers::{
+        hltas::{hltas_to_str, lines_to_str, str_to_lines},
+        locale::locale_lang::LocaleLang,
+    },
+};
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(into = "ActionData", try_from = "ActionData")]
+enum Action {
+    Delete {
+        indexes_and_lines: Vec<(usize, Line)>,
+    },
+    Add {
+        indexes: Vec<usize>,
+    },
+    Edit {
+        line: Line,
+        index: usize,
+    },
+    EditProperties {
+        properties: Properties,
+    },
+    /// Multiple actions that get undone / redone as one
+    Group(Vec<Action>),
+}
+
+impl Action {
+    /// Takes action to the hltas file depending on what enum is selected
+    ///
+    /// * `line_states` are kept in sync with the lines
+    /// * Returns a reverse of what action was taken in a new Action instance
+    fn take_action<S: LineStates>(&self, hltas: &mut HLTAS, line_states: &mut S) -> Self {
+        match self {
+            Action::Delete { indexes_and_lines } => {
+                for (i, line) in indexes_and_lines {
+                    if hltas.lines.is_empty() {
+                        line_states.insert_line(0, line);
+                        hltas.lines.push(line.to_owned());
+                    } else {
+                        line_states.insert_line(*i, line);
+                        hltas.lines.insert(*i, line.to_owned());
+                    }
+                }
+
+                Action::Add {
+                    indexes: indexes_and_lines.iter().map(|(i, _)| *i).collect(),
+                }
+            }
+            Action::Add { indexes } => {
+                let indexes_and_lines = indexes
+                    .iter()
+                    .map(|i| (*i, hltas.lines[*i].to_owned()))
+                    .collect();
+
+                for i in indexes.iter().rev() {
+                    line_states.remove_line(*i);
+                    hltas.lines.remove(*i);
+                }
+
+                Action::Delete { indexes_and_lines }
+            }
+            Action::Edit { line, index } => {
+                let line_before_edit = hltas.lines[*index].to_owned();
+
+                line_states.update_line(*index, line);
+                hltas.lines[*index] = line.to_owned();
+
+                Action::Edit {
+                    line: line_before_edit,
+                    index: *index,
+                }
+            }
+            Action::EditProperties { properties } => {
+                let properties_before_edit =
+                    std::mem::replace(&mut hltas.properties, properties.to_owned());
+
+                Action::EditProperties {
+                    properties: properties_before_edit,
+                }
+            }
+            Action::Group(actions) => {
+                // actions are taken in reverse, so the reversed actions are already in the right order
+                let reverse_actions = actions
+                    .iter()
+                    .rev()
+                    .map(|action| action.take_action(hltas, line_states))
+                    .collect();
+
+                Action::Group(reverse_actions)
+            }
+        }
+    }
+}
+
+/// Action in a serializable form, the lines and properties are stored in the hltas file format
+#[derive(Serialize, Deserialize)]
+enum ActionData {
+    Delete { indexes: Vec<usize>, lines: String },
+    Add { indexes: Vec<usize> },
+    Edit { line: String, index: usize },
+    EditProperties { properties: String },
+    Group(Vec<ActionData>),
+}
+
+impl From<Action> for ActionData {
+    fn from(action: Action) -> Self {
+        match action {
+            Action::Delete { indexes_and_lines } => {
+                let (indexes, lines): (Vec<_>, Vec<_>) = indexes_and_lines.into_iter().unzip();
+
+                ActionData::Delete {
+                    indexes,
+                    lines: lines_to_str(lines),
+                }
+            }
+            Action::Add { indexes } => ActionData::Add { indexes },
+            Action::Edit { line, index } => ActionData::Edit {
+                line: lines_to_str(vec![line]),
+                index,
+            },
+            Action::EditProperties { properties } => ActionData::EditProperties {
+                properties: hltas_to_str(&HLTAS {
+                    properties,
+                    ..Default::default()
+                }),
+            },
+            Action::Group(actions) => {
+                ActionData::Group(actions.into_iter().map(ActionData::from).collect())
+            }
+        }
+    }
+}
+
+impl TryFrom<ActionData> for Action {
+    type Error = String;
+
+    fn try_from(action_data: ActionData) -> Result<Self, Self::Error> {
+        let parse_lines = |lines: &str, count: usize| match str_to_lines(lines) {
+            Some(lines) if lines.len() == count => Ok(lines),
+            _ => Err(format!("invalid lines in undo history: {}", lines)),
+        };
+
+        Ok(match action_data {
+            ActionData::Delete { indexes, lines } => {
+                let lines = parse_lines(&lines, indexes.len())?;
+
+                Action::Delete {
+                    indexes_and_lines: indexes.into_iter().zip(lines).collect(),
+                }
+            }
+            ActionData::Add { indexes } => Action::Add { indexes },
+            ActionData::Edit { line, index } => Action::Edit {
+                line: parse_lines(&line, 1)?.remove(0),
+                index,
+            },
+            ActionData::EditProperties { properties } => Action::EditProperties {
+                properties: HLTAS::from_str(&properties)
+                    .map_err(|err| err.to_string())?
+                    .properties,
+            },
+            ActionData::Group(actions) => Action::Group(
+                actions
+                    .into_iter()
+                    .map(Action::try_from)
+                    .collect::<Result<_, _>>()?,
+            ),
+        })
+    }
+}
+
+/// What kind of change was done to the hltas
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+enum ChangeKind {
+    Add,
+    Delete,
+    Edit,
+    EditProperties,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct Change {
+    kind: ChangeKind,
+    first_index: usize,
+    line_count: usize,
+    /// Only set if a single line got changed
+    line_type: Option<String>,
+}
+
+/// Readable description of an undo point, it stays the same when undoing / redoing the action
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct ActionDescription {
+    changes: Vec<Change>,
+}
+
+impl ActionDescription {
+    fn new(action: &Action) -> Self {
+        let mut description = Self::default();
+        description.add_action(action);
+        description
+    }
+
+    fn add_action(&mut self, action: &Action) {
+        match action {
+            Action::Delete { indexes_and_lines } => self.add_change(
+                ChangeKind::Delete,
+                indexes_and_lines.iter().map(|(i, _)| *i).min().unwrap_or(0),
+                indexes_and_lines.len(),
+                indexes_and_lines.first().map(|(_, line)| line_type(line)),
+            ),
+            Action::Add { indexes } => self.add_change(
+                ChangeKind::Add,
+                indexes.iter().copied().min().unwrap_or(0),
+                indexes.len(),
+                None,
+            ),
+            Action::Edit { line, index } => {
+                self.add_change(ChangeKind::Edit, *index, 1, Some(line_type(line)))
+            }
+            Action::EditProperties { .. } => {
+                self.add_change(ChangeKind::EditProperties, 0, 0, None)
+            }
+            Action::Group(actions) => {
+                for action in actions {
+                    self.add_action(action);
+                }
+            }
+        }
+    }
+
+    fn add_change(
+        &mut self,
+        kind: ChangeKind,
+        first_index: usize,
+        line_count: usize,
+        line_type: Option<&'static str>,
+    ) {
+        match self.changes.iter_mut().find(|change| change.kind == kind) {
+            Some(change) => {
+                change.first_index = change.first_index.min(first_index);
+                change.line_count += line_count;
+                change.line_type = None;
+            }
+            None => self.changes.push(Change {
+                kind,
+                first_index,
+                line_count,
+                line_type: if line_count == 1 {
+                    line_type.map(str::to_string)
+                } else {
+                    None
+                },
+            }),
+        }
+    }
+
+    pub fn to_locale_string(&self, locale_lang: &LocaleLang) -> String {
+        self.changes
+            .iter()
+            .map(|change| {
+                let mut args = HashMap::new();
+                args.insert("line", FluentValue::from(change.first_index + 1));
+                args.insert("count", FluentValue::from(change.line_count));
+                args.insert(
+                    "type",
+                    FluentValue::from(change.line_type.as_deref().unwrap_or_default()),
+                );
+
+                let text_id = match (change.kind, change.line_count == 1) {
+                    (ChangeKind::Add, true) => "undo-add-line",
+                    (ChangeKind::Add, false) => "undo-add-lines",
+                    (ChangeKind::Delete, true) => "undo-delete-line",
+                    (ChangeKind::Delete, false) => "undo-delete-lines",
+                    (ChangeKind::Edit, true) => "undo-edit-line",
+                    (ChangeKind::Edit, false) => "undo-edit-lines",
+                    (ChangeKind::EditProperties, _) => "undo-edit-properties",
+                };
+
+                locale_lang.get_string_from_id_with_args(text_id, &args)
+            })
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+/// Name of the line type as it's written in the hltas file
+fn line_type(line: &Line) -> &'static str {
+    match line {
+        Line::FrameBulk(_) => "framebulk",
+        Line::Save(_) => "save",
+        Line::SharedSeed(_) => "seed",
+        Line::Buttons(_) => "buttons",
+        Line::LGAGSTMinSpeed(_) => "lgagstminspeed",
+        Line::Reset { .. } => "reset",
+        Line::Comment(_) => "comment",
+        Line::VectorialStrafing(_) => "vectorial_strafing",
+        Line::VectorialStrafingConstraints(_) => "target_yaw",
+        Line::Change(_) => "change",
+        Line::TargetYawOverride(_) => "target_yaw_override",
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct UndoPoint {
+    action: Action,
+    description: ActionDescription,
+}
+
+impl UndoPoint {
+    fn new(action: Action) -> Self {
+        Self {
+            description: ActionDescription::new(&action),
+            action,
+        }
+    }
+
+    pub fn description(&self) -> &ActionDescription {
+        &self.description
+    }
+
+    /// Takes the action and keeps the description for the reversed action
+    fn take_action<S: LineStates>(self, hltas: &mut HLTAS, line_states: &mut S) -> Self {
+        Self {
+            action: self.action.take_action(hltas, line_states),
+            description: self.description,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct UndoRedoHandler {
+    undo_stack: Vec<UndoPoint>,
+    redo_stack: Vec<UndoPoint>,
+    /// How many begin_group calls haven't been ended yet
+    #[serde(skip)]
+    group_depth: usize,
+    #[serde(skip)]
+    group_actions: Vec<Action>,
+}
+
+impl UndoRedoHandler {
+    /// Returns false if there was nothing to undo
+    pub fn undo<S: LineStates>(&mut self, hltas: &mut HLTAS, line_states: &mut S) -> bool {
+        match self.undo_stack.pop() {
+            Some(undo_point) => {
+                self.redo_stack
+                    .push(undo_point.take_action(hltas, line_states));
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Returns false if there was nothing to redo
+    pub fn redo<S: LineStates>(&mut self, hltas: &mut HLTAS, line_states: &mut S) -> bool {
+        match self.redo_stack.pop() {
+            Some(redo_point) => {
+                self.undo_stack
+                    .push(redo_point.take_action(hltas, line_states));
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Undo points from the oldest to the latest
+    pub fn undo_stack(&self) -> &[UndoPoint] {
+        &self.undo_stack
+    }
+
+    /// Redo points, the last one is the next to redo
+    pub fn redo_stack(&self) -> &[UndoPoint] {
+        &self.redo_stack
+    }
+
+    fn push_action(&mut self, action: Action) {
+        self.redo_stack.clear();
+
+        if self.group_depth > 0 {
+            self.group_actions.push(action);
+        } else {
+            self.undo_stack.push(UndoPoint::new(action));
+        }
+    }
+
+    /// Starts recording actions into a group that gets undone / redone as a single undo point
+    ///
+    /// * Groups can be nested, everything is recorded into the outermost group
+    /// * Must be followed with end_group
+    pub fn begin_group(&mut self) {
+        self.group_depth += 1;
+    }
+
+    /// Ends the group started with begin_group
+    ///
+    /// * Empty groups don't create an undo point
+    pub fn end_group(&mut self) {
+        if self.group_depth == 0 {
+            return;
+        }
+
+        self.group_depth -= 1;
+
+        if self.group_depth == 0 {
+            let mut actions = std::mem::take(&mut self.group_actions);
+
+            match actions.len() {
+                0 => (),
+                1 => self.undo_stack.push(UndoPoint::new(actions.remove(0))),
+                _ => self.undo_stack.push(UndoPoint::new(Action::Group(actions))),
+            }
+        }
+    }
+
+    pub fn delete_lines(&mut self, deleted_lines: Vec<(usize, Line)>) {
+        if deleted_lines.is_empty() {
+            return;
+        }
+
+        self.push_action(Action::Delete {
+            indexes_and_lines: deleted_lines,
+        });
+    }
+
+    pub fn add_lines(&mut self, indexes: Vec<usize>) {
+        if indexes.is_empty() {
+            return;
+        }
+
+        self.push_action(Action::Add { indexes });
+    }
+
+    // BUG undo on comment while having the cursor focused seems to break undo somehow
+    pub fn edit_line(&mut self, prev_state: Line, index: usize) {
+        self.push_action(Action::Edit {
+            index,
+            line: prev_state,
+        });
+    }
+
+    pub fn edit_properties(&mut self, prev_state: Properties) {
+        self.push_action(Action::EditProperties {
+            properties: prev_state,
+        });
+    }
+
+    /// Edit of multiple lines as a single undo point
+    pub fn edit_lines(&mut self, prev_states: Vec<(usize, Line)>) {
+        self.begin_group();
+        for (index, prev_state) in prev_states {
+            self.edit_line(prev_state, index);
+        }
+        self.end_group();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::selection::Selection;
+
+    use super::*;
+
+    fn hltas(lines: &str) -> HLTAS {
+        HLTAS::from_str(&format!("version 1\nframes\n{}", lines)).unwrap()
+    }
+
+    fn comment(text: &str) -> Line {
+        Line::Comment(text.to_string())
+    }
+
+    #[test]
+    fn undo_redo_edit() {
+        let mut hltas = hltas("// a");
+        let mut selection = Selection::new(1);
+        let mut undo_redo_handler = UndoRedoHandler::default();
+
+        hltas.lines[0] = comment(" b");
+        undo_redo_handler.edit_line(comment(" a"), 0);
+
+        assert!(undo_redo_handler.undo(&mut hltas, &mut selection));
+        assert_eq!(hltas.lines, vec![comment(" a")]);
+        assert!(!undo_redo_handler.undo(&mut hltas, &mut selection));
+
+        assert!(undo_redo_handler.redo(&mut hltas, &mut selection));
+        assert_eq!(hltas.lines, vec![comment(" b")]);
+        assert!(!undo_redo_handler.redo(&mut hltas, &mut selection));
+    }
+
+    #[test]
+    fn new_action_clears_redo() {
+        let mut hltas = hltas("// a");
+        let mut selection = Selection::new(1);
+        let mut undo_redo_handler = UndoRedoHandler::default();
+
+        undo_redo_handler.edit_line(comment(" a"), 0);
+        undo_redo_handler.undo(&mut hltas, &mut selection);
+        assert_eq!(undo_redo_handler.redo_stack().len(), 1);
+
+        undo_redo_handler.edit_line(comment(" a"), 0);
+        assert!(undo_redo_handler.redo_stack().is_empty());
+    }
+
+    #[test]
+    fn nested_groups_are_one_undo_point() {
+        let mut hltas = hltas("// a\n// b");
+        let mut selection = Selection::new(2);
+        let mut undo_redo_handler = UndoRedoHandler::default();
+
+        undo_redo_handler.begin_group();
+        hltas.lines[0] = comment(" x");
+        undo_redo_handler.edit_line(comment(" a"), 0);
+        undo_redo_handler.begin_group();
+        undo_redo_handler.delete_lines(vec![(1, hltas.lines.remove(1))]);
+        selection.remove_line(1);
+        undo_redo_handler.end_group();
+        undo_redo_handler.end_group();
+
+        // empty groups don't create undo points
+        undo_redo_handler.begin_group();
+        undo_redo_handler.end_group();
+
+        assert_eq!(undo_redo_handler.undo_stack().len(), 1);
+        undo_redo_handler.undo(&mut hltas, &mut selection);
+        assert_eq!(hltas.lines, vec![comment(" a"), comment(" b")]);
+        assert_eq!(selection.len(), 2);
+    }
+
+    #[test]
+    fn serialize_round_trip() {
+        let mut hltas = hltas("// a\n// b");
+        let mut selection = Selection::new(2);
+        let mut undo_redo_handler = UndoRedoHandler::default();
+
+        undo_redo_handler.delete_lines(vec![(1, hltas.lines.remove(1))]);
+        selection.remove_line(1);
+        let properties = hltas.properties.to_owned();
+        hltas.properties.demo = Some("demo".to_string());
+        undo_redo_handler.edit_properties(properties);
+
+        let data = serde_json::to_string(&undo_redo_handler).unwrap();
+        let mut undo_redo_handler: UndoRedoHandler = serde_json::from_str(&data).unwrap();
+
+        undo_redo_handler.undo(&mut hltas, &mut selection);
+        undo_redo_handler.undo(&mut hltas, &mut selection);
+        assert_eq!(hltas.lines, vec![comment(" a"), comment(" b")]);
+        assert_eq!(hltas.properties.demo, None);
+    }
+
+    #[test]
+    fn description_keeps_line_type() {
+        let mut undo_redo_handler = UndoRedoHandler::default();
+        undo_redo_handler.delete_lines(vec![(3, comment(" a"))]);
+
+        let locale_lang = LocaleLang::new(Some("en-US".parse().unwrap()));
+        let description = undo_redo_handler.undo_stack()[0]
+            .description()
+            .to_locale_string(&locale_lang);
+        assert!(description.contains("comment"), "{}", description);
+        assert!(description.contains('4'), "{}", description);
+    }
+}
@@ -1,56 +0,0 @@
-file-menu = File
-edit-menu = Edit
-search-menu = Search
-tools-menu = Tools
-options-menu = Options
-
-new-file = New
-open-file = Open
-save-file = Save
-close-file = Close
-recent-files = Recent files
-
-undo = Undo
-redo = Redo
-cut = Cut
-copy = Copy
-paste = Paste
-select-all = Select all
-
-goto-line = Goto line
-jump-to-line = Jump
-
-no-dupe-framebulk = No duplicate framebulk
-no-comments = No comments
-
-open-options-menu = Options menu
-toggle-graphics-editor = Toggle graphics editor
-toggle-simple-view = Toggle simple view
-
-new-file-title = New file
-
-save-file-question = Would you like to save the modified file?
-
-warning = Warning!
-error = Error
-
-frametime-f32-parse-err = Cannot parse frametime as f32
-setting-default-frametime = Setting frametime to default option
-
-properties = Properties
-demo-name = Demo name
-set-demo-recording = Set demo recording
-save-name = Save name
-save-after-hltas = Save after hltas
-enable-0ms-ducktap = Enable 0ms ducktap
-zero-ms-frametime = 0ms frametime
-enable-shared-nonshared = Enable shared / non-shared rng
-set-hlstrafe-version = Set hlstrafe version
-hlstrafe-version = hlstrafe version
-set-hltas-load-commands = Set hltas load commands
-load-commands = Load commands
-commands = Commands
-shared-rng = Shared rng
-non-shared-rng = Non-shared rng
-
-lines = Lines
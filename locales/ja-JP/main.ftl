@@ -1,56 +0,0 @@
-file-menu = ファイル
-edit-menu = 編集
-search-menu = 検索
-tools-menu = ツール
-options-menu = オプション
-
-new-file = 新規作成
-open-file = 開く
-save-file = 保存
-close-file = 閉じる
-recent-files = 最近使用したファイル
-
-undo = 元に戻す
-redo = やり直す
-cut = 切り取り
-copy = コピー
-paste = 貼り付け
-select-all = すべて選択
-
-goto-line = 行へジャンプ
-jump-to-line = ジャンプ
-
-no-dupe-framebulk = 複製のframebulkを一つに合わせる
-no-comments = コメントを消す
-
-open-options-menu = オプションメニュー
-toggle-graphics-editor = グラフィックエディタの切り替え
-toggle-simple-view = 簡単表示
-
-new-file-title = 新規
-
-save-file-question = 編集したファイルを保存したいですか？
-
-warning = 警告
-error = エラー
-
-frametime-f32-parse-err = フレームタイムをf32として解析できません
-setting-default-frametime = ディフォルトのフレームタイムに変えます
-
-properties = プロパティ
-demo-name = デモ名
-set-demo-recording = デモを録画する
-save-name = セーブ名
-save-after-hltas = hltas実行後にセーブ
-enable-0ms-ducktap = 0ms ダックタップを有効にする
-zero-ms-frametime = 0ms フレームタイム
-enable-shared-nonshared = 共用 / 非共用 乱数を有効
-set-hlstrafe-version = hlstrafeバージョンを選択する
-hlstrafe-version = hlstrafeバージョン
-set-hltas-load-commands = hltasロードコマンドを選択する
-load-commands = ロードコマンド
-commands = コマンド
-shared-rng = 共用乱数
-non-shared-rng = 非共用乱数
-
-lines = 行
@@ -0,0 +1,242 @@
+//! Command line tool for hltas files, which builds without the gui dependencies
+//!
+//! * Exit code is 0 on success, 1 if a check failed, and 2 for usage or io errors
+
+use std::{fs, path::Path, process};
+
+use hltas::HLTAS;
+use hltas_editor_core::helpers::{
+    hltas::{
+        check_hltas,
+        cleaner::Cleaner,
+        csv::{csv_to_hltas, hltas_to_csv},
+        hltas_to_str,
+        json::{hltas_to_json, json_to_hltas},
+        lint::{lint, Severity},
+        stats::ScriptStats,
+    },
+    locale::locale_lang::LocaleLang,
+};
+
+const USAGE: &str = "usage: hltas-editor <command> [options] <files>...
+
+commands:
+    fmt [--check]                  rewrites the files in the standard format
+    check                          reports parse errors and problems
+    stats                          prints the statistics of the files
+    clean [--no-dupe] [--no-comments]
+                                   removes duplicate framebulks / comments
+    export [--json]                writes the files as csv, or json, next to them
+    import                         reads csv or json files into hltas files next to them";
+
+const SUCCESS: i32 = 0;
+const CHECK_FAILED: i32 = 1;
+const USAGE_OR_IO_ERROR: i32 = 2;
+
+/// Reads and parses the file, the error is printed and returned as the exit code
+fn read_hltas(path: &str) -> Result<(String, HLTAS), i32> {
+    let file_content = fs::read_to_string(path).map_err(|err| {
+        eprintln!("{}: {}", path, err);
+        USAGE_OR_IO_ERROR
+    })?;
+
+    let hltas = HLTAS::from_str(&file_content).map_err(|err| {
+        // the error input is the rest of the file from where parsing failed
+        let offset = file_content.len() - err.input.len();
+        let line_number = file_content[..offset].matches('\n').count() + 1;
+        eprintln!("{}:{}: {}", path, line_number, err);
+        CHECK_FAILED
+    })?;
+
+    Ok((file_content, hltas))
+}
+
+fn write_hltas(path: &str, hltas: &HLTAS) -> Result<(), i32> {
+    write_file(Path::new(path), &hltas_to_str(hltas))
+}
+
+fn write_file(path: &Path, content: &str) -> Result<(), i32> {
+    fs::write(path, content).map_err(|err| {
+        eprintln!("{}: {}", path.display(), err);
+        USAGE_OR_IO_ERROR
+    })
+}
+
+fn fmt(path: &str, check: bool) -> Result<(), i32> {
+    let (file_content, hltas) = read_hltas(path)?;
+
+    if hltas_to_str(&hltas) == file_content {
+        return Ok(());
+    }
+
+    if check {
+        eprintln!("{}: not formatted", path);
+        Err(CHECK_FAILED)
+    } else {
+        write_hltas(path, &hltas)
+    }
+}
+
+fn check(path: &str, locale_lang: &LocaleLang) -> Result<(), i32> {
+    let (_, hltas) = read_hltas(path)?;
+    let diagnostics = lint(&hltas);
+
+    for diagnostic in &diagnostics {
+        // same line numbers as the editor
+        eprintln!(
+            "{}: line {}: {}: {}",
+            path,
+            diagnostic.line_index + 1,
+            match diagnostic.severity() {
+                Severity::Warning => "warning",
+                Severity::Error => "error",
+            },
+            locale_lang.get_string_from_id(diagnostic.kind.id())
+        );
+    }
+
+    if diagnostics
+        .iter()
+        .any(|diagnostic| diagnostic.severity() == Severity::Error)
+    {
+        Err(CHECK_FAILED)
+    } else {
+        Ok(())
+    }
+}
+
+fn stats(path: &str) -> Result<(), i32> {
+    let (_, hltas) = read_hltas(path)?;
+    println!("{}:\n{}\n", path, ScriptStats::new(&hltas));
+    Ok(())
+}
+
+fn clean(path: &str, cleaners: &[Cleaner]) -> Result<(), i32> {
+    let (_, mut hltas) = read_hltas(path)?;
+
+    let lines_removed = cleaners
+        .iter()
+        .map(|cleaner| cleaner.cleaner_fn()(&mut hltas).lines_removed.len())
+        .sum::<usize>();
+    println!("{}: removed {} lines", path, lines_removed);
+
+    write_hltas(path, &hltas)
+}
+
+fn export(path: &str, json: bool) -> Result<(), i32> {
+    let (_, hltas) = read_hltas(path)?;
+
+    let (extension, content) = if json {
+        ("json", hltas_to_json(&hltas))
+    } else {
+        ("csv", hltas_to_csv(&hltas))
+    };
+
+    write_file(&Path::new(path).with_extension(extension), &content)
+}
+
+/// Files ending in `.json` are read as json, others as csv
+fn import(path: &str) -> Result<(), i32> {
+    let path = Path::new(path);
+    let file_content = fs::read_to_string(path).map_err(|err| {
+        eprintln!("{}: {}", path.display(), err);
+        USAGE_OR_IO_ERROR
+    })?;
+
+    let hltas = if path
+        .extension()
+        .is_some_and(|extension| extension == "json")
+    {
+        json_to_hltas(&file_content).map_err(|err| err.to_string())
+    } else {
+        csv_to_hltas(&file_content).map_err(|err| err.to_string())
+    }
+    .and_then(|hltas| {
+        check_hltas(&hltas)
+            .map(|_| hltas)
+            .map_err(|err| err.to_string())
+    })
+    .map_err(|err| {
+        eprintln!("{}: {}", path.display(), err);
+        CHECK_FAILED
+    })?;
+
+    write_file(&path.with_extension("hltas"), &hltas_to_str(&hltas))
+}
+
+fn run(args: &[String]) -> i32 {
+    let (command, args) = match args.split_first() {
+        Some((command, args)) => (command.as_str(), args),
+        None => {
+            eprintln!("{}", USAGE);
+            return USAGE_OR_IO_ERROR;
+        }
+    };
+
+    let (flags, paths): (Vec<_>, Vec<_>) = args.iter().partition(|arg| arg.starts_with("--"));
+    let has_flag = |flag: &str| flags.iter().any(|arg| *arg == flag);
+    let allowed_flags: &[&str] = match command {
+        "fmt" => &["--check"],
+        "check" | "stats" => &[],
+        "clean" => &["--no-dupe", "--no-comments"],
+        "export" => &["--json"],
+        "import" => &[],
+        _ => {
+            eprintln!("unknown command `{}`\n{}", command, USAGE);
+            return USAGE_OR_IO_ERROR;
+        }
+    };
+
+    if let Some(flag) = flags
+        .iter()
+        .find(|flag| !allowed_flags.contains(&flag.as_str()))
+    {
+        eprintln!("unknown option `{}` for `{}`\n{}", flag, command, USAGE);
+        return USAGE_OR_IO_ERROR;
+    }
+    if paths.is_empty() {
+        eprintln!("no files given\n{}", USAGE);
+        return USAGE_OR_IO_ERROR;
+    }
+
+    let cleaners = [
+        ("--no-dupe", Cleaner::NoDupeFramebulks),
+        ("--no-comments", Cleaner::RemoveComments),
+    ]
+    .iter()
+    .filter(|(flag, _)| has_flag(flag))
+    .map(|(_, cleaner)| *cleaner)
+    .collect::<Vec<_>>();
+    if command == "clean" && cleaners.is_empty() {
+        eprintln!("no cleaners given\n{}", USAGE);
+        return USAGE_OR_IO_ERROR;
+    }
+
+    let locale_lang = LocaleLang::default();
+
+    // every file is processed, the worst result is the exit code
+    paths
+        .iter()
+        .map(|path| {
+            let result = match command {
+                "fmt" => fmt(path, has_flag("--check")),
+                "check" => check(path, &locale_lang),
+                "stats" => stats(path),
+                "export" => export(path, has_flag("--json")),
+                "import" => import(path),
+                _ => clean(path, &cleaners),
+            };
+
+            match result {
+                Ok(()) => SUCCESS,
+                Err(exit_code) => exit_code,
+            }
+        })
+        .max()
+        .unwrap_or(SUCCESS)
+}
+
+fn main() {
+    let args = std::env::args().skip(1).collect::<Vec<_>>();
+    process::exit(run(&args));
+}
@@ -0,0 +1,188 @@
+use std::convert::TryFrom;
+
+use serde::{Deserialize, Serialize};
+use winit::event::VirtualKeyCode;
+
+use crate::helpers::{hltas::cleaner::Cleaner, locale::locale_lang::LocaleLang};
+
+use super::{key_combination::KeyCombination, new_line::NewLineKind, option_menu::Category};
+
+/// Every action of the editor
+///
+/// * Menus, shortcuts and the command palette all run actions through `MainGUI::run_action`
+/// * Actions are saved with their id, which stays the same between versions
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Serialize, Deserialize)]
+#[serde(into = "String", try_from = "String")]
+pub enum Action {
+    NewFile,
+    OpenFile,
+    SaveFile,
+    CloseFile,
+    ExportCsv,
+    ExportJson,
+    Import,
+    Undo,
+    Redo,
+    UndoHistory,
+    Cut,
+    Copy,
+    Paste,
+    SelectAll,
+    BulkEdit,
+    TransformSelection,
+    GotoLine,
+    FindReplace,
+    FilterLines,
+    Cleaner(Cleaner),
+    NewLine(NewLineKind),
+    Statistics,
+    Problems,
+    Compare,
+    Merge,
+    ToggleGraphicsEditor,
+    ToggleSimpleView,
+    ToggleTimingGutter,
+    OpenOptions,
+    OptionsCategory(Category),
+    CommandPalette,
+}
+
+impl Action {
+    pub fn all() -> Vec<Action> {
+        let mut actions = vec![
+            Action::NewFile,
+            Action::OpenFile,
+            Action::SaveFile,
+            Action::CloseFile,
+            Action::ExportCsv,
+            Action::ExportJson,
+            Action::Import,
+            Action::Undo,
+            Action::Redo,
+            Action::UndoHistory,
+            Action::Cut,
+            Action::Copy,
+            Action::Paste,
+            Action::SelectAll,
+            Action::BulkEdit,
+            Action::TransformSelection,
+            Action::GotoLine,
+            Action::FindReplace,
+            Action::FilterLines,
+        ];
+        actions.extend(Cleaner::ALL.iter().map(|cleaner| Action::Cleaner(*cleaner)));
+        actions.extend(NewLineKind::ALL.iter().map(|kind| Action::NewLine(*kind)));
+        actions.extend(vec![
+            Action::Statistics,
+            Action::Problems,
+            Action::Compare,
+            Action::Merge,
+            Action::ToggleGraphicsEditor,
+            Action::ToggleSimpleView,
+            Action::ToggleTimingGutter,
+            Action::OpenOptions,
+        ]);
+        actions.extend(
+            Category::ALL
+                .iter()
+                .map(|category| Action::OptionsCategory(*category)),
+        );
+        actions.push(Action::CommandPalette);
+
+        actions
+    }
+
+    pub fn id(&self) -> String {
+        match self {
+            Action::NewFile => "new-file".to_string(),
+            Action::OpenFile => "open-file".to_string(),
+            Action::SaveFile => "save-file".to_string(),
+            Action::CloseFile => "close-file".to_string(),
+            Action::ExportCsv => "export-csv".to_string(),
+            Action::ExportJson => "export-json".to_string(),
+            Action::Import => "import-file".to_string(),
+            Action::Undo => "undo".to_string(),
+            Action::Redo => "redo".to_string(),
+            Action::UndoHistory => "undo-history".to_string(),
+            Action::Cut => "cut".to_string(),
+            Action::Copy => "copy".to_string(),
+            Action::Paste => "paste".to_string(),
+            Action::SelectAll => "select-all".to_string(),
+            Action::BulkEdit => "bulk-edit".to_string(),
+            Action::TransformSelection => "transform-selection".to_string(),
+            Action::GotoLine => "goto-line".to_string(),
+            Action::FindReplace => "find-replace".to_string(),
+            Action::FilterLines => "filter-lines".to_string(),
+            Action::Cleaner(cleaner) => format!("cleaner-{}", cleaner.id()),
+            Action::NewLine(kind) => format!("new-line-{}", kind.id()),
+            Action::Statistics => "statistics".to_string(),
+            Action::Problems => "problems".to_string(),
+            Action::Compare => "compare".to_string(),
+            Action::Merge => "merge".to_string(),
+            Action::ToggleGraphicsEditor => "toggle-graphics-editor".to_string(),
+            Action::ToggleSimpleView => "toggle-simple-view".to_string(),
+            Action::ToggleTimingGutter => "toggle-timing-gutter".to_string(),
+            Action::OpenOptions => "open-options-menu".to_string(),
+            Action::OptionsCategory(category) => format!("options-{}", category.id()),
+            Action::CommandPalette => "command-palette".to_string(),
+        }
+    }
+
+    pub fn label(&self, locale_lang: &LocaleLang) -> String {
+        match self {
+            Action::Cleaner(cleaner) => locale_lang.get_string_from_id(cleaner.id()),
+            Action::NewLine(kind) => format!(
+                "{}: {}",
+                locale_lang.get_string_from_id("new-line"),
+                kind.label()
+            ),
+            Action::OptionsCategory(category) => format!(
+                "{}: {}",
+                locale_lang.get_string_from_id("open-options-menu"),
+                category.label()
+            ),
+            _ => locale_lang.get_string_from_id(&self.id()),
+        }
+    }
+
+    pub fn default_key(&self) -> Option<KeyCombination> {
+        let key = match self {
+            Action::NewFile => KeyCombination::new(VirtualKeyCode::N).ctrl(),
+            Action::OpenFile => KeyCombination::new(VirtualKeyCode::O).ctrl(),
+            Action::SaveFile => KeyCombination::new(VirtualKeyCode::S).ctrl(),
+            Action::CloseFile => KeyCombination::new(VirtualKeyCode::W).ctrl(),
+            Action::Undo => KeyCombination::new(VirtualKeyCode::Z).ctrl(),
+            Action::Redo => KeyCombination::new(VirtualKeyCode::Y).ctrl(),
+            Action::Cut => KeyCombination::new(VirtualKeyCode::X).ctrl(),
+            Action::Copy => KeyCombination::new(VirtualKeyCode::C).ctrl(),
+            Action::Paste => KeyCombination::new(VirtualKeyCode::V).ctrl(),
+            Action::SelectAll => KeyCombination::new(VirtualKeyCode::A).ctrl(),
+            Action::GotoLine => KeyCombination::new(VirtualKeyCode::G).ctrl(),
+            Action::FindReplace => KeyCombination::new(VirtualKeyCode::F).ctrl(),
+            Action::FilterLines => KeyCombination::new(VirtualKeyCode::F).ctrl().shift(),
+            Action::ToggleSimpleView => KeyCombination::new(VirtualKeyCode::S).ctrl().shift(),
+            Action::ToggleGraphicsEditor => KeyCombination::new(VirtualKeyCode::G).alt(),
+            Action::CommandPalette => KeyCombination::new(VirtualKeyCode::P).ctrl().shift(),
+            _ => return None,
+        };
+
+        Some(key)
+    }
+}
+
+impl From<Action> for String {
+    fn from(action: Action) -> Self {
+        action.id()
+    }
+}
+
+impl TryFrom<String> for Action {
+    type Error = String;
+
+    fn try_from(id: String) -> Result<Self, Self::Error> {
+        Action::all()
+            .into_iter()
+            .find(|action| action.id() == id)
+            .ok_or_else(|| format!("unknown action `{}`", id))
+    }
+}
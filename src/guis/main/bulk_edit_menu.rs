@@ -0,0 +1,313 @@
+use std::collections::HashMap;
+
+use fluent_templates::fluent_bundle::FluentValue;
+use hltas::types::{AutoMovement, FrameBulk, LeaveGroundActionSpeed, Line, StrafeType};
+use imgui::{ComboBox, Condition, InputFloat, InputText, Selectable, Ui, Window};
+
+use crate::helpers::{
+    hltas::bulk_edit::{
+        framebulk_strafe_type, framebulk_yaw, ActionKey, BulkEdit, FieldState, StrafeDirKind,
+    },
+    locale::locale_lang::LocaleLang,
+};
+
+use super::tab::HLTASFileTab;
+
+/// Combo box showing the value shared by the framebulks, or the mixed label if they differ
+///
+/// * Disabled if no framebulk has the field
+fn show_field_combo<T: Copy + PartialEq>(
+    ui: &Ui,
+    label: &str,
+    mixed_label: &str,
+    state: Option<FieldState<T>>,
+    pending: &mut Option<T>,
+    items: &[(&str, T)],
+) {
+    let label_of = |value: T| {
+        items
+            .iter()
+            .find(|(_, item)| *item == value)
+            .map(|(label, _)| label.to_string())
+            .unwrap_or_default()
+    };
+
+    let preview = match (*pending, &state) {
+        (Some(value), _) => label_of(value),
+        (None, Some(FieldState::Same(value))) => label_of(*value),
+        (None, Some(FieldState::Mixed)) => mixed_label.to_string(),
+        (None, None) => String::new(),
+    };
+
+    ui.disabled(state.is_none(), || {
+        ComboBox::new(label).preview_value(preview).build(ui, || {
+            for (item_label, value) in items {
+                if Selectable::new(item_label).build(ui) {
+                    *pending = Some(*value);
+                }
+            }
+        });
+    });
+}
+
+/// Shows the mixed label next to the last widget if the field isn't being edited
+fn show_mixed_hint<T>(ui: &Ui, mixed_label: &str, state: &Option<FieldState<T>>, pending: bool) {
+    if !pending && matches!(state, Some(FieldState::Mixed)) {
+        ui.same_line();
+        ui.text_disabled(mixed_label);
+    }
+}
+
+/// Edits a field on every selected framebulk at once
+#[derive(Default)]
+pub struct BulkEditMenu {
+    opened: bool,
+    edit: BulkEdit,
+}
+
+impl BulkEditMenu {
+    pub fn open(&mut self) {
+        self.opened = true;
+    }
+
+    pub fn show(&mut self, ui: &Ui, locale_lang: &LocaleLang, current_tab: &mut HLTASFileTab) {
+        if !self.opened {
+            return;
+        }
+
+        let edit = &mut self.edit;
+
+        Window::new(locale_lang.get_string_from_id("bulk-edit"))
+            .opened(&mut self.opened)
+            .size([380.0, 420.0], Condition::FirstUseEver)
+            .position_pivot([0.5, 0.5])
+            .position(
+                {
+                    let display_size = ui.io().display_size;
+                    [display_size[0] * 0.5, display_size[1] * 0.5]
+                },
+                Condition::Appearing,
+            )
+            .build(ui, || {
+                let framebulk_indexes = current_tab
+                    .line_states
+                    .selected_indexes_collection()
+                    .into_iter()
+                    .filter(|i| matches!(current_tab.hltas_lines()[*i], Line::FrameBulk(_)))
+                    .collect::<Vec<_>>();
+
+                let mut args = HashMap::new();
+                args.insert("count", FluentValue::from(framebulk_indexes.len()));
+                ui.text(locale_lang.get_string_from_id_with_args("bulk-edit-selected", &args));
+
+                if framebulk_indexes.is_empty() {
+                    ui.text_disabled(locale_lang.get_string_from_id("bulk-edit-no-framebulks"));
+                    return;
+                }
+
+                let framebulks = framebulk_indexes
+                    .iter()
+                    .filter_map(|i| match &current_tab.hltas_lines()[*i] {
+                        Line::FrameBulk(framebulk) => Some(framebulk),
+                        _ => None,
+                    })
+                    .collect::<Vec<&FrameBulk>>();
+
+                let mixed_label = locale_lang.get_string_from_id("bulk-edit-mixed");
+                let mixed_label = mixed_label.as_str();
+
+                ui.separator();
+
+                // frametime
+                let frame_time_state = FieldState::from_values(
+                    framebulks
+                        .iter()
+                        .map(|framebulk| framebulk.frame_time.as_str()),
+                );
+                let mut frame_time = match (&edit.frame_time, &frame_time_state) {
+                    (Some(frame_time), _) => frame_time.to_owned(),
+                    (None, Some(FieldState::Same(frame_time))) => frame_time.to_string(),
+                    _ => String::new(),
+                };
+                if InputText::new(
+                    ui,
+                    locale_lang.get_string_from_id("bulk-edit-frametime"),
+                    &mut frame_time,
+                )
+                .hint(mixed_label)
+                .build()
+                {
+                    edit.frame_time = Some(frame_time);
+                }
+
+                // strafing
+                let strafe_type_state =
+                    FieldState::from_values(framebulks.iter().map(|f| framebulk_strafe_type(f)));
+                show_field_combo(
+                    ui,
+                    &locale_lang.get_string_from_id("bulk-edit-strafe-type"),
+                    mixed_label,
+                    strafe_type_state,
+                    &mut edit.strafe_type,
+                    &[
+                        ("Max accel", Some(StrafeType::MaxAccel)),
+                        ("Max angle", Some(StrafeType::MaxAngle)),
+                        ("Max deccel", Some(StrafeType::MaxDeccel)),
+                        ("Const speed", Some(StrafeType::ConstSpeed)),
+                        ("None", None),
+                    ],
+                );
+
+                let strafe_dir_state =
+                    FieldState::from_values(framebulks.iter().filter_map(|framebulk| {
+                        match &framebulk.auto_actions.movement {
+                            Some(AutoMovement::Strafe(strafe_settings)) => {
+                                Some(StrafeDirKind::from_dir(&strafe_settings.dir))
+                            }
+                            _ => None,
+                        }
+                    }))
+                    .map(|state| match state {
+                        FieldState::Same(Some(dir)) => FieldState::Same(dir),
+                        _ => FieldState::Mixed,
+                    });
+                let strafe_dirs = StrafeDirKind::ALL
+                    .iter()
+                    .map(|dir| (dir.label(), *dir))
+                    .collect::<Vec<_>>();
+                show_field_combo(
+                    ui,
+                    &locale_lang.get_string_from_id("bulk-edit-strafe-dir"),
+                    mixed_label,
+                    strafe_dir_state,
+                    &mut edit.strafe_dir,
+                    &strafe_dirs,
+                );
+
+                // view angles
+                let yaw_state =
+                    FieldState::from_values(framebulks.iter().filter_map(|f| framebulk_yaw(f)));
+                let mut yaw = match (edit.yaw, &yaw_state) {
+                    (Some(yaw), _) => yaw,
+                    (None, Some(FieldState::Same(yaw))) => *yaw,
+                    _ => 0.0,
+                };
+                if InputFloat::new(
+                    ui,
+                    locale_lang.get_string_from_id("bulk-edit-yaw"),
+                    &mut yaw,
+                )
+                .build()
+                {
+                    edit.yaw = Some(yaw);
+                }
+                show_mixed_hint(ui, mixed_label, &yaw_state, edit.yaw.is_some());
+
+                let pitch_state =
+                    FieldState::from_values(framebulks.iter().map(|framebulk| framebulk.pitch));
+                let mut pitch = match (edit.pitch, &pitch_state) {
+                    (Some(pitch), _) => pitch,
+                    (None, Some(FieldState::Same(Some(pitch)))) => *pitch,
+                    _ => 0.0,
+                };
+                if InputFloat::new(
+                    ui,
+                    locale_lang.get_string_from_id("bulk-edit-pitch"),
+                    &mut pitch,
+                )
+                .build()
+                {
+                    edit.pitch = Some(pitch.clamp(-89.0, 89.0));
+                }
+                show_mixed_hint(ui, mixed_label, &pitch_state, edit.pitch.is_some());
+
+                // jumps
+                let lgagst_state = FieldState::from_values(framebulks.iter().filter_map(|f| {
+                    f.auto_actions
+                        .leave_ground_action
+                        .map(|leave_ground_action| leave_ground_action.speed)
+                }));
+                show_field_combo(
+                    ui,
+                    &locale_lang.get_string_from_id("bulk-edit-lgagst"),
+                    mixed_label,
+                    lgagst_state,
+                    &mut edit.lgagst_speed,
+                    &[
+                        ("No Lgagst", LeaveGroundActionSpeed::Any),
+                        ("Lgagst", LeaveGroundActionSpeed::Optimal),
+                        (
+                            "Lgagst With Max Spd",
+                            LeaveGroundActionSpeed::OptimalWithFullMaxspeed,
+                        ),
+                    ],
+                );
+
+                // keys
+                ui.text(locale_lang.get_string_from_id("bulk-edit-keys"));
+                for (i, (key, pending)) in ActionKey::ALL
+                    .iter()
+                    .zip(edit.action_keys.iter_mut())
+                    .enumerate()
+                {
+                    let key_state = FieldState::from_values(framebulks.iter().map(|f| key.get(f)));
+                    let mut pressed = match (*pending, &key_state) {
+                        (Some(pressed), _) => pressed,
+                        (None, Some(FieldState::Same(pressed))) => *pressed,
+                        _ => false,
+                    };
+
+                    if ui.checkbox(format!("{}##bulk_edit_key", key.label()), &mut pressed) {
+                        *pending = Some(pressed);
+                    }
+                    show_mixed_hint(ui, mixed_label, &key_state, pending.is_some());
+
+                    if i % 3 != 2 {
+                        ui.same_line();
+                    }
+                }
+
+                InputText::new(
+                    ui,
+                    locale_lang.get_string_from_id("bulk-edit-append-command"),
+                    &mut edit.append_console_command,
+                )
+                .hint(locale_lang.get_string_from_id("commands"))
+                .build();
+
+                ui.separator();
+
+                let mut edited_lines = None;
+                ui.disabled(edit.is_empty() || !edit.is_valid(), || {
+                    if ui.button(locale_lang.get_string_from_id("bulk-edit-apply")) {
+                        edited_lines = Some(
+                            framebulk_indexes
+                                .iter()
+                                .zip(framebulks.iter())
+                                .filter_map(|(i, framebulk)| {
+                                    let mut edited = (*framebulk).to_owned();
+                                    edit.apply(&mut edited);
+
+                                    if edited != **framebulk {
+                                        Some((*i, Line::FrameBulk(edited)))
+                                    } else {
+                                        None
+                                    }
+                                })
+                                .collect::<Vec<_>>(),
+                        );
+                    }
+                    ui.same_line();
+                    if ui.button(locale_lang.get_string_from_id("bulk-edit-revert")) {
+                        *edit = BulkEdit::default();
+                    }
+                });
+
+                if let Some(edited_lines) = edited_lines {
+                    // a single undo point for the whole edit
+                    current_tab.replace_lines(edited_lines);
+                    *edit = BulkEdit::default();
+                }
+            });
+    }
+}
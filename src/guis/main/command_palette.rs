@@ -0,0 +1,139 @@
+use imgui::{ChildWindow, Condition, InputText, Selectable, StyleColor, Ui, Window};
+use winit::event::VirtualKeyCode;
+
+use crate::helpers::{fuzzy::fuzzy_match, locale::locale_lang::LocaleLang};
+
+use super::{actions::Action, key_state::KeyboardState, keymap::Keymap};
+
+#[derive(Default)]
+pub struct CommandPalette {
+    prev_opened: bool,
+    opened: bool,
+    query: String,
+    /// Index into the matching actions
+    selected_index: usize,
+}
+
+impl CommandPalette {
+    pub fn open(&mut self) {
+        self.opened = true;
+    }
+
+    /// Shows the palette with the actions that can be run right now
+    ///
+    /// * Returns the action the user picked
+    pub fn show(
+        &mut self,
+        ui: &Ui,
+        locale_lang: &LocaleLang,
+        keymap: &Keymap,
+        keyboard_state: &KeyboardState,
+        actions: &[Action],
+    ) -> Option<Action> {
+        let mut picked_action = None;
+
+        if self.opened {
+            let mut opened_internal = true;
+            let query = &mut self.query;
+            let selected_index = &mut self.selected_index;
+            let prev_opened = self.prev_opened;
+
+            // reset menu state
+            if !prev_opened {
+                query.clear();
+                *selected_index = 0;
+            }
+
+            Window::new(locale_lang.get_string_from_id("command-palette"))
+                .opened(&mut self.opened)
+                .resizable(false)
+                .collapsible(false)
+                .position_pivot([0.5, 0.0])
+                .size([450.0, 300.0], Condition::Always)
+                .position(
+                    [ui.io().display_size[0] * 0.5, ui.frame_height() * 2.0],
+                    Condition::Appearing,
+                )
+                .build(ui, || {
+                    if !prev_opened {
+                        ui.set_keyboard_focus_here();
+                    }
+
+                    ui.set_next_item_width(-1.0);
+                    if InputText::new(ui, "##command_palette_query", query).build() {
+                        *selected_index = 0;
+                    }
+
+                    let mut matches = actions
+                        .iter()
+                        .map(|action| (action, action.label(locale_lang)))
+                        .filter_map(|(action, label)| {
+                            fuzzy_match(query, &label).map(|score| (action, label, score))
+                        })
+                        .collect::<Vec<_>>();
+                    // stable sort keeps the registration order for equal scores
+                    matches.sort_by(|(_, _, score), (_, _, other_score)| other_score.cmp(score));
+
+                    if keyboard_state.just_pressed(VirtualKeyCode::Down)
+                        && *selected_index + 1 < matches.len()
+                    {
+                        *selected_index += 1;
+                    }
+                    if keyboard_state.just_pressed(VirtualKeyCode::Up) && *selected_index > 0 {
+                        *selected_index -= 1;
+                    }
+                    if *selected_index >= matches.len() {
+                        *selected_index = matches.len().saturating_sub(1);
+                    }
+
+                    ChildWindow::new("command_palette_actions").build(ui, || {
+                        for (i, (action, label, _)) in matches.iter().enumerate() {
+                            let is_selected = i == *selected_index;
+
+                            if Selectable::new(format!("{}##{}", label, action.id()))
+                                .selected(is_selected)
+                                .build(ui)
+                            {
+                                picked_action = Some(**action);
+                            }
+                            if is_selected
+                                && (keyboard_state.just_pressed(VirtualKeyCode::Up)
+                                    || keyboard_state.just_pressed(VirtualKeyCode::Down))
+                            {
+                                ui.set_scroll_here_y();
+                            }
+
+                            let shortcut = keymap.shortcut_text(action);
+                            if !shortcut.is_empty() {
+                                ui.same_line_with_pos(
+                                    ui.window_content_region_width()
+                                        - ui.calc_text_size(&shortcut)[0],
+                                );
+                                ui.text_colored(ui.style_color(StyleColor::TextDisabled), shortcut);
+                            }
+                        }
+                    });
+
+                    if keyboard_state.just_pressed(VirtualKeyCode::Return) {
+                        if let Some((action, _, _)) = matches.get(*selected_index) {
+                            picked_action = Some(**action);
+                        }
+                    } else if keyboard_state.just_pressed(VirtualKeyCode::Escape) {
+                        opened_internal = false;
+                    }
+                });
+
+            if !opened_internal || picked_action.is_some() {
+                self.opened = false;
+            }
+        }
+
+        self.prev_opened = self.opened;
+
+        picked_action
+    }
+
+    pub fn is_opened(&self) -> bool {
+        self.opened
+    }
+}
@@ -0,0 +1,226 @@
+use std::{
+    cell::RefCell,
+    fs,
+    rc::{Rc, Weak},
+};
+
+use hltas::HLTAS;
+use imgui::{ComboBox, Condition, Selectable, Ui, Window};
+
+use crate::helpers::{
+    hltas::diff::{diff_lines, diff_rows, DiffRow},
+    locale::locale_lang::LocaleLang,
+};
+
+use super::{diff_view::show_side_by_side_diff_view, tab::HLTASFileTab};
+
+/// One side of the comparison
+#[derive(Clone)]
+struct CompareSource {
+    tab: Weak<RefCell<HLTASFileTab>>,
+    /// Uses the file at the tab path instead of the tab content
+    on_disk: bool,
+}
+
+impl CompareSource {
+    fn is_same(&self, tab: &Rc<RefCell<HLTASFileTab>>, on_disk: bool) -> bool {
+        self.on_disk == on_disk && Weak::ptr_eq(&self.tab, &Rc::downgrade(tab))
+    }
+
+    fn label(&self, locale_lang: &LocaleLang) -> String {
+        match self.tab.upgrade() {
+            Some(tab) => source_label(&tab.borrow(), self.on_disk, locale_lang),
+            None => locale_lang.get_string_from_id("compare-tab-closed"),
+        }
+    }
+
+    fn load(&self, locale_lang: &LocaleLang) -> Result<HLTAS, String> {
+        let tab = self
+            .tab
+            .upgrade()
+            .ok_or_else(|| locale_lang.get_string_from_id("compare-tab-closed"))?;
+        let tab = tab.borrow();
+
+        if !self.on_disk {
+            return Ok(tab.hltas().to_owned());
+        }
+
+        let path = tab
+            .path()
+            .ok_or_else(|| locale_lang.get_string_from_id("compare-no-file"))?;
+        let file_content = fs::read_to_string(path).map_err(|err| err.to_string())?;
+        HLTAS::from_str(&file_content).map_err(|err| err.to_string())
+    }
+}
+
+fn source_label(tab: &HLTASFileTab, on_disk: bool, locale_lang: &LocaleLang) -> String {
+    if on_disk {
+        format!(
+            "{} ({})",
+            tab.title(),
+            locale_lang.get_string_from_id("compare-on-disk")
+        )
+    } else {
+        tab.title().to_string()
+    }
+}
+
+struct Comparison {
+    old: HLTAS,
+    new: HLTAS,
+    rows: Vec<DiffRow>,
+}
+
+/// Side by side diff between two tabs, or a tab and its file
+#[derive(Default)]
+pub struct CompareMenu {
+    opened: bool,
+    old: Option<CompareSource>,
+    new: Option<CompareSource>,
+    comparison: Option<Result<Comparison, String>>,
+}
+
+impl CompareMenu {
+    /// Opens the menu comparing the file of the tab with the tab
+    pub fn open(&mut self, current_tab: &Rc<RefCell<HLTASFileTab>>) {
+        let has_path = current_tab.borrow().path().is_some();
+
+        self.opened = true;
+        self.old = Some(CompareSource {
+            tab: Rc::downgrade(current_tab),
+            on_disk: has_path,
+        });
+        self.new = Some(CompareSource {
+            tab: Rc::downgrade(current_tab),
+            on_disk: false,
+        });
+        // compared when shown
+        self.comparison = None;
+    }
+
+    fn compare(&mut self, locale_lang: &LocaleLang) {
+        self.comparison = match (&self.old, &self.new) {
+            (Some(old), Some(new)) => Some(old.load(locale_lang).and_then(|old| {
+                let new = new.load(locale_lang)?;
+                let diff = diff_lines(&old.lines, &new.lines);
+                let rows = diff_rows(&old.lines, &new.lines, &diff);
+
+                Ok(Comparison { old, new, rows })
+            })),
+            _ => None,
+        };
+    }
+
+    pub fn show(&mut self, ui: &Ui, locale_lang: &LocaleLang, tabs: &[Rc<RefCell<HLTASFileTab>>]) {
+        if !self.opened {
+            return;
+        }
+
+        if self.comparison.is_none() {
+            self.compare(locale_lang);
+        }
+
+        let mut opened = self.opened;
+        let mut sources_changed = false;
+        let mut refresh = false;
+
+        Window::new(locale_lang.get_string_from_id("compare"))
+            .opened(&mut opened)
+            .size([900.0, 550.0], Condition::FirstUseEver)
+            .position_pivot([0.5, 0.5])
+            .position(
+                {
+                    let display_size = ui.io().display_size;
+                    [display_size[0] * 0.5, display_size[1] * 0.5]
+                },
+                Condition::Appearing,
+            )
+            .build(ui, || {
+                let combo_width = ui.window_content_region_width() * 0.35;
+
+                for (label, source) in [
+                    (locale_lang.get_string_from_id("compare-old"), &mut self.old),
+                    (locale_lang.get_string_from_id("compare-new"), &mut self.new),
+                ] {
+                    let preview = source
+                        .as_ref()
+                        .map(|source| source.label(locale_lang))
+                        .unwrap_or_default();
+
+                    ui.set_next_item_width(combo_width);
+                    ComboBox::new(&label).preview_value(preview).build(ui, || {
+                        for (i, tab) in tabs.iter().enumerate() {
+                            let on_disk_options = if tab.borrow().path().is_some() {
+                                vec![false, true]
+                            } else {
+                                vec![false]
+                            };
+
+                            for on_disk in on_disk_options {
+                                let selected = source
+                                    .as_ref()
+                                    .is_some_and(|source| source.is_same(tab, on_disk));
+
+                                // tabs can have the same title
+                                if Selectable::new(format!(
+                                    "{}##{}",
+                                    source_label(&tab.borrow(), on_disk, locale_lang),
+                                    i
+                                ))
+                                .selected(selected)
+                                .build(ui)
+                                {
+                                    *source = Some(CompareSource {
+                                        tab: Rc::downgrade(tab),
+                                        on_disk,
+                                    });
+                                    sources_changed = true;
+                                }
+                            }
+                        }
+                    });
+                    ui.same_line();
+                }
+
+                if ui.button(locale_lang.get_string_from_id("compare-swap")) {
+                    std::mem::swap(&mut self.old, &mut self.new);
+                    sources_changed = true;
+                }
+                ui.same_line();
+                refresh = ui.button(locale_lang.get_string_from_id("compare-refresh"));
+
+                match &self.comparison {
+                    Some(Ok(comparison)) => {
+                        let count = |is_kind: fn(&DiffRow) -> bool| {
+                            comparison.rows.iter().filter(|row| is_kind(row)).count()
+                        };
+                        ui.text(format!(
+                            "+{} -{} ~{}",
+                            count(|row| matches!(row, DiffRow::Insert { .. })),
+                            count(|row| matches!(row, DiffRow::Delete { .. })),
+                            count(|row| matches!(row, DiffRow::Changed { .. })),
+                        ));
+
+                        show_side_by_side_diff_view(
+                            ui,
+                            "compare_diff",
+                            &comparison.old.lines,
+                            &comparison.new.lines,
+                            &comparison.rows,
+                            ui.content_region_avail(),
+                        );
+                    }
+                    Some(Err(err)) => ui.text_colored([1.0, 0.4, 0.4, 1.0], err),
+                    None => (),
+                }
+            });
+
+        self.opened = opened;
+        if sources_changed || refresh {
+            self.compare(locale_lang);
+        }
+        if !self.opened {
+            self.comparison = None;
+        }
+    }
+}
@@ -0,0 +1,140 @@
+use hltas::types::Line;
+use imgui::{ChildWindow, StyleColor, Ui};
+
+use crate::helpers::hltas::{
+    diff::{framebulk_field_diffs, DiffOp, DiffRow},
+    lines_to_str,
+};
+
+/// Shows the diff as a unified diff with line numbers of the old and the new lines
+pub fn show_diff_view(
+    ui: &Ui,
+    id: &str,
+    old: &[Line],
+    new: &[Line],
+    diff: &[DiffOp],
+    size: [f32; 2],
+) {
+    ChildWindow::new(id)
+        .size(size)
+        .border(true)
+        .horizontal_scrollbar(true)
+        .build(ui, || {
+            for diff_op in diff {
+                let (old_number, new_number, prefix, line, color) = match *diff_op {
+                    DiffOp::Equal {
+                        old_index,
+                        new_index,
+                    } => (
+                        Some(old_index),
+                        Some(new_index),
+                        " ",
+                        &old[old_index],
+                        ui.style_color(StyleColor::TextDisabled),
+                    ),
+                    DiffOp::Delete { old_index } => (
+                        Some(old_index),
+                        None,
+                        "-",
+                        &old[old_index],
+                        [1.0, 0.4, 0.4, 1.0],
+                    ),
+                    DiffOp::Insert { new_index } => (
+                        None,
+                        Some(new_index),
+                        "+",
+                        &new[new_index],
+                        [0.4, 1.0, 0.4, 1.0],
+                    ),
+                };
+
+                let line_number = |index: Option<usize>| match index {
+                    Some(index) => format!("{:>5}", index + 1),
+                    None => " ".repeat(5),
+                };
+
+                ui.text_colored(
+                    color,
+                    format!(
+                        "{} {} {} {}",
+                        line_number(old_number),
+                        line_number(new_number),
+                        prefix,
+                        lines_to_str(vec![line.to_owned()])
+                    ),
+                );
+            }
+        });
+}
+
+/// Shows the rows side by side, with the fields that differ under changed framebulks
+pub fn show_side_by_side_diff_view(
+    ui: &Ui,
+    id: &str,
+    old: &[Line],
+    new: &[Line],
+    rows: &[DiffRow],
+    size: [f32; 2],
+) {
+    ChildWindow::new(id)
+        .size(size)
+        .border(true)
+        .horizontal_scrollbar(true)
+        .build(ui, || {
+            let equal_color = ui.style_color(StyleColor::TextDisabled);
+            let changed_color = [1.0, 0.85, 0.4, 1.0];
+            let deleted_color = [1.0, 0.4, 0.4, 1.0];
+            let inserted_color = [0.4, 1.0, 0.4, 1.0];
+
+            let show_line = |lines: &[Line], index: Option<usize>, color: [f32; 4]| {
+                if let Some(index) = index {
+                    ui.text_colored(
+                        color,
+                        format!(
+                            "{:>5} {}",
+                            index + 1,
+                            lines_to_str(vec![lines[index].to_owned()])
+                        ),
+                    );
+                }
+            };
+
+            ui.columns(2, format!("{}_columns", id), true);
+            for row in rows {
+                let (old_index, new_index, color) = match *row {
+                    DiffRow::Equal {
+                        old_index,
+                        new_index,
+                    } => (Some(old_index), Some(new_index), equal_color),
+                    DiffRow::Changed {
+                        old_index,
+                        new_index,
+                    } => (Some(old_index), Some(new_index), changed_color),
+                    DiffRow::Delete { old_index } => (Some(old_index), None, deleted_color),
+                    DiffRow::Insert { new_index } => (None, Some(new_index), inserted_color),
+                };
+
+                show_line(old, old_index, color);
+                ui.next_column();
+                show_line(new, new_index, color);
+
+                if let DiffRow::Changed {
+                    old_index,
+                    new_index,
+                } = *row
+                {
+                    if let (Line::FrameBulk(old_framebulk), Line::FrameBulk(new_framebulk)) =
+                        (&old[old_index], &new[new_index])
+                    {
+                        let fields = framebulk_field_diffs(old_framebulk, new_framebulk)
+                            .iter()
+                            .map(|field| field.label())
+                            .collect::<Vec<_>>();
+                        ui.text_disabled(format!("      ~ {}", fields.join(", ")));
+                    }
+                }
+                ui.next_column();
+            }
+            ui.columns(1, format!("{}_columns_end", id), false);
+        });
+}
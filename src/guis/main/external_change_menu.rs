@@ -0,0 +1,116 @@
+use std::{cell::RefCell, rc::Rc};
+
+use hltas::HLTAS;
+use imgui::{Condition, Ui, Window, WindowFlags};
+
+use crate::helpers::{
+    hltas::diff::{diff_lines, DiffOp},
+    locale::locale_lang::LocaleLang,
+};
+
+use super::{diff_view::show_diff_view, tab::HLTASFileTab};
+
+struct ChangedTab {
+    tab: Rc<RefCell<HLTASFileTab>>,
+    hltas_on_disk: HLTAS,
+    /// Diff from the tab lines to the lines on disk
+    diff: Vec<DiffOp>,
+}
+
+/// Asks what to do with a modified tab when its file got changed by another program
+#[derive(Default)]
+pub struct ExternalChangeMenu {
+    changed_tab: Option<ChangedTab>,
+    show_diff: bool,
+}
+
+impl ExternalChangeMenu {
+    pub fn open(&mut self, tab: Rc<RefCell<HLTASFileTab>>, hltas_on_disk: HLTAS) {
+        let diff = diff_lines(tab.borrow().hltas_lines(), &hltas_on_disk.lines);
+
+        self.changed_tab = Some(ChangedTab {
+            tab,
+            hltas_on_disk,
+            diff,
+        });
+        self.show_diff = false;
+    }
+
+    pub fn is_opened(&self) -> bool {
+        self.changed_tab.is_some()
+    }
+
+    pub fn show(&mut self, ui: &Ui, locale_lang: &LocaleLang) {
+        let changed_tab = match &self.changed_tab {
+            Some(changed_tab) => changed_tab,
+            None => return,
+        };
+        let show_diff = &mut self.show_diff;
+        let mut close = false;
+
+        Window::new(locale_lang.get_string_from_id("external-change-title"))
+            .flags(WindowFlags::NO_COLLAPSE)
+            .position_pivot([0.5, 0.5])
+            .size(
+                if *show_diff {
+                    [700.0, 500.0]
+                } else {
+                    [450.0, 120.0]
+                },
+                Condition::Always,
+            )
+            .position(
+                {
+                    let display_size = ui.io().display_size;
+                    [display_size[0] * 0.5, display_size[1] * 0.5]
+                },
+                Condition::Appearing,
+            )
+            .build(ui, || {
+                let tab = changed_tab.tab.borrow();
+
+                ui.text_wrapped(format!(
+                    "{}\n{}",
+                    tab.title(),
+                    locale_lang.get_string_from_id("external-change-question")
+                ));
+
+                let reload_clicked = ui.button(locale_lang.get_string_from_id("reload"));
+                ui.same_line();
+                let keep_mine_clicked = ui.button(locale_lang.get_string_from_id("keep-mine"));
+                ui.same_line();
+                if ui.button(locale_lang.get_string_from_id(if *show_diff {
+                    "hide-diff"
+                } else {
+                    "show-diff"
+                })) {
+                    *show_diff = !*show_diff;
+                }
+
+                if *show_diff {
+                    show_diff_view(
+                        ui,
+                        "external_change_diff",
+                        tab.hltas_lines(),
+                        &changed_tab.hltas_on_disk.lines,
+                        &changed_tab.diff,
+                        ui.content_region_avail(),
+                    );
+                }
+
+                drop(tab);
+
+                if reload_clicked {
+                    changed_tab
+                        .tab
+                        .borrow_mut()
+                        .reload_hltas(changed_tab.hltas_on_disk.to_owned());
+                }
+                close = reload_clicked || keep_mine_clicked;
+            });
+
+        if close {
+            self.changed_tab = None;
+        }
+    }
+}
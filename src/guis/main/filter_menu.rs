@@ -0,0 +1,144 @@
+use imgui::{Condition, InputText, Ui, Window};
+use winit::event::VirtualKeyCode;
+
+use crate::helpers::{
+    hltas::query::{Query, QueryError},
+    locale::locale_lang::LocaleLang,
+};
+
+use super::{key_state::KeyboardState, tab::HLTASFileTab};
+
+#[derive(Default)]
+pub struct FilterMenu {
+    prev_opened: bool,
+    opened: bool,
+    query: String,
+    /// Parsed `query`, only parsed again when the text changes
+    parsed_query: Option<Result<Query, QueryError>>,
+    only_show_matches: bool,
+}
+
+impl FilterMenu {
+    pub fn open(&mut self) {
+        self.opened = true;
+    }
+
+    pub fn show(
+        &mut self,
+        ui: &Ui,
+        locale_lang: &LocaleLang,
+        current_tab: &mut HLTASFileTab,
+        keyboard_state: &KeyboardState,
+    ) {
+        if self.opened {
+            let mut opened_internal = true;
+            let query_text = &mut self.query;
+            let parsed_query = &mut self.parsed_query;
+            let only_show_matches = &mut self.only_show_matches;
+            let prev_opened = self.prev_opened;
+
+            Window::new(locale_lang.get_string_from_id("filter-lines"))
+                .opened(&mut self.opened)
+                .resizable(false)
+                .position_pivot([0.5, 0.5])
+                .size([450.0, 150.0], Condition::Always)
+                .position(
+                    {
+                        let display_size = ui.io().display_size;
+                        [display_size[0] * 0.5, display_size[1] * 0.5]
+                    },
+                    Condition::Appearing,
+                )
+                .build(ui, || {
+                    if !prev_opened {
+                        ui.set_keyboard_focus_here();
+                    }
+
+                    let query_edited = InputText::new(
+                        ui,
+                        locale_lang.get_string_from_id("filter-query"),
+                        query_text,
+                    )
+                    .hint("strafe=maxangle frametime<0.004 !ducktap")
+                    .build();
+                    if query_edited {
+                        *parsed_query = None;
+                    }
+
+                    let query = match parsed_query.get_or_insert_with(|| Query::parse(query_text)) {
+                        Ok(query) => Some(query.to_owned()),
+                        Err(err) => {
+                            ui.text_colored(
+                                [1.0, 0.0, 0.0, 1.0],
+                                format!(
+                                    "{}: {}",
+                                    locale_lang.get_string_from_id("filter-query-err"),
+                                    err
+                                ),
+                            );
+                            None
+                        }
+                    };
+                    let query = query.filter(|query| !query.is_empty());
+
+                    let match_count = match &query {
+                        Some(query) => current_tab.query_matches(query).len(),
+                        None => 0,
+                    };
+                    ui.text(format!(
+                        "{}: {}",
+                        locale_lang.get_string_from_id("filter-matches"),
+                        match_count
+                    ));
+
+                    ui.checkbox(
+                        locale_lang.get_string_from_id("filter-only-show-matches"),
+                        only_show_matches,
+                    );
+                    ui.same_line();
+
+                    let enter_pressed = ui.is_window_focused()
+                        && keyboard_state.just_pressed(VirtualKeyCode::Return);
+                    if ui.button(locale_lang.get_string_from_id("filter-select-matches"))
+                        || enter_pressed
+                    {
+                        if let Some(query) = &query {
+                            let matches = current_tab.query_matches(query).to_vec();
+                            let tab_menu_data = &mut current_tab.line_states;
+                            tab_menu_data.reset_selected_indexes();
+
+                            // select consecutive matches as ranges
+                            let mut range_start = None;
+                            for (i, line_index) in matches.iter().enumerate() {
+                                let start = *range_start.get_or_insert(*line_index);
+
+                                if matches.get(i + 1) != Some(&(line_index + 1)) {
+                                    tab_menu_data.select_index_range(start..line_index + 1, true);
+                                    range_start = None;
+                                }
+                            }
+                        }
+                    }
+
+                    let (lines, _, line_states, _) = current_tab.split_fields_mut();
+                    line_states
+                        .set_line_filter(if *only_show_matches { query } else { None }, lines);
+
+                    if ui.is_window_focused() && keyboard_state.just_pressed(VirtualKeyCode::Escape)
+                    {
+                        opened_internal = false;
+                    }
+                });
+
+            if !opened_internal {
+                self.opened = false;
+            }
+        }
+
+        self.prev_opened = self.opened;
+    }
+
+    pub fn is_opened(&self) -> bool {
+        self.opened
+    }
+}
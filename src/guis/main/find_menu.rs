@@ -0,0 +1,203 @@
+use imgui::{Condition, InputText, Ui, Window};
+use winit::event::VirtualKeyCode;
+
+use crate::{
+    guis::radio_button_enum::show_radio_button_enum,
+    helpers::{
+        hltas::search::{LineSearch, SearchTarget},
+        locale::locale_lang::LocaleLang,
+    },
+};
+
+use super::{key_state::KeyboardState, tab::HLTASFileTab};
+
+#[derive(Default)]
+pub struct FindMenu {
+    prev_opened: bool,
+    opened: bool,
+    search: LineSearch,
+    /// Line index of the match the user is on
+    current_match: Option<usize>,
+}
+
+impl FindMenu {
+    pub fn open(&mut self) {
+        self.opened = true;
+    }
+
+    pub fn show(
+        &mut self,
+        ui: &Ui,
+        locale_lang: &LocaleLang,
+        current_tab: &mut HLTASFileTab,
+        keyboard_state: &KeyboardState,
+    ) {
+        if self.opened {
+            let mut opened_internal = true;
+            let search = &mut self.search;
+            let current_match = &mut self.current_match;
+            let prev_opened = self.prev_opened;
+
+            Window::new(locale_lang.get_string_from_id("find-replace"))
+                .opened(&mut self.opened)
+                .resizable(false)
+                .position_pivot([0.5, 0.5])
+                .size([450.0, 170.0], Condition::Always)
+                .position(
+                    {
+                        let display_size = ui.io().display_size;
+                        [display_size[0] * 0.5, display_size[1] * 0.5]
+                    },
+                    Condition::Appearing,
+                )
+                .build(ui, || {
+                    if !prev_opened {
+                        ui.set_keyboard_focus_here();
+                    }
+
+                    InputText::new(ui, locale_lang.get_string_from_id("find"), &mut search.find)
+                        .build();
+                    InputText::new(
+                        ui,
+                        locale_lang.get_string_from_id("replace"),
+                        &mut search.replace,
+                    )
+                    .build();
+
+                    let line_label = locale_lang.get_string_from_id("search-target-line");
+                    let comment_label = locale_lang.get_string_from_id("search-target-comment");
+                    let command_label = locale_lang.get_string_from_id("search-target-command");
+                    let save_label = locale_lang.get_string_from_id("search-target-save");
+                    let frametime_label = locale_lang.get_string_from_id("search-target-frametime");
+                    show_radio_button_enum(
+                        ui,
+                        &mut search.target,
+                        vec![
+                            (line_label.as_str(), SearchTarget::Line),
+                            (comment_label.as_str(), SearchTarget::Comment),
+                            (command_label.as_str(), SearchTarget::Command),
+                            (save_label.as_str(), SearchTarget::Save),
+                            (frametime_label.as_str(), SearchTarget::Frametime),
+                        ],
+                        "find_menu_target".to_string(),
+                        true,
+                    );
+
+                    ui.checkbox(
+                        locale_lang.get_string_from_id("match-case"),
+                        &mut search.match_case,
+                    );
+
+                    let matches = search.find_matches(current_tab.hltas_lines());
+
+                    if let Some(line_index) = *current_match {
+                        if matches.binary_search(&line_index).is_err() {
+                            *current_match = None;
+                        }
+                    }
+
+                    let current_match_number = match *current_match {
+                        Some(line_index) => matches.binary_search(&line_index).unwrap() + 1,
+                        None => 0,
+                    };
+                    ui.text(format!("{} / {}", current_match_number, matches.len()));
+
+                    let (enter_pressed, escape_pressed) = if ui.is_window_focused() {
+                        (
+                            keyboard_state.just_pressed(VirtualKeyCode::Return),
+                            keyboard_state.just_pressed(VirtualKeyCode::Escape),
+                        )
+                    } else {
+                        (false, false)
+                    };
+                    let shift_held = keyboard_state.held(VirtualKeyCode::LShift)
+                        || keyboard_state.held(VirtualKeyCode::RShift);
+
+                    let previous_clicked = ui
+                        .button(locale_lang.get_string_from_id("find-previous"))
+                        || (enter_pressed && shift_held);
+                    ui.same_line();
+                    let next_clicked = ui.button(locale_lang.get_string_from_id("find-next"))
+                        || (enter_pressed && !shift_held);
+                    ui.same_line();
+                    let replace_clicked = ui.button(format!(
+                        "{}##find_menu_replace_button",
+                        locale_lang.get_string_from_id("replace")
+                    ));
+                    ui.same_line();
+                    let replace_all_clicked =
+                        ui.button(locale_lang.get_string_from_id("replace-all"));
+
+                    let mut jump_to_match = false;
+
+                    if replace_clicked {
+                        if let Some(line_index) = *current_match {
+                            if let Some(line) =
+                                search.replace_in_line(&current_tab.hltas_lines()[line_index])
+                            {
+                                current_tab.replace_lines(vec![(line_index, line)]);
+                            }
+                        }
+                    }
+
+                    if (next_clicked || replace_clicked) && !matches.is_empty() {
+                        *current_match = match *current_match {
+                            Some(line_index) => matches.iter().find(|i| **i > line_index),
+                            None => None,
+                        }
+                        .or_else(|| matches.first())
+                        .copied();
+                        jump_to_match = true;
+                    } else if previous_clicked && !matches.is_empty() {
+                        *current_match = match *current_match {
+                            Some(line_index) => matches.iter().rev().find(|i| **i < line_index),
+                            None => None,
+                        }
+                        .or_else(|| matches.last())
+                        .copied();
+                        jump_to_match = true;
+                    }
+
+                    if replace_all_clicked {
+                        let lines = current_tab.hltas_lines();
+                        let replaced_lines = matches
+                            .iter()
+                            .filter_map(|i| {
+                                search.replace_in_line(&lines[*i]).map(|line| (*i, line))
+                            })
+                            .collect::<Vec<_>>();
+
+                        current_tab.replace_lines(replaced_lines);
+                    }
+
+                    if jump_to_match {
+                        if let Some(line_index) = *current_match {
+                            let tab_menu_data = &mut current_tab.line_states;
+
+                            tab_menu_data.reset_selected_indexes();
+                            tab_menu_data.change_selected_index(line_index, true);
+                            tab_menu_data.set_goto_line(line_index);
+                        }
+                    }
+
+                    current_tab
+                        .line_states
+                        .set_search_matches(matches, *current_match);
+
+                    if escape_pressed {
+                        opened_internal = false;
+                    }
+                });
+
+            if !opened_internal {
+                self.opened = false;
+            }
+        }
+
+        self.prev_opened = self.opened;
+    }
+
+    pub fn is_opened(&self) -> bool {
+        self.opened
+    }
+}
@@ -1,14 +1,36 @@
-use imgui::{Condition, Ui, Window};
+use std::collections::HashMap;
+
+use fluent_templates::fluent_bundle::FluentValue;
+use imgui::{Condition, InputFloat, Ui, Window};
 use winit::event::VirtualKeyCode;
 
-use crate::helpers::{imgui::input_editor::InputUsize, locale::locale_lang::LocaleLang};
+use crate::{
+    guis::radio_button_enum::show_radio_button_enum,
+    helpers::{
+        hltas::timing::{find_frame, find_time, line_timings, FramePosition},
+        imgui::input_editor::InputUsize,
+        locale::locale_lang::LocaleLang,
+    },
+};
 
 use super::{key_state::KeyboardState, tab::HLTASFileTab};
 
+#[derive(Clone, Copy, PartialEq)]
+enum GotoMode {
+    Line,
+    Frame,
+    Time,
+}
+
 pub struct GotoMenu {
     prev_opened: bool,
     opened: bool,
+    mode: GotoMode,
     selected_index: usize,
+    frame: usize,
+    time: f32,
+    /// Splits the framebulk so the new framebulk starts at the frame jumped to
+    split_framebulk: bool,
 }
 
 impl GotoMenu {
@@ -25,7 +47,11 @@ impl GotoMenu {
     ) {
         if self.opened {
             let mut opened_internal = true;
+            let mode = &mut self.mode;
             let selected_index = &mut self.selected_index;
+            let frame = &mut self.frame;
+            let time = &mut self.time;
+            let split_framebulk = &mut self.split_framebulk;
             let prev_opened = self.prev_opened;
 
             // don't open if no lines exist
@@ -43,7 +69,7 @@ impl GotoMenu {
                 .opened(&mut self.opened)
                 .resizable(false)
                 .position_pivot([0.5, 0.5])
-                .size([250.0, 100.0], Condition::Always)
+                .size([300.0, 170.0], Condition::Always)
                 .position(
                     {
                         let display_size = ui.io().display_size;
@@ -52,30 +78,125 @@ impl GotoMenu {
                     Condition::Appearing,
                 )
                 .build(ui, || {
+                    let line_label = locale_lang.get_string_from_id("goto-mode-line");
+                    let frame_label = locale_lang.get_string_from_id("goto-mode-frame");
+                    let time_label = locale_lang.get_string_from_id("goto-mode-time");
+                    show_radio_button_enum(
+                        ui,
+                        mode,
+                        vec![
+                            (line_label.as_str(), GotoMode::Line),
+                            (frame_label.as_str(), GotoMode::Frame),
+                            (time_label.as_str(), GotoMode::Time),
+                        ],
+                        "goto_mode".to_string(),
+                        true,
+                    );
+
                     if !prev_opened {
                         ui.set_keyboard_focus_here();
                     }
 
                     let lines = current_tab.hltas_lines();
 
-                    ui.text(format!("{} lines total", lines.len()));
-
-                    InputUsize::new().auto_select_all(true).build(
-                        ui,
-                        "##goto_line_input",
-                        selected_index,
-                    );
-                    // limit upper to 1 ~ lines len
-                    if *selected_index < 1 {
-                        *selected_index = 1;
-                    } else if *selected_index > lines.len() {
-                        *selected_index = lines.len();
+                    let frame_position = match mode {
+                        GotoMode::Line => {
+                            ui.text(format!("{} lines total", lines.len()));
+
+                            InputUsize::new().auto_select_all(true).build(
+                                ui,
+                                "##goto_line_input",
+                                selected_index,
+                            );
+                            // limit upper to 1 ~ lines len
+                            if *selected_index < 1 {
+                                *selected_index = 1;
+                            } else if *selected_index > lines.len() {
+                                *selected_index = lines.len();
+                            }
+
+                            None
+                        }
+                        GotoMode::Frame => {
+                            let end_timing = *line_timings(current_tab.hltas()).last().unwrap();
+                            ui.text(format!("{} frames total", end_timing.frame));
+
+                            InputUsize::new().auto_select_all(true).build(
+                                ui,
+                                "##goto_frame_input",
+                                frame,
+                            );
+
+                            Some(find_frame(current_tab.hltas(), *frame as u64))
+                        }
+                        GotoMode::Time => {
+                            let end_timing = *line_timings(current_tab.hltas()).last().unwrap();
+                            ui.text(format!("{:.3}s total", end_timing.time));
+
+                            InputFloat::new(ui, "##goto_time_input", time)
+                                .auto_select_all(true)
+                                .build();
+                            if *time < 0.0 {
+                                *time = 0.0;
+                            }
+
+                            Some(find_time(current_tab.hltas(), *time as f64))
+                        }
+                    };
+
+                    if let Some(frame_position) = frame_position {
+                        match frame_position {
+                            Some(FramePosition {
+                                line_index,
+                                frame_in_line,
+                            }) => {
+                                let mut args = HashMap::new();
+                                args.insert("line", FluentValue::from(line_index + 1));
+                                args.insert("frame", FluentValue::from(frame_in_line));
+                                ui.text_disabled(
+                                    locale_lang
+                                        .get_string_from_id_with_args("goto-frame-position", &args),
+                                );
+                            }
+                            None => {
+                                ui.text_disabled(locale_lang.get_string_from_id("goto-not-found"))
+                            }
+                        }
+
+                        ui.checkbox(
+                            locale_lang.get_string_from_id("goto-split-framebulk"),
+                            split_framebulk,
+                        );
                     }
 
                     if ui.button(locale_lang.get_string_from_id("jump-to-line"))
                         || keyboard_state.just_pressed(VirtualKeyCode::Return)
                     {
-                        current_tab.tab_menu_data.set_goto_line(*selected_index);
+                        match frame_position {
+                            // the line number shown to the user starts at 1
+                            None => current_tab
+                                .line_states
+                                .set_goto_line(selected_index.saturating_sub(1)),
+                            Some(Some(FramePosition {
+                                line_index,
+                                frame_in_line,
+                            })) => {
+                                let goto_index = if *split_framebulk
+                                    && current_tab.split_framebulk_at(line_index, frame_in_line)
+                                {
+                                    line_index + 1
+                                } else {
+                                    line_index
+                                };
+
+                                current_tab.line_states.reset_selected_indexes();
+                                current_tab
+                                    .line_states
+                                    .change_selected_index(goto_index, true);
+                                current_tab.line_states.set_goto_line(goto_index);
+                            }
+                            Some(None) => (),
+                        }
                         opened_internal = false;
                     } else if keyboard_state.just_pressed(VirtualKeyCode::Escape) {
                         opened_internal = false;
@@ -100,7 +221,11 @@ impl Default for GotoMenu {
         Self {
             prev_opened: Default::default(),
             opened: Default::default(),
+            mode: GotoMode::Line,
             selected_index: 1,
+            frame: 0,
+            time: 0.0,
+            split_framebulk: false,
         }
     }
 }
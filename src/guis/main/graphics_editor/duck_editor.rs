@@ -1,8 +1,7 @@
 use hltas::types::{DuckBeforeCollision, DuckBeforeGround, DuckWhenJump, Line, Times};
+use hltas_editor_core::undo_redo_hltas::UndoRedoHandler;
 use imgui::Ui;
 
-use crate::guis::main::undo_redo_hltas::UndoRedoHandler;
-
 use super::framebulk_editor::{FramebulkEditor, FramebulkEditorMiscData, FramebulkInfo};
 
 pub struct DuckEditor;
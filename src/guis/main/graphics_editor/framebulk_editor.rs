@@ -1,9 +1,8 @@
 use hltas::types::{FrameBulk, Properties};
+use hltas_editor_core::undo_redo_hltas::UndoRedoHandler;
 use imgui::Ui;
 
-use crate::guis::main::{
-    option_menu::AppOptions, tab::HLTASMenuState, undo_redo_hltas::UndoRedoHandler,
-};
+use crate::guis::main::{option_menu::AppOptions, tab::HLTASMenuState};
 
 pub struct FramebulkInfo<'a> {
     pub framebulk: &'a mut FrameBulk,
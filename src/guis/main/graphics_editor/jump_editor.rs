@@ -1,10 +1,9 @@
 use hltas::types::{
     JumpBug, LeaveGroundAction, LeaveGroundActionSpeed, LeaveGroundActionType, Line, Times,
 };
+use hltas_editor_core::undo_redo_hltas::UndoRedoHandler;
 use imgui::{Selectable, StyleColor, Ui};
 
-use crate::guis::main::undo_redo_hltas::UndoRedoHandler;
-
 use super::framebulk_editor::{FramebulkEditor, FramebulkEditorMiscData, FramebulkInfo};
 
 struct BeforeState {
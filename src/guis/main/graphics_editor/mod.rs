@@ -10,16 +10,20 @@ mod yaw_pitch_editor;
 
 use std::num::NonZeroU32;
 
-use hltas::types::{
-    Button, Buttons, Change, ChangeTarget, Line, Seeds, VectorialStrafingConstraints,
+use hltas::types::{Button, Buttons, ChangeTarget, Line, Seeds, VectorialStrafingConstraints};
+use imgui::{
+    ComboBox, Drag, InputFloat, InputText, ItemHoveredFlags, MouseButton, Selectable, StyleColor,
+    Ui,
 };
-use imgui::{ComboBox, Drag, InputFloat, InputText, MouseButton, Selectable, StyleColor, Ui};
 use native_dialog::{MessageDialog, MessageType};
 use winit::event::VirtualKeyCode;
 
 use crate::{
     guis::{radio_button_enum::show_radio_button_enum, x_button::show_x_button},
-    helpers::hltas::{button_to_str, empty_framebulk},
+    helpers::{
+        hltas::{button_to_str, timing::line_timings},
+        imgui::input_editor::InputUsize,
+    },
 };
 
 use self::{
@@ -37,7 +41,9 @@ use self::{
 use super::{
     cmd_editor::show_cmd_editor,
     key_state::KeyboardState,
+    new_line::{insert_new_line, NewLineKind},
     option_menu::AppOptions,
+    problems_menu::{fix_defaults, severity_color},
     property_some_none_field::{property_some_none_field_ui, PropertyFieldResult},
     property_string_field::property_string_field_ui,
     tab::HLTASFileTab,
@@ -53,12 +59,13 @@ pub fn show_graphics_editor(
     options: &AppOptions,
     keyboard_state: &KeyboardState,
 ) {
-    tab.tab_menu_data.tick();
+    tab.line_states.tick();
 
     let draw_list = ui.get_window_draw_list();
 
     ui.text(options.locale_lang().get_string_from_id("properties"));
 
+    let properties_before_edit = tab.hltas_properties().to_owned();
     let properties_edited = ui.group(|| {
         let demo_edited = property_string_field_ui(
             ui,
@@ -254,152 +261,83 @@ pub fn show_graphics_editor(
             || load_cmds_edited
     });
 
+    if properties_edited {
+        tab.line_states
+            .set_properties_edit_backup(&properties_before_edit);
+    }
+    // the undo point is set after the user is done editing the field
+    if !ui.is_item_active() {
+        tab.set_properties_undo_point();
+    }
+
     ui.separator();
     ui.text(options.locale_lang().get_string_from_id("lines"));
 
     let new_line_menu_id = "new_line_menu";
     ui.popup(new_line_menu_id, || {
-        let previous_lines = match tab.tab_menu_data.right_click_popup_index() {
-            Some(index) => Some(&tab.hltas_lines()[..index]),
-            None => {
-                let lines = tab.hltas_lines();
-                if lines.is_empty() {
-                    None
-                } else {
-                    Some(lines)
-                }
-            }
-        };
+        if let Some(line_index) = tab.line_states.right_click_line() {
+            if let Some(Line::FrameBulk(framebulk)) = tab.hltas_lines().get(line_index) {
+                let frame_count = framebulk.frame_count.get() as usize;
+                let locale_lang = options.locale_lang();
 
-        let new_framebulk_with_frametime_framecount = || {
-            let default_frametime = &options.default_frametime().to_string();
+                ui.text(locale_lang.get_string_from_id("framebulk-menu"));
 
-            let frametime = if let Some(previous_lines) = previous_lines {
-                if let Some(Line::FrameBulk(framebulk)) = &previous_lines
-                    .iter()
-                    .rev()
-                    .find(|line| matches!(line, Line::FrameBulk(..)))
-                {
-                    &framebulk.frame_time
-                } else {
-                    default_frametime
-                }
-            } else {
-                default_frametime
-            };
-
-            let frame_count = NonZeroU32::new(1).unwrap();
-
-            Line::FrameBulk(empty_framebulk(frametime, frame_count))
-        };
+                let split_frame = tab.line_states.right_click_split_frame_mut();
+                ui.set_next_item_width(ui.calc_text_size("00000000")[0]);
+                InputUsize::new().build(ui, "##split_framebulk_frame", split_frame);
+                // the second framebulk needs at least a frame
+                *split_frame = (*split_frame).clamp(1, frame_count.max(2) - 1);
+                let split_frame = *split_frame as u32;
 
-        // TODO option for what to choose here
-        let name_and_types = vec![
-            ("framebulk", {
-                let new_framebulk = new_framebulk_with_frametime_framecount();
-
-                if options.copy_previous_framebulk() {
-                    if let Some(previous_lines) = previous_lines {
-                        if let Some(previous_framebulk) = previous_lines
-                            .iter()
-                            .rev()
-                            .find(|line| matches!(line, Line::FrameBulk(..)))
-                        {
-                            previous_framebulk.to_owned()
-                        } else {
-                            new_framebulk
-                        }
-                    } else {
-                        new_framebulk
+                ui.same_line();
+                ui.disabled(frame_count < 2, || {
+                    if ui.button(locale_lang.get_string_from_id("split-framebulk")) {
+                        tab.split_framebulk_at(line_index, split_frame);
+                        ui.close_current_popup();
                     }
-                } else {
-                    new_framebulk
-                }
-            }),
-            ("empty framebulk", new_framebulk_with_frametime_framecount()),
-            ("save", Line::Save(options.save_buffer_name().to_string())),
-            ("shared seed", Line::SharedSeed(0)),
-            (
-                "buttons",
-                Line::Buttons(Buttons::Set {
-                    air_left: Button::Left,
-                    air_right: Button::Right,
-                    ground_left: Button::Left,
-                    ground_right: Button::Right,
-                }),
-            ),
-            ("lgagst min spd", {
-                let options_lgagst_min_spd = Line::LGAGSTMinSpeed(options.lgagst_min_speed());
-
-                if options.lgagst_min_speed_grab_prev() {
-                    if let Some(previous_lines) = previous_lines {
-                        if let Some(Line::LGAGSTMinSpeed(lgagst_min_spd)) = previous_lines
-                            .iter()
-                            .rev()
-                            .find(|line| matches!(line, Line::LGAGSTMinSpeed(_)))
-                        {
-                            Line::LGAGSTMinSpeed(*lgagst_min_spd)
-                        } else {
-                            options_lgagst_min_spd
-                        }
-                    } else {
-                        options_lgagst_min_spd
+                });
+
+                ui.disabled(!tab.can_merge_framebulk_at(line_index), || {
+                    if ui.button(locale_lang.get_string_from_id("merge-framebulks")) {
+                        tab.merge_framebulks_at(line_index);
+                        ui.close_current_popup();
                     }
-                } else {
-                    options_lgagst_min_spd
+                });
+                if ui.is_item_hovered_with_flags(ItemHoveredFlags::ALLOW_WHEN_DISABLED) {
+                    ui.tooltip_text(locale_lang.get_string_from_id("merge-framebulks-tooltip"));
                 }
-            }),
-            ("non-shared seed", Line::Reset { non_shared_seed: 0 }),
-            (
-                "comment",
-                Line::Comment(options.default_comment().to_string()),
-            ),
-            ("vectorial strafing", {
-                let default_option = Line::VectorialStrafing(true);
-                if let Some(previous_lines) = previous_lines {
-                    if let Some(Line::VectorialStrafing(vectorial_strafing)) = previous_lines
-                        .iter()
-                        .rev()
-                        .find(|line| matches!(line, Line::VectorialStrafing(_)))
-                    {
-                        Line::VectorialStrafing(!*vectorial_strafing)
-                    } else {
-                        default_option
+
+                ui.separator();
+            }
+
+            let quick_fixes = tab
+                .diagnostics()
+                .iter()
+                .filter(|diagnostic| diagnostic.line_index == line_index)
+                .flat_map(|diagnostic| diagnostic.kind.quick_fixes().iter().copied())
+                .collect::<Vec<_>>();
+            if !quick_fixes.is_empty() {
+                let locale_lang = options.locale_lang();
+
+                ui.text(locale_lang.get_string_from_id("quick-fixes"));
+                for quick_fix in quick_fixes {
+                    if ui.button(locale_lang.get_string_from_id(quick_fix.id())) {
+                        tab.apply_quick_fixes(&[(line_index, quick_fix)], &fix_defaults(options));
+                        ui.close_current_popup();
                     }
-                } else {
-                    default_option
                 }
-            }),
-            (
-                "vectorial strafing constraints",
-                Line::VectorialStrafingConstraints(
-                    VectorialStrafingConstraints::VelocityYawLocking { tolerance: 0.0 },
-                ),
-            ),
-            // TODO think about this one
-            (
-                "change",
-                Line::Change(Change {
-                    target: ChangeTarget::Yaw,
-                    final_value: 0.0,
-                    over: 0.4,
-                }),
-            ),
-            ("target yaw override", Line::TargetYawOverride(vec![0.0])),
-        ];
 
-        ui.text("new line menu");
+                ui.separator();
+            }
+        }
 
-        let half_way_index = name_and_types.len() / 2;
-        for (i, (button_name, button_type)) in name_and_types.iter().enumerate() {
-            if ui.button(button_name) {
-                tab.new_line_at_click_index(button_type.to_owned());
+        ui.text("new line menu");
 
-                let right_click_index = match tab.tab_menu_data.right_click_popup_index() {
-                    Some(index) => index,
-                    None => tab.hltas_lines().len() - 1,
-                };
-                tab.undo_redo_handler.add_lines(vec![right_click_index]);
+        let half_way_index = NewLineKind::ALL.len() / 2;
+        for (i, kind) in NewLineKind::ALL.iter().enumerate() {
+            if ui.button(kind.label()) {
+                let right_click_index = tab.line_states.right_click_popup_index();
+                insert_new_line(tab, options, *kind, right_click_index);
 
                 ui.close_current_popup();
             }
@@ -412,7 +350,7 @@ pub fn show_graphics_editor(
 
     if tab.hltas_lines().is_empty() {
         if ui.is_mouse_clicked(MouseButton::Right) {
-            tab.tab_menu_data.right_click_elsewhere();
+            tab.line_states.right_click_elsewhere();
             ui.open_popup(new_line_menu_id);
         }
 
@@ -425,11 +363,26 @@ pub fn show_graphics_editor(
     let mut stale_line = None;
     let mut new_line_menu_clicked_on_line = false;
 
+    let line_timings = if tab.line_states.timing_gutter() {
+        Some(line_timings(tab.hltas()))
+    } else {
+        None
+    };
+
+    let diagnostics = tab.diagnostics().to_vec();
+
     let (lines, properties, tab_menu_data, undo_redo_handler) = tab.split_fields_mut();
     let lines_is_empty = lines.is_empty();
     let goto_line = tab_menu_data.goto_line();
+    let mut top_line = None;
 
     for (i, line) in lines.iter_mut().enumerate() {
+        if tab_menu_data.is_line_filtered_out(line) {
+            // lines selected with select all or a range stay out of reach
+            tab_menu_data.change_selected_index(i, false);
+            continue;
+        }
+
         if let Some(goto_line) = goto_line {
             if i == goto_line {
                 ui.set_scroll_here_y_with_ratio(0.0);
@@ -445,10 +398,53 @@ pub fn show_graphics_editor(
             cursor_y > scroll_y - 120.0 && cursor_y < scroll_y + window_y
         };
 
+        if top_line.is_none() && ui.cursor_pos()[1] >= ui.scroll_y() {
+            top_line = Some(i);
+        }
+
         if is_rendering_line {
             ui.text(format!("{}", i + 1));
             ui.same_line();
 
+            let line_diagnostics = diagnostics
+                .iter()
+                .filter(|diagnostic| diagnostic.line_index == i)
+                .collect::<Vec<_>>();
+            if let Some(severity) = line_diagnostics
+                .iter()
+                .map(|diagnostic| diagnostic.severity())
+                .max()
+            {
+                ui.text_colored(severity_color(severity), "!");
+                if ui.is_item_hovered() {
+                    ui.tooltip(|| {
+                        for diagnostic in &line_diagnostics {
+                            ui.text_colored(
+                                severity_color(diagnostic.severity()),
+                                options
+                                    .locale_lang()
+                                    .get_string_from_id(diagnostic.kind.id()),
+                            );
+                        }
+                    });
+                }
+                ui.same_line();
+            }
+
+            if let Some(line_timings) = &line_timings {
+                let timing = &line_timings[i];
+
+                if timing.zero_ms_frames == 0 {
+                    ui.text_disabled(format!("{} {:.3}s", timing.frame, timing.time));
+                } else {
+                    ui.text_disabled(format!(
+                        "{}+{} {:.3}s",
+                        timing.frame, timing.zero_ms_frames, timing.time
+                    ));
+                }
+                ui.same_line();
+            }
+
             // grab area
             // TODO option for area size
             ui.dummy([20.0, 20.0]);
@@ -491,8 +487,6 @@ pub fn show_graphics_editor(
                 tab_menu_data.change_selected_index(i, !is_selected);
             }
 
-            if keyboard_state.just_pressed(VirtualKeyCode::Delete) {}
-
             // TODO translation
             let mut line_edited = false;
             ui.group(|| {
@@ -621,7 +615,7 @@ pub fn show_graphics_editor(
                                     ComboBox::new(format!("##button_editor{}{}", i, id))
                                         .preview_value(button_to_str(button))
                                         .build(ui, || {
-                                            let button_enums = vec![
+                                            let button_enums = [
                                                 Button::Forward,
                                                 Button::ForwardLeft,
                                                 Button::Left,
@@ -914,7 +908,7 @@ pub fn show_graphics_editor(
 
                 // check if right click for new line menu
                 if ui.is_mouse_clicked(MouseButton::Right) {
-                    tab_menu_data.set_right_click_index(i + 1);
+                    tab_menu_data.set_right_click_line(i);
                     new_line_menu_clicked_on_line = true;
                     ui.open_popup(new_line_menu_id);
                 }
@@ -946,6 +940,21 @@ pub fn show_graphics_editor(
                 .filled(tab_menu_data.is_line_selected(i))
                 .build();
 
+            if tab_menu_data.is_search_match(i) {
+                draw_list
+                    .add_rect(
+                        group_rect_min,
+                        group_rect_max,
+                        if tab_menu_data.is_current_search_match(i) {
+                            [1.0, 0.843, 0.0, 0.35]
+                        } else {
+                            [1.0, 0.843, 0.0, 0.15]
+                        },
+                    )
+                    .filled(true)
+                    .build();
+            }
+
             if !lines_edited && line_edited {
                 lines_edited = true;
             }
@@ -977,39 +986,26 @@ pub fn show_graphics_editor(
         }
     }
 
+    tab_menu_data.set_top_line(top_line.unwrap_or(0));
+
     // if tab_menu_data.is_hovering_something() && lines_edited {
     //     tab_menu_data.hovering_line_edited(undo_redo_handler);
     // }
 
     if let Some(stale_line) = stale_line {
-        tab.undo_redo_handler
-            .delete_lines(vec![(stale_line, tab.hltas_lines()[stale_line].to_owned())]);
+        let line = tab.hltas_lines()[stale_line].to_owned();
+        tab.undo_redo_handler.delete_lines(vec![(stale_line, line)]);
         tab.remove_line_at_index(stale_line);
     }
 
-    if !tab.tab_menu_data.is_modifying_line()
+    if !tab.line_states.is_modifying_line()
         && (keyboard_state.just_pressed(VirtualKeyCode::Delete)
             || keyboard_state.just_pressed(VirtualKeyCode::Back))
     {
-        let lines_to_delete = tab
-            .tab_menu_data
-            .selected_indexes()
-            .iter()
-            .enumerate()
-            .filter_map(|(i, is_selected)| {
-                if *is_selected {
-                    Some((i, tab.hltas_lines()[i].to_owned()))
-                } else {
-                    None
-                }
-            })
-            .collect::<Vec<_>>();
-        tab.undo_redo_handler.delete_lines(lines_to_delete);
-
-        tab.remove_selected_lines();
+        tab.delete_selected_lines();
     }
 
     if properties_edited || lines_edited {
-        tab.tab_menu_data.got_modified();
+        tab.got_modified();
     }
 }
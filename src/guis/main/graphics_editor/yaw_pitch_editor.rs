@@ -171,7 +171,7 @@ impl FramebulkEditor for YawPitchEditor {
         };
 
         let angles_text = {
-            let angles_text = vec![
+            let angles_text = [
                 match yaw {
                     Some(yaw) => yaw.to_string(),
                     None => "-".to_string(),
@@ -1,9 +1,23 @@
 use std::fmt::Display;
 
+use serde::{Deserialize, Serialize};
 use winit::event::VirtualKeyCode;
 
 use super::key_state::KeyboardState;
 
+/// Keys that can be bound by pressing them in the key bindings menu
+const CAPTURABLE_KEYS: &[VirtualKeyCode] = {
+    use VirtualKeyCode::*;
+    &[
+        A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, Key0, Key1,
+        Key2, Key3, Key4, Key5, Key6, Key7, Key8, Key9, F1, F2, F3, F4, F5, F6, F7, F8, F9, F10,
+        F11, F12, Insert, Delete, Home, End, PageUp, PageDown, Left, Up, Right, Down, Back, Return,
+        Space, Tab, Minus, Equals, Comma, Period, Slash, Semicolon, Apostrophe, LBracket, RBracket,
+        Backslash, Grave,
+    ]
+};
+
+#[derive(Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub struct KeyCombination {
     key_ctrl: bool,
     key_alt: bool,
@@ -28,12 +42,12 @@ impl KeyCombination {
         }
     }
 
-    // pub fn alt(self) -> Self {
-    //     Self {
-    //         key_alt: true,
-    //         ..self
-    //     }
-    // }
+    pub fn alt(self) -> Self {
+        Self {
+            key_alt: true,
+            ..self
+        }
+    }
 
     pub fn shift(self) -> Self {
         Self {
@@ -42,14 +56,35 @@ impl KeyCombination {
         }
     }
 
-    pub fn just_pressed(&self, keyboard_state: &KeyboardState) -> bool {
-        let alt_pressed =
-            keyboard_state.held(VirtualKeyCode::LAlt) || keyboard_state.held(VirtualKeyCode::RAlt);
+    /// Creates a key combination from the key that just got pressed with the held modifiers
+    pub fn from_just_pressed(keyboard_state: &KeyboardState) -> Option<Self> {
+        let key = *CAPTURABLE_KEYS
+            .iter()
+            .find(|key| keyboard_state.just_pressed(**key))?;
+        let (ctrl_pressed, alt_pressed, shift_pressed) = Self::modifiers_held(keyboard_state);
+
+        Some(Self {
+            key_ctrl: ctrl_pressed,
+            key_alt: alt_pressed,
+            key_shift: shift_pressed,
+            key,
+        })
+    }
+
+    fn modifiers_held(keyboard_state: &KeyboardState) -> (bool, bool, bool) {
         let ctrl_pressed = keyboard_state.held(VirtualKeyCode::LControl)
             || keyboard_state.held(VirtualKeyCode::RControl);
+        let alt_pressed =
+            keyboard_state.held(VirtualKeyCode::LAlt) || keyboard_state.held(VirtualKeyCode::RAlt);
         let shift_pressed = keyboard_state.held(VirtualKeyCode::LShift)
             || keyboard_state.held(VirtualKeyCode::RShift);
 
+        (ctrl_pressed, alt_pressed, shift_pressed)
+    }
+
+    pub fn just_pressed(&self, keyboard_state: &KeyboardState) -> bool {
+        let (ctrl_pressed, alt_pressed, shift_pressed) = Self::modifiers_held(keyboard_state);
+
         (self.key_alt == alt_pressed)
             && (self.key_ctrl == ctrl_pressed)
             && (self.key_shift == shift_pressed)
@@ -0,0 +1,90 @@
+use std::{collections::HashMap, convert::TryFrom};
+
+use serde::{Deserialize, Deserializer, Serialize};
+
+use super::{actions::Action, key_combination::KeyCombination};
+
+/// Key combinations of each action
+///
+/// * Actions that aren't in the map use their default key combination
+/// * `None` unbinds the action
+#[derive(Clone, Default, Serialize, Deserialize)]
+pub struct Keymap {
+    #[serde(deserialize_with = "deserialize_bindings")]
+    bindings: HashMap<Action, Option<KeyCombination>>,
+}
+
+/// Skips bindings of unknown actions, so an action that got removed or renamed doesn't fail loading the options
+fn deserialize_bindings<'de, D>(
+    deserializer: D,
+) -> Result<HashMap<Action, Option<KeyCombination>>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let bindings = HashMap::<String, Option<KeyCombination>>::deserialize(deserializer)?;
+
+    Ok(bindings
+        .into_iter()
+        .filter_map(|(id, key)| match Action::try_from(id) {
+            Ok(action) => Some((action, key)),
+            Err(err) => {
+                eprintln!("skipping key binding: {}", err);
+                None
+            }
+        })
+        .collect())
+}
+
+impl Keymap {
+    pub fn get(&self, action: &Action) -> Option<KeyCombination> {
+        match self.bindings.get(action) {
+            Some(key) => *key,
+            None => action.default_key(),
+        }
+    }
+
+    pub fn set(&mut self, action: Action, key: KeyCombination) {
+        if Some(key) == action.default_key() {
+            self.bindings.remove(&action);
+        } else {
+            self.bindings.insert(action, Some(key));
+        }
+    }
+
+    /// Removes the key combination, including the default one
+    pub fn unbind(&mut self, action: Action) {
+        if action.default_key().is_none() {
+            self.bindings.remove(&action);
+        } else {
+            self.bindings.insert(action, None);
+        }
+    }
+
+    pub fn reset(&mut self, action: &Action) {
+        self.bindings.remove(action);
+    }
+
+    pub fn reset_all(&mut self) {
+        self.bindings.clear();
+    }
+
+    /// Other actions that are bound to the same key combination as `action`
+    pub fn conflicts(&self, action: &Action) -> Vec<Action> {
+        let key = match self.get(action) {
+            Some(key) => key,
+            None => return Vec::new(),
+        };
+
+        Action::all()
+            .into_iter()
+            .filter(|other| other != action && self.get(other) == Some(key))
+            .collect()
+    }
+
+    /// Shortcut text shown next to menu items
+    pub fn shortcut_text(&self, action: &Action) -> String {
+        self.get(action)
+            .map(|key| key.to_string())
+            .unwrap_or_default()
+    }
+}
@@ -0,0 +1,266 @@
+use std::{fs, path::Path};
+
+use hltas::{types::Line, HLTAS};
+use imgui::{ChildWindow, CollapsingHeader, Condition, InputText, TreeNodeFlags, Ui, Window};
+use native_dialog::FileDialog;
+
+use crate::{
+    guis::radio_button_enum::show_radio_button_enum,
+    helpers::{
+        hltas::{
+            lines_to_str,
+            merge::{ConflictResolution, Merge, PropertiesMerge, Side},
+        },
+        locale::locale_lang::LocaleLang,
+    },
+};
+
+fn read_hltas(path: &str) -> Result<HLTAS, String> {
+    let file_content = fs::read_to_string(path).map_err(|err| format!("{}: {}", path, err))?;
+    HLTAS::from_str(&file_content).map_err(|err| format!("{}: {}", path, err))
+}
+
+fn show_lines(ui: &Ui, lines: &[Line]) {
+    if lines.is_empty() {
+        ui.text_disabled("-");
+    } else {
+        ui.text(lines_to_str(lines.to_vec()));
+    }
+}
+
+/// Three-way merge of hltas files with the conflicts picked by the user
+#[derive(Default)]
+pub struct MergeMenu {
+    opened: bool,
+    base_path: String,
+    ours_path: String,
+    theirs_path: String,
+    merge: Option<Merge>,
+    error: Option<String>,
+}
+
+impl MergeMenu {
+    pub fn open(&mut self) {
+        self.opened = true;
+    }
+
+    fn merge(&mut self) {
+        let files = read_hltas(&self.base_path).and_then(|base| {
+            Ok((
+                base,
+                read_hltas(&self.ours_path)?,
+                read_hltas(&self.theirs_path)?,
+            ))
+        });
+
+        match files {
+            Ok((base, ours, theirs)) => {
+                self.merge = Some(Merge::new(&base, &ours, &theirs));
+                self.error = None;
+            }
+            Err(err) => {
+                self.merge = None;
+                self.error = Some(err);
+            }
+        }
+    }
+
+    /// Title of the tab the merge result opens in
+    pub fn result_title(&self) -> String {
+        Path::new(&self.ours_path)
+            .file_name()
+            .map(|file_name| file_name.to_string_lossy().to_string())
+            .unwrap_or_default()
+    }
+
+    /// Returns the merged hltas once the user opens it
+    pub fn show(&mut self, ui: &Ui, locale_lang: &LocaleLang) -> Option<HLTAS> {
+        if !self.opened {
+            return None;
+        }
+
+        let mut opened = self.opened;
+        let mut merge_clicked = false;
+        let mut result = None;
+
+        Window::new(locale_lang.get_string_from_id("merge"))
+            .opened(&mut opened)
+            .size([900.0, 600.0], Condition::FirstUseEver)
+            .position_pivot([0.5, 0.5])
+            .position(
+                {
+                    let display_size = ui.io().display_size;
+                    [display_size[0] * 0.5, display_size[1] * 0.5]
+                },
+                Condition::Appearing,
+            )
+            .build(ui, || {
+                for (label_id, path) in [
+                    ("merge-base", &mut self.base_path),
+                    ("merge-ours", &mut self.ours_path),
+                    ("merge-theirs", &mut self.theirs_path),
+                ] {
+                    if ui.button(format!("...##{}", label_id)) {
+                        if let Ok(Some(pathbuf)) = FileDialog::new()
+                            .add_filter("HLTAS Files", &["hltas", "txt"])
+                            .add_filter("Any", &["*"])
+                            .show_open_single_file()
+                        {
+                            *path = pathbuf.to_string_lossy().to_string();
+                        }
+                    }
+                    ui.same_line();
+                    InputText::new(ui, locale_lang.get_string_from_id(label_id), path).build();
+                }
+
+                merge_clicked = ui.button(locale_lang.get_string_from_id("merge-start"));
+
+                if let Some(error) = &self.error {
+                    ui.text_colored([1.0, 0.4, 0.4, 1.0], error);
+                }
+
+                let merge = match &mut self.merge {
+                    Some(merge) => merge,
+                    None => return,
+                };
+
+                ui.separator();
+
+                let unresolved_count = merge.unresolved_count();
+                ui.text(format!(
+                    "{}: {}",
+                    locale_lang.get_string_from_id("merge-unresolved"),
+                    unresolved_count
+                ));
+                ui.same_line();
+                ui.disabled(unresolved_count > 0, || {
+                    if ui.button(locale_lang.get_string_from_id("merge-open-result")) {
+                        result = merge.result();
+                    }
+                });
+
+                let ours_label = locale_lang.get_string_from_id("merge-ours");
+                let theirs_label = locale_lang.get_string_from_id("merge-theirs");
+
+                ChildWindow::new("merge_conflicts")
+                    .border(true)
+                    .horizontal_scrollbar(true)
+                    .build(ui, || {
+                        if let PropertiesMerge::Conflict { pick, .. } = &mut merge.properties {
+                            ui.text(locale_lang.get_string_from_id("merge-properties-conflict"));
+                            show_radio_button_enum(
+                                ui,
+                                pick,
+                                vec![
+                                    (ours_label.as_str(), Some(Side::Ours)),
+                                    (theirs_label.as_str(), Some(Side::Theirs)),
+                                ],
+                                "merge_properties".to_string(),
+                                true,
+                            );
+                            ui.separator();
+                        }
+
+                        for (i, conflict) in merge.conflicts_mut().enumerate() {
+                            let header = format!(
+                                "{} {} ({} {} / {} {}) {}###merge_conflict{}",
+                                locale_lang.get_string_from_id("merge-conflict"),
+                                i + 1,
+                                ours_label,
+                                conflict.ours_index + 1,
+                                theirs_label,
+                                conflict.theirs_index + 1,
+                                if conflict.is_resolved() {
+                                    locale_lang.get_string_from_id("merge-resolved")
+                                } else {
+                                    String::new()
+                                },
+                                i
+                            );
+                            if !CollapsingHeader::new(&header)
+                                .flags(TreeNodeFlags::DEFAULT_OPEN)
+                                .build(ui)
+                            {
+                                continue;
+                            }
+
+                            ui.columns(3, format!("merge_conflict_columns{}", i), true);
+                            ui.text_disabled(locale_lang.get_string_from_id("merge-base"));
+                            show_lines(ui, &conflict.base);
+                            ui.next_column();
+                            ui.text_disabled(&ours_label);
+                            show_lines(ui, &conflict.ours);
+                            ui.next_column();
+                            ui.text_disabled(&theirs_label);
+                            show_lines(ui, &conflict.theirs);
+                            ui.columns(1, format!("merge_conflict_columns_end{}", i), false);
+
+                            let by_field_label = locale_lang.get_string_from_id("merge-by-field");
+                            let ours_then_theirs_label =
+                                locale_lang.get_string_from_id("merge-ours-then-theirs");
+                            let theirs_then_ours_label =
+                                locale_lang.get_string_from_id("merge-theirs-then-ours");
+                            let mut resolutions = vec![
+                                (ours_label.as_str(), Some(ConflictResolution::Ours)),
+                                (theirs_label.as_str(), Some(ConflictResolution::Theirs)),
+                                (
+                                    ours_then_theirs_label.as_str(),
+                                    Some(ConflictResolution::OursThenTheirs),
+                                ),
+                                (
+                                    theirs_then_ours_label.as_str(),
+                                    Some(ConflictResolution::TheirsThenOurs),
+                                ),
+                            ];
+                            let can_merge_fields = conflict.merged_framebulks.is_some();
+                            if can_merge_fields {
+                                resolutions.insert(0, (by_field_label.as_str(), None));
+                            }
+                            show_radio_button_enum(
+                                ui,
+                                &mut conflict.resolution,
+                                resolutions,
+                                format!("merge_resolution{}", i),
+                                true,
+                            );
+
+                            if can_merge_fields && conflict.resolution.is_none() {
+                                for (j, field_conflict) in
+                                    conflict.field_conflicts.iter_mut().enumerate()
+                                {
+                                    ui.text(format!(
+                                        "{} {}: {}",
+                                        locale_lang.get_string_from_id("framebulk-menu"),
+                                        conflict.ours_index + field_conflict.index + 1,
+                                        field_conflict.field.label()
+                                    ));
+                                    ui.same_line();
+                                    show_radio_button_enum(
+                                        ui,
+                                        &mut field_conflict.pick,
+                                        vec![
+                                            (ours_label.as_str(), Some(Side::Ours)),
+                                            (theirs_label.as_str(), Some(Side::Theirs)),
+                                        ],
+                                        format!("merge_field{}_{}", i, j),
+                                        true,
+                                    );
+                                }
+                            }
+
+                            ui.separator();
+                        }
+                    });
+            });
+
+        self.opened = opened;
+        if merge_clicked {
+            self.merge();
+        }
+        if result.is_some() {
+            self.opened = false;
+        }
+
+        result
+    }
+}
@@ -1,35 +1,77 @@
+mod actions;
+mod bulk_edit_menu;
 mod cmd_editor;
+mod command_palette;
+mod compare_menu;
+mod diff_view;
+mod external_change_menu;
+mod filter_menu;
+mod find_menu;
 mod goto_menu;
 mod graphics_editor;
 mod key_combination;
 mod key_state;
+mod keymap;
+mod merge_menu;
+mod new_line;
 mod option_menu;
+mod problems_menu;
 mod property_some_none_field;
 mod property_string_field;
+mod recovery;
+mod session;
+mod statistics_menu;
 mod tab;
-pub mod undo_redo_hltas;
+mod text_editor;
+mod transform_menu;
+mod undo_history_menu;
+mod undo_history_save;
 mod zero_ms_editor;
 
 use std::cell::RefCell;
+use std::error::Error;
 use std::path::Path;
 use std::rc::Rc;
+use std::time::Instant;
 use std::{collections::VecDeque, fs, path::PathBuf};
 
-use hltas_cleaner::cleaners;
+use hltas::HLTAS;
+use hltas_editor_core::document::{read_disk_modified_time, title_from_path};
 use imgui::{
     Condition, MenuItem, StyleVar, TabBar, TabItem, TabItemFlags, Ui, Window, WindowFlags,
 };
 use native_dialog::{FileDialog, MessageDialog, MessageType};
-use winit::event::VirtualKeyCode;
 
-use crate::helpers::hltas::{lines_to_str, str_to_lines};
+use crate::helpers::hltas::{
+    check_hltas,
+    cleaner::Cleaner,
+    csv::{csv_to_hltas, hltas_to_csv},
+    hltas_to_str,
+    json::{hltas_to_json, json_to_hltas},
+};
 
+use self::actions::Action;
+use self::bulk_edit_menu::BulkEditMenu;
+use self::command_palette::CommandPalette;
+use self::compare_menu::CompareMenu;
+use self::external_change_menu::ExternalChangeMenu;
+use self::filter_menu::FilterMenu;
+use self::find_menu::FindMenu;
 use self::goto_menu::GotoMenu;
 use self::graphics_editor::show_graphics_editor;
-use self::key_combination::KeyCombination;
 use self::key_state::KeyboardState;
+use self::merge_menu::MergeMenu;
+use self::new_line::insert_new_line;
 use self::option_menu::{AppOptions, OptionMenu};
+use self::problems_menu::ProblemsMenu;
+use self::recovery::RecoveryTab;
+use self::session::{Session, SessionTab};
+use self::statistics_menu::StatisticsMenu;
 use self::tab::HLTASFileTab;
+use self::text_editor::show_text_editor;
+use self::transform_menu::TransformMenu;
+use self::undo_history_menu::UndoHistoryMenu;
+use self::undo_history_save::SavedUndoHistories;
 
 pub struct MainGUI {
     tabs: Vec<Rc<RefCell<HLTASFileTab>>>,
@@ -43,6 +85,21 @@ pub struct MainGUI {
     debug_menu_opened: bool,
     keyboard_state: KeyboardState,
     goto_menu: GotoMenu,
+    find_menu: FindMenu,
+    filter_menu: FilterMenu,
+    undo_history_menu: UndoHistoryMenu,
+    last_autosave: Instant,
+    /// Recovered tabs the user didn't restore yet, written back with every autosave
+    kept_recovery_tabs: Vec<RecoveryTab>,
+    last_external_change_check: Instant,
+    external_change_menu: ExternalChangeMenu,
+    command_palette: CommandPalette,
+    statistics_menu: StatisticsMenu,
+    problems_menu: ProblemsMenu,
+    bulk_edit_menu: BulkEditMenu,
+    transform_menu: TransformMenu,
+    compare_menu: CompareMenu,
+    merge_menu: MergeMenu,
 }
 
 impl MainGUI {
@@ -65,6 +122,76 @@ impl MainGUI {
         }
     }
 
+    /// Exports the current tab as csv or json to a location picked with a dialog
+    pub fn export_current_tab(&self, json: bool) -> Result<(), Box<dyn Error>> {
+        let mut tab = match &self.current_tab {
+            Some(tab) => tab.borrow_mut(),
+            None => return Ok(()),
+        };
+        // text editor content is what the user sees, so that has to be exported
+        tab.apply_text_editor()?;
+
+        let (filter_name, extension, content) = if json {
+            ("JSON Files", "json", hltas_to_json(tab.hltas()))
+        } else {
+            ("CSV Files", "csv", hltas_to_csv(tab.hltas()))
+        };
+
+        if let Ok(Some(path)) = FileDialog::new()
+            .add_filter(filter_name, &[extension])
+            .show_save_single_file()
+        {
+            fs::write(path, content)?;
+        }
+
+        Ok(())
+    }
+
+    /// Opens a csv or json file picked with a dialog in a new unsaved tab
+    pub fn import_file_by_dialog(&mut self) -> Result<(), Box<dyn Error>> {
+        let path = match FileDialog::new()
+            .add_filter("CSV / JSON Files", &["csv", "json"])
+            .add_filter("Any", &["*"])
+            .show_open_single_file()
+        {
+            Ok(Some(path)) => path,
+            _ => return Ok(()),
+        };
+
+        let content = fs::read_to_string(&path)?;
+        let hltas = if path
+            .extension()
+            .is_some_and(|extension| extension == "json")
+        {
+            json_to_hltas(&content)?
+        } else {
+            csv_to_hltas(&content)?
+        };
+        check_hltas(&hltas)?;
+
+        let title = title_from_path(
+            &path.with_extension("hltas"),
+            &self.options.locale_lang().get_lang(),
+        );
+        let tab = HLTASFileTab::from_hltas(title, hltas);
+        self.tabs.push(Rc::new(RefCell::new(tab)));
+
+        if self.options.auto_switch_new_tab() {
+            self.tab_switch_index = Some(self.tabs.len() - 1);
+        }
+
+        Ok(())
+    }
+
+    fn show_error(&self, text: &str) {
+        MessageDialog::new()
+            .set_title(&self.options.locale_lang().get_string_from_id("error"))
+            .set_type(MessageType::Error)
+            .set_text(text)
+            .show_alert()
+            .ok();
+    }
+
     fn add_recent_path(&mut self, path: &Path) {
         let path_as_str = path.as_os_str().to_str();
 
@@ -81,8 +208,12 @@ impl MainGUI {
         }
 
         self.recent_paths.push_back(path.to_owned());
+        self.truncate_recent_paths();
+    }
 
-        if self.recent_paths.len() > self.options.recent_path_size() {
+    /// Removes the oldest recent paths above the recent path size
+    fn truncate_recent_paths(&mut self) {
+        while self.recent_paths.len() > self.options.recent_path_size() {
             self.recent_paths.pop_front();
         }
     }
@@ -102,9 +233,13 @@ impl MainGUI {
             }
         }
 
-        if let Ok(file_content) = fs::read_to_string(&path) {
+        if let Ok(file_content) = fs::read_to_string(path) {
             match HLTASFileTab::open_path(path, &file_content) {
-                Ok(tab) => {
+                Ok(mut tab) => {
+                    if self.options.persist_undo_history() {
+                        self.restore_undo_history(&mut tab);
+                    }
+
                     self.tabs.push(Rc::new(RefCell::new(tab)));
 
                     if self.options.auto_switch_new_tab() {
@@ -125,6 +260,308 @@ impl MainGUI {
         }
     }
 
+    fn restore_undo_history(&self, tab: &mut HLTASFileTab) {
+        if let (Ok(mut saved_undo_histories), Some(path)) =
+            (SavedUndoHistories::load(), tab.path().cloned())
+        {
+            if let Some(undo_redo_handler) = saved_undo_histories.take(&path, tab.hltas()) {
+                tab.undo_redo_handler = undo_redo_handler;
+            }
+            saved_undo_histories.save().ok();
+        }
+    }
+
+    /// Saves undo histories of the tabs that have no unsaved changes
+    fn save_undo_histories<'a, I>(&self, tabs: I)
+    where
+        I: IntoIterator<Item = &'a HLTASFileTab>,
+    {
+        if !self.options.persist_undo_history() {
+            return;
+        }
+
+        let mut saved_undo_histories = SavedUndoHistories::load().unwrap_or_default();
+
+        for tab in tabs {
+            if let Some(path) = tab.path() {
+                if !tab.is_modified() {
+                    saved_undo_histories.insert(path, tab.hltas(), &tab.undo_redo_handler);
+                }
+            }
+        }
+
+        if let Err(err) = saved_undo_histories.save() {
+            MessageDialog::new()
+                .set_title(&self.options.locale_lang().get_string_from_id("error"))
+                .set_text(&err.to_string())
+                .set_type(MessageType::Error)
+                .show_alert()
+                .ok();
+        }
+    }
+
+    /// Called when the app is closing
+    pub fn exit(&mut self) {
+        let tabs = self.tabs.iter().map(|tab| tab.borrow()).collect::<Vec<_>>();
+        self.save_undo_histories(tabs.iter().map(|tab| &**tab));
+
+        self.save_session().ok();
+
+        // there's no save prompt on exit, so unsaved tabs are kept for the next start
+        self.save_recovery_tabs();
+    }
+
+    /// Writes all modified tabs to the recovery folder if the autosave interval passed
+    fn autosave(&mut self) {
+        let autosave_interval = self.options.autosave_interval();
+
+        if autosave_interval == 0 || self.last_autosave.elapsed().as_secs() < autosave_interval {
+            return;
+        }
+        self.last_autosave = Instant::now();
+
+        self.save_recovery_tabs();
+    }
+
+    /// Replaces the recovery folder with the modified tabs and the recovered tabs that weren't restored
+    fn save_recovery_tabs(&self) {
+        let recovery_tabs = self
+            .tabs
+            .iter()
+            .filter(|tab| tab.borrow().is_modified())
+            .map(|tab| tab.borrow().to_recovery_tab())
+            .chain(self.kept_recovery_tabs.iter().cloned())
+            .collect::<Vec<_>>();
+
+        // autosave failing shouldn't interrupt the user
+        recovery::save_recovery_tabs(&recovery_tabs).ok();
+    }
+
+    /// Checks if files of the tabs got changed by another program
+    ///
+    /// * Unmodified tabs get reloaded, modified tabs ask the user what to do
+    fn check_external_changes(&mut self) {
+        if self.external_change_menu.is_opened()
+            || self.last_external_change_check.elapsed().as_secs() < 1
+        {
+            return;
+        }
+        self.last_external_change_check = Instant::now();
+
+        for tab in &self.tabs {
+            let path = match tab.borrow().path() {
+                Some(path) => path.to_owned(),
+                None => continue,
+            };
+
+            let disk_modified_time = read_disk_modified_time(&path);
+            if disk_modified_time.is_none()
+                || disk_modified_time == tab.borrow().disk_modified_time()
+            {
+                continue;
+            }
+
+            let file_content = match fs::read_to_string(&path) {
+                Ok(file_content) => file_content,
+                Err(_) => continue,
+            };
+            // the other program might not be done writing, so this is checked again later
+            let hltas_on_disk = match HLTAS::from_str(&file_content) {
+                Ok(hltas) => hltas,
+                Err(_) => continue,
+            };
+
+            tab.borrow_mut().set_disk_modified_time(disk_modified_time);
+
+            if hltas_to_str(&hltas_on_disk) == hltas_to_str(tab.borrow().hltas()) {
+                continue;
+            }
+
+            if tab.borrow().is_modified() {
+                self.external_change_menu
+                    .open(Rc::clone(tab), hltas_on_disk);
+                // one at a time
+                return;
+            }
+
+            tab.borrow_mut().reload_hltas(hltas_on_disk);
+        }
+    }
+
+    /// Asks to restore the tabs left from a crash
+    fn restore_recovery_tabs(&mut self) {
+        let recovery_tabs = match recovery::load_recovery_tabs() {
+            Ok(recovery_tabs) if !recovery_tabs.is_empty() => recovery_tabs,
+            _ => return,
+        };
+
+        let restore = MessageDialog::new()
+            .set_title(&self.options.locale_lang().get_string_from_id("warning"))
+            .set_type(MessageType::Warning)
+            .set_text(&format!(
+                "{}\n{}",
+                self.options
+                    .locale_lang()
+                    .get_string_from_id("recovery-question"),
+                recovery_tabs
+                    .iter()
+                    .map(|recovery_tab| recovery_tab.title.as_str())
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            ))
+            .show_confirm()
+            .unwrap_or(false);
+
+        if !restore {
+            let discard = MessageDialog::new()
+                .set_title(&self.options.locale_lang().get_string_from_id("warning"))
+                .set_type(MessageType::Warning)
+                .set_text(
+                    &self
+                        .options
+                        .locale_lang()
+                        .get_string_from_id("recovery-discard-question"),
+                )
+                .show_confirm()
+                .unwrap_or(false);
+
+            if discard {
+                recovery::clear_recovery_tabs().ok();
+            } else {
+                self.kept_recovery_tabs = recovery_tabs;
+            }
+            return;
+        }
+
+        let tabs = recovery_tabs
+            .iter()
+            .filter_map(|recovery_tab| HLTASFileTab::open_recovery(recovery_tab).ok())
+            .map(|tab| Rc::new(RefCell::new(tab)))
+            .collect::<Vec<_>>();
+
+        if !tabs.is_empty() {
+            // the empty new file tab isn't needed anymore
+            self.tabs.retain(|tab| {
+                let tab = tab.borrow();
+                tab.path().is_some() || tab.is_modified()
+            });
+
+            let mut first_tab_index = None;
+
+            for tab in tabs {
+                let dupe_index = tab.borrow().path().and_then(|path| {
+                    self.tabs
+                        .iter()
+                        .position(|session_tab| session_tab.borrow().path() == Some(path))
+                });
+
+                // the recovered tab is newer than the one from the session
+                let index = match dupe_index {
+                    Some(index) => {
+                        self.tabs[index] = tab;
+                        index
+                    }
+                    None => {
+                        self.tabs.push(tab);
+                        self.tabs.len() - 1
+                    }
+                };
+                first_tab_index.get_or_insert(index);
+            }
+
+            if let Some(first_tab_index) = first_tab_index {
+                self.current_tab = Some(Rc::clone(&self.tabs[first_tab_index]));
+                self.tab_switch_index = Some(first_tab_index);
+            }
+
+            // the restored tabs are modified, so they replace the recovered files
+            self.save_recovery_tabs();
+        }
+    }
+
+    /// Reopens the tabs and recent paths from the last session
+    ///
+    /// * Files that were deleted or can't be parsed anymore are skipped
+    fn restore_session(&mut self) {
+        let session = match Session::load() {
+            Ok(session) => session,
+            Err(_) => return,
+        };
+
+        let mut tabs = Vec::new();
+        let mut current_tab_index = None;
+
+        for (i, session_tab) in session.tabs.iter().enumerate() {
+            let file_content = match fs::read_to_string(&session_tab.path) {
+                Ok(file_content) => file_content,
+                Err(_) => continue,
+            };
+            let mut tab = match HLTASFileTab::open_path(&session_tab.path, &file_content) {
+                Ok(tab) => tab,
+                Err(_) => continue,
+            };
+
+            if self.options.persist_undo_history() {
+                self.restore_undo_history(&mut tab);
+            }
+            tab.line_states.set_view_state(&session_tab.view_state);
+
+            if session.current_tab == Some(i) {
+                current_tab_index = Some(tabs.len());
+            }
+            tabs.push(Rc::new(RefCell::new(tab)));
+        }
+
+        self.recent_paths = session.recent_paths.into_iter().collect();
+        self.truncate_recent_paths();
+
+        if !tabs.is_empty() {
+            let current_tab_index = current_tab_index.unwrap_or(0);
+
+            self.current_tab = Some(Rc::clone(&tabs[current_tab_index]));
+            self.tab_switch_index = Some(current_tab_index);
+            self.tabs = tabs;
+        }
+    }
+
+    fn save_session(&self) -> Result<(), std::io::Error> {
+        let mut session = Session {
+            recent_paths: self
+                .recent_paths
+                .iter()
+                .skip(
+                    self.recent_paths
+                        .len()
+                        .saturating_sub(self.options.recent_path_size()),
+                )
+                .cloned()
+                .collect(),
+            ..Default::default()
+        };
+
+        for tab in &self.tabs {
+            let is_current_tab = match &self.current_tab {
+                Some(current_tab) => current_tab.as_ptr() == tab.as_ptr(),
+                None => false,
+            };
+            let tab = tab.borrow();
+
+            // new files without a path can't be reopened
+            if let Some(path) = tab.path() {
+                if is_current_tab {
+                    session.current_tab = Some(session.tabs.len());
+                }
+
+                session.tabs.push(SessionTab {
+                    path: path.to_owned(),
+                    view_state: tab.line_states.view_state(),
+                });
+            }
+        }
+
+        session.save()
+    }
+
     pub fn save_current_tab(&self, warn_user: Option<String>) -> Result<(), std::io::Error> {
         if let Some(tab) = &self.current_tab {
             self.save_tab(warn_user, &mut tab.borrow_mut())?;
@@ -153,12 +590,12 @@ impl MainGUI {
             }
         }
 
-        tab.write_hltas_to_file(self.options.locale_lang())
+        tab.write_hltas_to_file()
     }
 
     pub fn close_current_tab(&mut self) {
         let remove_index = if let Some(tab) = &self.current_tab {
-            let got_modified = tab.borrow().tab_menu_data.is_modified();
+            let got_modified = tab.borrow().is_modified();
             if got_modified
                 && self
                     .save_current_tab(Some(
@@ -177,6 +614,8 @@ impl MainGUI {
         };
 
         if let Some(remove_index) = remove_index {
+            self.save_undo_histories(Some(&*self.tabs[remove_index].borrow()));
+
             self.tabs.remove(remove_index);
             self.current_tab = None;
 
@@ -194,7 +633,7 @@ impl MainGUI {
         {
             let mut tab = self.tabs[index].borrow_mut();
 
-            if tab.tab_menu_data.is_modified()
+            if tab.is_modified()
                 && self
                     .save_tab(
                         Some(
@@ -210,6 +649,7 @@ impl MainGUI {
             }
         }
 
+        self.save_undo_histories(Some(&*self.tabs[index].borrow()));
         self.tabs.remove(index);
 
         if self.tabs.is_empty() {
@@ -219,6 +659,20 @@ impl MainGUI {
 
     pub fn show(&mut self, _: &mut bool, ui: &mut Ui) {
         self.keyboard_state.update(ui.io());
+        self.autosave();
+        self.check_external_changes();
+        self.external_change_menu
+            .show(ui, self.options.locale_lang());
+        self.compare_menu
+            .show(ui, self.options.locale_lang(), &self.tabs);
+        if let Some(hltas) = self.merge_menu.show(ui, self.options.locale_lang()) {
+            let tab = HLTASFileTab::from_hltas(self.merge_menu.result_title(), hltas);
+            self.tabs.push(Rc::new(RefCell::new(tab)));
+
+            if self.options.auto_switch_new_tab() {
+                self.tab_switch_index = Some(self.tabs.len() - 1);
+            }
+        }
 
         if let Some(current_tab) = &self.current_tab {
             self.goto_menu.show(
@@ -227,38 +681,85 @@ impl MainGUI {
                 &mut current_tab.borrow_mut(),
                 &self.keyboard_state,
             );
+            self.statistics_menu
+                .show(ui, self.options.locale_lang(), &current_tab.borrow());
+            self.problems_menu
+                .show(ui, &self.options, &mut current_tab.borrow_mut());
+
+            if self.graphics_editor {
+                self.find_menu.show(
+                    ui,
+                    self.options.locale_lang(),
+                    &mut current_tab.borrow_mut(),
+                    &self.keyboard_state,
+                );
+                self.filter_menu.show(
+                    ui,
+                    self.options.locale_lang(),
+                    &mut current_tab.borrow_mut(),
+                    &self.keyboard_state,
+                );
+                self.undo_history_menu.show(
+                    ui,
+                    self.options.locale_lang(),
+                    &mut current_tab.borrow_mut(),
+                );
+                self.bulk_edit_menu.show(
+                    ui,
+                    self.options.locale_lang(),
+                    &mut current_tab.borrow_mut(),
+                );
+                self.transform_menu.show(
+                    ui,
+                    self.options.locale_lang(),
+                    &mut current_tab.borrow_mut(),
+                );
+            }
         }
 
-        let window_border_size_token = ui.push_style_var(StyleVar::WindowBorderSize(0.0));
-        let window_min_size_token = ui.push_style_var(StyleVar::WindowMinSize([1.0, 1.0]));
-
-        ui.main_menu_bar(|| {
-            // TODO better solution
-            let new_file_key = KeyCombination::new(VirtualKeyCode::N).ctrl();
-            let open_file_key = KeyCombination::new(VirtualKeyCode::O).ctrl();
-            let save_file_key = KeyCombination::new(VirtualKeyCode::S).ctrl();
-            let close_file_key = KeyCombination::new(VirtualKeyCode::W).ctrl();
+        let palette_actions = if self.command_palette.is_opened() {
+            Action::all()
+                .into_iter()
+                .filter(|action| self.action_enabled(action))
+                .collect()
+        } else {
+            Vec::new()
+        };
+        if let Some(action) = self.command_palette.show(
+            ui,
+            self.options.locale_lang(),
+            &self.options.front_end.keymap,
+            &self.keyboard_state,
+            &palette_actions,
+        ) {
+            self.run_action(&action, ui);
+        }
 
-            if new_file_key.just_pressed(&self.keyboard_state) {
-                self.new_file();
-            }
-            if open_file_key.just_pressed(&self.keyboard_state) {
-                self.open_file_by_dialog();
+        if !self.find_menu.is_opened() {
+            for tab in &self.tabs {
+                tab.borrow_mut().line_states.clear_search_matches();
             }
-            if save_file_key.just_pressed(&self.keyboard_state) {
-                self.save_current_tab(None).unwrap_or_else(|err| {
-                    MessageDialog::new()
-                        .set_title(&self.options.locale_lang().get_string_from_id("error"))
-                        .set_type(MessageType::Error)
-                        .set_text(&err.to_string())
-                        .show_alert()
-                        .ok();
-                });
+        }
+        if !self.filter_menu.is_opened() {
+            for tab in &self.tabs {
+                tab.borrow_mut().line_states.clear_line_filter();
             }
-            if close_file_key.just_pressed(&self.keyboard_state) {
-                self.close_current_tab();
+        }
+
+        let window_border_size_token = ui.push_style_var(StyleVar::WindowBorderSize(0.0));
+        let window_min_size_token = ui.push_style_var(StyleVar::WindowMinSize([1.0, 1.0]));
+
+        if !self.option_menu.is_capturing_key() {
+            for action in Action::all() {
+                if let Some(key) = self.options.front_end.keymap.get(&action) {
+                    if key.just_pressed(&self.keyboard_state) && self.action_enabled(&action) {
+                        self.run_action(&action, ui);
+                    }
+                }
             }
+        }
 
+        ui.main_menu_bar(|| {
             ui.menu(
                 self.options.locale_lang().get_string_from_id("file-menu"),
                 || {
@@ -266,37 +767,13 @@ impl MainGUI {
                     if MenuItem::new("debug menu").build(ui) {
                         self.debug_menu_opened = !self.debug_menu_opened;
                     }
-                    if MenuItem::new(self.options.locale_lang().get_string_from_id("new-file"))
-                        .shortcut(new_file_key.to_string())
-                        .build(ui)
-                    {
-                        self.new_file();
-                    }
-                    if MenuItem::new(self.options.locale_lang().get_string_from_id("open-file"))
-                        .shortcut(open_file_key.to_string())
-                        .build(ui)
-                    {
-                        self.open_file_by_dialog();
-                    }
-                    if MenuItem::new(self.options.locale_lang().get_string_from_id("save-file"))
-                        .shortcut(save_file_key.to_string())
-                        .build(ui)
-                    {
-                        self.save_current_tab(None).unwrap_or_else(|err| {
-                            MessageDialog::new()
-                                .set_title(&self.options.locale_lang().get_string_from_id("error"))
-                                .set_type(MessageType::Error)
-                                .set_text(&err.to_string())
-                                .show_alert()
-                                .ok();
-                        });
-                    }
-                    if MenuItem::new(self.options.locale_lang().get_string_from_id("close-file"))
-                        .shortcut(close_file_key.to_string())
-                        .build(ui)
-                    {
-                        self.close_current_tab();
-                    }
+                    self.action_menu_item(ui, &Action::NewFile);
+                    self.action_menu_item(ui, &Action::OpenFile);
+                    self.action_menu_item(ui, &Action::SaveFile);
+                    self.action_menu_item(ui, &Action::CloseFile);
+                    self.action_menu_item(ui, &Action::ExportCsv);
+                    self.action_menu_item(ui, &Action::ExportJson);
+                    self.action_menu_item(ui, &Action::Import);
 
                     ui.menu(
                         self.options
@@ -319,249 +796,54 @@ impl MainGUI {
                     );
                 },
             );
-            let undo_key = KeyCombination::new(VirtualKeyCode::Z).ctrl();
-            let redo_key = KeyCombination::new(VirtualKeyCode::Y).ctrl();
-            let cut_key = KeyCombination::new(VirtualKeyCode::X).ctrl();
-            let copy_key = KeyCombination::new(VirtualKeyCode::C).ctrl();
-            let paste_key = KeyCombination::new(VirtualKeyCode::V).ctrl();
-            let select_all_key = KeyCombination::new(VirtualKeyCode::A).ctrl();
-
-            let paste = || {
-                if let Some(current_tab) = &self.current_tab {
-                    if let Some(clipboard) = ui.clipboard_text() {
-                        if let Some(clipboard) = str_to_lines(&clipboard) {
-                            let mut current_tab = current_tab.borrow_mut();
-
-                            if let Some(last_selected_index) = current_tab
-                                .tab_menu_data
-                                .selected_indexes_collection()
-                                .last()
-                            {
-                                current_tab.undo_redo_handler.add_lines(
-                                    clipboard
-                                        .iter()
-                                        .enumerate()
-                                        .map(|(i, _)| i + *last_selected_index)
-                                        .collect::<Vec<_>>(),
-                                );
-
-                                for (i, line) in clipboard.iter().enumerate() {
-                                    current_tab
-                                        .insert_line(last_selected_index + i, line.to_owned());
-                                }
-                            } else if current_tab.hltas_lines().is_empty() {
-                                current_tab.undo_redo_handler.add_lines(
-                                    clipboard
-                                        .iter()
-                                        .enumerate()
-                                        .map(|(i, _)| i)
-                                        .collect::<Vec<_>>(),
-                                );
-
-                                for line in clipboard {
-                                    current_tab.push_line(line);
-                                }
-                            }
-                        }
-                    }
-                }
-            };
-            let cut = || {
-                if let Some(current_tab) = &self.current_tab {
-                    let selected_lines = current_tab
-                        .borrow()
-                        .tab_menu_data
-                        .selected_indexes()
-                        .iter()
-                        .enumerate()
-                        .filter_map(|(i, is_selected)| {
-                            if *is_selected {
-                                Some((i, current_tab.borrow().hltas_lines()[i].to_owned()))
-                            } else {
-                                None
-                            }
-                        })
-                        .collect::<Vec<_>>();
-                    current_tab
-                        .borrow_mut()
-                        .undo_redo_handler
-                        .delete_lines(selected_lines);
-
-                    ui.set_clipboard_text(lines_to_str(
-                        current_tab
-                            .borrow()
-                            .get_selected_lines()
-                            .into_iter()
-                            .cloned()
-                            .collect::<Vec<_>>(),
-                    ));
-
-                    current_tab.borrow_mut().remove_selected_lines();
-                }
-            };
-            let undo = || {
-                if let Some(current_tab) = &self.current_tab {
-                    current_tab.borrow_mut().undo_hltas();
-                }
-            };
-            let redo = || {
-                if let Some(current_tab) = &self.current_tab {
-                    current_tab.borrow_mut().redo_hltas();
-                }
-            };
-
-            if copy_key.just_pressed(&self.keyboard_state) {
-                self.copy_selection(ui);
-            }
-            if paste_key.just_pressed(&self.keyboard_state) {
-                paste();
-            }
-            if select_all_key.just_pressed(&self.keyboard_state) {
-                self.select_all();
-            }
-            if cut_key.just_pressed(&self.keyboard_state) {
-                cut();
-            }
-            if undo_key.just_pressed(&self.keyboard_state) {
-                undo();
-            }
-            if redo_key.just_pressed(&self.keyboard_state) {
-                redo();
-            }
-
             ui.menu(
                 self.options.locale_lang().get_string_from_id("edit-menu"),
                 || {
-                    if MenuItem::new(self.options.locale_lang().get_string_from_id("undo"))
-                        .shortcut(undo_key.to_string())
-                        .build(ui)
-                    {
-                        undo();
-                    }
-                    if MenuItem::new(self.options.locale_lang().get_string_from_id("redo"))
-                        .shortcut(redo_key.to_string())
-                        .build(ui)
-                    {
-                        redo();
-                    }
+                    self.action_menu_item(ui, &Action::Undo);
+                    self.action_menu_item(ui, &Action::Redo);
+                    self.action_menu_item(ui, &Action::UndoHistory);
                     ui.separator();
-                    if MenuItem::new(self.options.locale_lang().get_string_from_id("cut"))
-                        .shortcut(cut_key.to_string())
-                        .build(ui)
-                    {
-                        cut();
-                    }
-                    if MenuItem::new(self.options.locale_lang().get_string_from_id("copy"))
-                        .shortcut(copy_key.to_string())
-                        .build(ui)
-                    {
-                        self.copy_selection(ui);
-                    }
-                    if MenuItem::new(self.options.locale_lang().get_string_from_id("paste"))
-                        .shortcut(paste_key.to_string())
-                        .build(ui)
-                    {
-                        paste();
-                    }
+                    self.action_menu_item(ui, &Action::Cut);
+                    self.action_menu_item(ui, &Action::Copy);
+                    self.action_menu_item(ui, &Action::Paste);
                     ui.separator();
-                    if MenuItem::new(self.options.locale_lang().get_string_from_id("select-all"))
-                        .shortcut(select_all_key.to_string())
-                        .build(ui)
-                    {
-                        self.select_all();
-                    }
+                    self.action_menu_item(ui, &Action::SelectAll);
+                    self.action_menu_item(ui, &Action::BulkEdit);
+                    self.action_menu_item(ui, &Action::TransformSelection);
                 },
             );
-
-            let goto_key = KeyCombination::new(VirtualKeyCode::G).ctrl();
-
-            if goto_key.just_pressed(&self.keyboard_state) && self.current_tab.is_some() {
-                self.goto_menu.open();
-            }
-
-            // TODO find menu
             ui.menu(
                 self.options.locale_lang().get_string_from_id("search-menu"),
                 || {
-                    if MenuItem::new(self.options.locale_lang().get_string_from_id("goto-line"))
-                        .build(ui)
-                        && self.current_tab.is_some()
-                    {
-                        self.goto_menu.open();
-                    }
+                    self.action_menu_item(ui, &Action::GotoLine);
+                    self.action_menu_item(ui, &Action::FindReplace);
+                    self.action_menu_item(ui, &Action::FilterLines);
                 },
             );
             ui.menu(
                 self.options.locale_lang().get_string_from_id("tools-menu"),
                 || {
-                    if MenuItem::new(
-                        self.options
-                            .locale_lang()
-                            .get_string_from_id("no-dupe-framebulk"),
-                    )
-                    .build(ui)
-                    {
-                        if let Some(current_tab) = &self.current_tab {
-                            current_tab
-                                .borrow_mut()
-                                .hltas_cleaner_fn(cleaners::no_dupe_framebulks);
-                        }
-                    }
-                    if MenuItem::new(self.options.locale_lang().get_string_from_id("no-comments"))
-                        .build(ui)
-                    {
-                        if let Some(current_tab) = &self.current_tab {
-                            current_tab
-                                .borrow_mut()
-                                .hltas_cleaner_fn(cleaners::remove_comments);
-                        }
+                    self.action_menu_item(ui, &Action::CommandPalette);
+                    ui.separator();
+                    self.action_menu_item(ui, &Action::Statistics);
+                    self.action_menu_item(ui, &Action::Problems);
+                    self.action_menu_item(ui, &Action::Compare);
+                    self.action_menu_item(ui, &Action::Merge);
+                    ui.separator();
+                    for cleaner in Cleaner::ALL {
+                        self.action_menu_item(ui, &Action::Cleaner(*cleaner));
                     }
                 },
             );
-
-            let toggle_simple_view_key = KeyCombination::new(VirtualKeyCode::S).ctrl().shift();
-
-            if toggle_simple_view_key.just_pressed(&self.keyboard_state) {
-                if let Some(current_tab) = &self.current_tab {
-                    current_tab.borrow_mut().tab_menu_data.toggle_simple_view();
-                }
-            }
-
             ui.menu(
                 self.options
                     .locale_lang()
                     .get_string_from_id("options-menu"),
                 || {
-                    if MenuItem::new(
-                        self.options
-                            .locale_lang()
-                            .get_string_from_id("toggle-graphics-editor"),
-                    )
-                    .build(ui)
-                    {
-                        self.graphics_editor = !self.graphics_editor;
-                    }
-                    if MenuItem::new(
-                        self.options
-                            .locale_lang()
-                            .get_string_from_id("open-options-menu"),
-                    )
-                    .build(ui)
-                    {
-                        self.option_menu.open();
-                    }
-                    if MenuItem::new(
-                        self.options
-                            .locale_lang()
-                            .get_string_from_id("toggle-simple-view"),
-                    )
-                    .shortcut(toggle_simple_view_key.to_string())
-                    .build(ui)
-                    {
-                        if let Some(current_tab) = &self.current_tab {
-                            current_tab.borrow_mut().tab_menu_data.toggle_simple_view();
-                        }
-                    }
+                    self.action_menu_item(ui, &Action::ToggleGraphicsEditor);
+                    self.action_menu_item(ui, &Action::OpenOptions);
+                    self.action_menu_item(ui, &Action::ToggleSimpleView);
+                    self.action_menu_item(ui, &Action::ToggleTimingGutter);
                 },
             );
         });
@@ -610,7 +892,7 @@ impl MainGUI {
                                 self.current_tab = Some(Rc::clone(tab));
                             }
 
-                            if tab.borrow().tab_menu_data.is_modified() {
+                            if tab.borrow().is_modified() {
                                 flags = flags.union(TabItemFlags::UNSAVED_DOCUMENT);
                             }
 
@@ -674,8 +956,8 @@ impl MainGUI {
                             &self.keyboard_state,
                         );
                     }
-                } else {
-                    // show_text_editor(ui);
+                } else if let Some(tab) = &self.current_tab {
+                    show_text_editor(ui, &mut tab.borrow_mut(), &self.options);
                 }
             });
 
@@ -687,6 +969,7 @@ impl MainGUI {
             let mut options_menu_opened = self.option_menu.is_opened();
             let options = &mut self.options;
             let option_menu = &mut self.option_menu;
+            let keyboard_state = &self.keyboard_state;
 
             if options_menu_opened {
                 Window::new("options##options_menu")
@@ -709,7 +992,7 @@ impl MainGUI {
                     .position_pivot([0.5, 0.5])
                     .size([500.0, 400.0], Condition::Always)
                     .build(ui, || {
-                        option_menu.show(ui, options);
+                        option_menu.show(ui, options, keyboard_state);
                     });
             }
 
@@ -741,40 +1024,212 @@ impl MainGUI {
             }
         };
 
-        Self {
+        let mut main_gui = Self {
             options,
             ..main_gui
+        };
+        main_gui.restore_session();
+        main_gui.restore_recovery_tabs();
+
+        main_gui
+    }
+
+    /// Switches between the graphics editor and the text editor
+    ///
+    /// Switching back to the graphics editor fails if any text edited tab can't be parsed,
+    /// the tab with the error gets selected in that case
+    pub fn toggle_graphics_editor(&mut self) {
+        if self.graphics_editor {
+            self.graphics_editor = false;
+            return;
+        }
+
+        for (i, tab) in self.tabs.iter().enumerate() {
+            if tab.borrow_mut().close_text_editor().is_err() {
+                self.tab_switch_index = Some(i);
+                return;
+            }
+        }
+
+        self.graphics_editor = true;
+    }
+
+    /// The current tab if its lines have the keyboard, not a text field of a menu or a line
+    fn focused_lines_tab(&self) -> Option<&Rc<RefCell<HLTASFileTab>>> {
+        let current_tab = self.current_tab.as_ref()?;
+
+        if !self.goto_menu.is_opened()
+            && !self.find_menu.is_opened()
+            && !self.filter_menu.is_opened()
+            && !self.option_menu.is_opened()
+            && !self.command_palette.is_opened()
+            && self.graphics_editor
+            && !current_tab.borrow().line_states.is_modifying_line()
+        {
+            Some(current_tab)
+        } else {
+            None
         }
     }
 
     pub fn copy_selection(&self, ui: &Ui) {
-        if let Some(current_tab) = &self.current_tab {
-            if !self.goto_menu.is_opened()
-                && !self.option_menu.is_opened()
-                && self.graphics_editor
-                && !current_tab.borrow().tab_menu_data.is_modifying_line()
-            {
-                ui.set_clipboard_text(lines_to_str(
+        if let Some(current_tab) = self.focused_lines_tab() {
+            ui.set_clipboard_text(current_tab.borrow().copy_selected_lines());
+        }
+    }
+
+    fn cut_selection(&self, ui: &Ui) {
+        if let Some(current_tab) = self.focused_lines_tab() {
+            ui.set_clipboard_text(current_tab.borrow_mut().cut_selected_lines());
+        }
+    }
+
+    fn paste(&self, ui: &Ui) {
+        if let Some(current_tab) = self.focused_lines_tab() {
+            if let Some(clipboard) = ui.clipboard_text() {
+                current_tab.borrow_mut().paste_lines(&clipboard);
+            }
+        }
+    }
+
+    fn action_menu_item(&mut self, ui: &Ui, action: &Action) {
+        if MenuItem::new(action.label(self.options.locale_lang()))
+            .shortcut(self.options.front_end.keymap.shortcut_text(action))
+            .enabled(self.action_enabled(action))
+            .build(ui)
+        {
+            self.run_action(action, ui);
+        }
+    }
+
+    fn action_enabled(&self, action: &Action) -> bool {
+        let has_tab = self.current_tab.is_some();
+
+        match action {
+            // text editor has its own undo / redo and clipboard
+            Action::Undo
+            | Action::Redo
+            | Action::UndoHistory
+            | Action::Cut
+            | Action::Copy
+            | Action::Paste
+            | Action::SelectAll
+            | Action::BulkEdit
+            | Action::TransformSelection
+            | Action::FindReplace
+            | Action::FilterLines
+            | Action::Cleaner(_)
+            | Action::NewLine(_) => has_tab && self.graphics_editor,
+            Action::SaveFile
+            | Action::CloseFile
+            | Action::ExportCsv
+            | Action::ExportJson
+            | Action::GotoLine
+            | Action::Statistics
+            | Action::Problems
+            | Action::Compare
+            | Action::ToggleSimpleView
+            | Action::ToggleTimingGutter => has_tab,
+            Action::NewFile
+            | Action::OpenFile
+            | Action::Import
+            | Action::ToggleGraphicsEditor
+            | Action::OpenOptions
+            | Action::OptionsCategory(_)
+            | Action::Merge
+            | Action::CommandPalette => true,
+        }
+    }
+
+    /// Runs the action of a menu item, a shortcut or the command palette
+    fn run_action(&mut self, action: &Action, ui: &Ui) {
+        match action {
+            Action::NewFile => self.new_file(),
+            Action::OpenFile => self.open_file_by_dialog(),
+            Action::SaveFile => {
+                if let Err(err) = self.save_current_tab(None) {
+                    self.show_error(&err.to_string());
+                }
+            }
+            Action::CloseFile => self.close_current_tab(),
+            Action::ExportCsv | Action::ExportJson => {
+                if let Err(err) = self.export_current_tab(*action == Action::ExportJson) {
+                    self.show_error(&err.to_string());
+                }
+            }
+            Action::Import => {
+                if let Err(err) = self.import_file_by_dialog() {
+                    self.show_error(&err.to_string());
+                }
+            }
+            Action::Undo => {
+                if let Some(current_tab) = self.focused_lines_tab() {
+                    current_tab.borrow_mut().undo_hltas();
+                }
+            }
+            Action::Redo => {
+                if let Some(current_tab) = self.focused_lines_tab() {
+                    current_tab.borrow_mut().redo_hltas();
+                }
+            }
+            Action::UndoHistory => self.undo_history_menu.open(),
+            Action::Cut => self.cut_selection(ui),
+            Action::Copy => self.copy_selection(ui),
+            Action::Paste => self.paste(ui),
+            Action::SelectAll => self.select_all(),
+            Action::BulkEdit => self.bulk_edit_menu.open(),
+            Action::TransformSelection => self.transform_menu.open(),
+            Action::GotoLine => self.goto_menu.open(),
+            Action::FindReplace => self.find_menu.open(),
+            Action::FilterLines => self.filter_menu.open(),
+            Action::Cleaner(cleaner) => {
+                if let Some(current_tab) = &self.current_tab {
                     current_tab
-                        .borrow()
-                        .get_selected_lines()
-                        .into_iter()
-                        .cloned()
-                        .collect::<Vec<_>>(),
-                ));
+                        .borrow_mut()
+                        .hltas_cleaner_fn(cleaner.cleaner_fn());
+                }
+            }
+            Action::NewLine(kind) => {
+                if let Some(current_tab) = &self.current_tab {
+                    let mut current_tab = current_tab.borrow_mut();
+                    // goes after the selected lines
+                    let index = current_tab
+                        .line_states
+                        .selected_indexes_collection()
+                        .last()
+                        .map(|index| index + 1);
+
+                    insert_new_line(&mut current_tab, &self.options, *kind, index);
+                }
+            }
+            Action::Statistics => self.statistics_menu.open(),
+            Action::Problems => self.problems_menu.open(),
+            Action::Compare => {
+                if let Some(current_tab) = &self.current_tab {
+                    self.compare_menu.open(current_tab);
+                }
             }
+            Action::Merge => self.merge_menu.open(),
+            Action::ToggleGraphicsEditor => self.toggle_graphics_editor(),
+            Action::ToggleSimpleView => {
+                if let Some(current_tab) = &self.current_tab {
+                    current_tab.borrow_mut().line_states.toggle_simple_view();
+                }
+            }
+            Action::ToggleTimingGutter => {
+                if let Some(current_tab) = &self.current_tab {
+                    current_tab.borrow_mut().line_states.toggle_timing_gutter();
+                }
+            }
+            Action::OpenOptions => self.option_menu.open(),
+            Action::OptionsCategory(category) => self.option_menu.open_category(*category),
+            Action::CommandPalette => self.command_palette.open(),
         }
     }
 
     pub fn select_all(&self) {
-        if let Some(current_tab) = &self.current_tab {
-            if !self.goto_menu.is_opened()
-                && !self.option_menu.is_opened()
-                && self.graphics_editor
-                && !current_tab.borrow().tab_menu_data.is_modifying_line()
-            {
-                current_tab.borrow_mut().select_all_lines();
-            }
+        if let Some(current_tab) = self.focused_lines_tab() {
+            current_tab.borrow_mut().select_all_lines();
         }
     }
 }
@@ -790,6 +1245,20 @@ impl Default for MainGUI {
 
         Self {
             goto_menu: GotoMenu::default(),
+            find_menu: FindMenu::default(),
+            filter_menu: FilterMenu::default(),
+            undo_history_menu: UndoHistoryMenu::default(),
+            last_autosave: Instant::now(),
+            kept_recovery_tabs: Vec::new(),
+            last_external_change_check: Instant::now(),
+            external_change_menu: ExternalChangeMenu::default(),
+            command_palette: CommandPalette::default(),
+            statistics_menu: StatisticsMenu::default(),
+            problems_menu: ProblemsMenu::default(),
+            bulk_edit_menu: BulkEditMenu::default(),
+            transform_menu: TransformMenu::default(),
+            compare_menu: CompareMenu::default(),
+            merge_menu: MergeMenu::default(),
             tabs,
             current_tab,
             tab_switch_index: None,
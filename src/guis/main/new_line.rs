@@ -0,0 +1,175 @@
+use std::num::NonZeroU32;
+
+use hltas::types::{Button, Buttons, Change, ChangeTarget, Line, VectorialStrafingConstraints};
+
+use crate::helpers::hltas::empty_framebulk;
+
+use super::{option_menu::AppOptions, tab::HLTASFileTab};
+
+/// Line types that can be created from the new line menu
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum NewLineKind {
+    Framebulk,
+    EmptyFramebulk,
+    Save,
+    SharedSeed,
+    Buttons,
+    LgagstMinSpeed,
+    NonSharedSeed,
+    Comment,
+    VectorialStrafing,
+    VectorialStrafingConstraints,
+    Change,
+    TargetYawOverride,
+}
+
+impl NewLineKind {
+    pub const ALL: &'static [NewLineKind] = &[
+        NewLineKind::Framebulk,
+        NewLineKind::EmptyFramebulk,
+        NewLineKind::Save,
+        NewLineKind::SharedSeed,
+        NewLineKind::Buttons,
+        NewLineKind::LgagstMinSpeed,
+        NewLineKind::NonSharedSeed,
+        NewLineKind::Comment,
+        NewLineKind::VectorialStrafing,
+        NewLineKind::VectorialStrafingConstraints,
+        NewLineKind::Change,
+        NewLineKind::TargetYawOverride,
+    ];
+
+    pub fn id(&self) -> &'static str {
+        match self {
+            NewLineKind::Framebulk => "framebulk",
+            NewLineKind::EmptyFramebulk => "empty-framebulk",
+            NewLineKind::Save => "save",
+            NewLineKind::SharedSeed => "shared-seed",
+            NewLineKind::Buttons => "buttons",
+            NewLineKind::LgagstMinSpeed => "lgagst-min-speed",
+            NewLineKind::NonSharedSeed => "non-shared-seed",
+            NewLineKind::Comment => "comment",
+            NewLineKind::VectorialStrafing => "vectorial-strafing",
+            NewLineKind::VectorialStrafingConstraints => "vectorial-strafing-constraints",
+            NewLineKind::Change => "change",
+            NewLineKind::TargetYawOverride => "target-yaw-override",
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            NewLineKind::Framebulk => "framebulk",
+            NewLineKind::EmptyFramebulk => "empty framebulk",
+            NewLineKind::Save => "save",
+            NewLineKind::SharedSeed => "shared seed",
+            NewLineKind::Buttons => "buttons",
+            NewLineKind::LgagstMinSpeed => "lgagst min spd",
+            NewLineKind::NonSharedSeed => "non-shared seed",
+            NewLineKind::Comment => "comment",
+            NewLineKind::VectorialStrafing => "vectorial strafing",
+            NewLineKind::VectorialStrafingConstraints => "vectorial strafing constraints",
+            NewLineKind::Change => "change",
+            NewLineKind::TargetYawOverride => "target yaw override",
+        }
+    }
+
+    /// Creates the line, some line types copy values from `previous_lines`
+    pub fn create(&self, options: &AppOptions, previous_lines: &[Line]) -> Line {
+        let new_framebulk_with_frametime_framecount = || {
+            let default_frametime = &options.default_frametime().to_string();
+
+            let frametime = if let Some(Line::FrameBulk(framebulk)) = previous_lines
+                .iter()
+                .rev()
+                .find(|line| matches!(line, Line::FrameBulk(..)))
+            {
+                &framebulk.frame_time
+            } else {
+                default_frametime
+            };
+
+            let frame_count = NonZeroU32::new(1).unwrap();
+
+            Line::FrameBulk(empty_framebulk(frametime, frame_count))
+        };
+
+        // TODO option for what to choose here
+        match self {
+            NewLineKind::Framebulk => {
+                if options.copy_previous_framebulk() {
+                    if let Some(previous_framebulk) = previous_lines
+                        .iter()
+                        .rev()
+                        .find(|line| matches!(line, Line::FrameBulk(..)))
+                    {
+                        return previous_framebulk.to_owned();
+                    }
+                }
+
+                new_framebulk_with_frametime_framecount()
+            }
+            NewLineKind::EmptyFramebulk => new_framebulk_with_frametime_framecount(),
+            NewLineKind::Save => Line::Save(options.save_buffer_name().to_string()),
+            NewLineKind::SharedSeed => Line::SharedSeed(0),
+            NewLineKind::Buttons => Line::Buttons(Buttons::Set {
+                air_left: Button::Left,
+                air_right: Button::Right,
+                ground_left: Button::Left,
+                ground_right: Button::Right,
+            }),
+            NewLineKind::LgagstMinSpeed => {
+                if options.lgagst_min_speed_grab_prev() {
+                    if let Some(Line::LGAGSTMinSpeed(lgagst_min_spd)) = previous_lines
+                        .iter()
+                        .rev()
+                        .find(|line| matches!(line, Line::LGAGSTMinSpeed(_)))
+                    {
+                        return Line::LGAGSTMinSpeed(*lgagst_min_spd);
+                    }
+                }
+
+                Line::LGAGSTMinSpeed(options.lgagst_min_speed())
+            }
+            NewLineKind::NonSharedSeed => Line::Reset { non_shared_seed: 0 },
+            NewLineKind::Comment => Line::Comment(options.default_comment().to_string()),
+            NewLineKind::VectorialStrafing => {
+                if let Some(Line::VectorialStrafing(vectorial_strafing)) = previous_lines
+                    .iter()
+                    .rev()
+                    .find(|line| matches!(line, Line::VectorialStrafing(_)))
+                {
+                    Line::VectorialStrafing(!*vectorial_strafing)
+                } else {
+                    Line::VectorialStrafing(true)
+                }
+            }
+            NewLineKind::VectorialStrafingConstraints => Line::VectorialStrafingConstraints(
+                VectorialStrafingConstraints::VelocityYawLocking { tolerance: 0.0 },
+            ),
+            // TODO think about this one
+            NewLineKind::Change => Line::Change(Change {
+                target: ChangeTarget::Yaw,
+                final_value: 0.0,
+                over: 0.4,
+            }),
+            NewLineKind::TargetYawOverride => Line::TargetYawOverride(vec![0.0]),
+        }
+    }
+}
+
+/// Inserts a new line at `index` or at the end of the lines, with an undo point
+pub fn insert_new_line(
+    tab: &mut HLTASFileTab,
+    options: &AppOptions,
+    kind: NewLineKind,
+    index: Option<usize>,
+) {
+    let index = match index {
+        Some(index) if index < tab.hltas_lines().len() => index,
+        _ => tab.hltas_lines().len(),
+    };
+    let line = kind.create(options, &tab.hltas_lines()[..index]);
+
+    tab.insert_line(index, line);
+    tab.undo_redo_handler.add_lines(vec![index]);
+}
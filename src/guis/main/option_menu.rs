@@ -1,213 +1,47 @@
-use std::{
-    env, fs,
-    num::{IntErrorKind, ParseIntError},
-    path::PathBuf,
-};
+use std::num::{IntErrorKind, ParseIntError};
 
 use fluent_templates::Loader;
 use hltas::types::LeaveGroundActionSpeed;
-use home::home_dir;
-use imgui::{ColorEdit, ComboBox, Drag, InputFloat, InputText, Selectable, StyleColor, Ui};
+use hltas_editor_core::options::LgagstOption;
+use imgui::{
+    ChildWindow, ColorEdit, ComboBox, Drag, InputFloat, InputText, Selectable, StyleColor, Ui,
+};
 use serde::{Deserialize, Serialize};
+use winit::event::VirtualKeyCode;
 
 use crate::{
-    helpers::{
-        hltas::frametime, imgui::list_box_enum::show_list_box_enum, locale::locale_lang::LocaleLang,
-    },
+    helpers::{hltas::frametime, imgui::list_box_enum::show_list_box_enum},
     locale::LOCALES,
 };
 
-use super::zero_ms_editor::show_zero_ms_editor;
-
-#[derive(Clone, Serialize, Deserialize)]
-pub struct AppOptions {
-    copy_previous_framebulk: bool,
-    jump_lgagst_option: LgagstOption,
-    ducktap_lgagst_option: LgagstOption,
-    recent_path_size: usize,
-    #[serde(skip_serializing, skip_deserializing)]
-    locale_lang: LocaleLang,
-    auto_switch_new_tab: bool,
-    default_comment: String,
-    comment_colour: [f32; 4],
-    lgagst_min_speed: f32,
-    lgagst_min_speed_grab_prev: bool,
-    default_0ms_frametime: f32,
-    default_frametime: f32,
-    save_buffer_name: String,
-    zero_ms_if_property_enabled: bool,
-}
-
-impl AppOptions {
-    pub fn get_save_dir() -> Result<PathBuf, std::io::Error> {
-        let mut save_dir = match home_dir() {
-            Some(home_dir) => home_dir,
-            None => env::current_dir()?,
-        };
-
-        save_dir.push("hltas-editor");
-
-        if !save_dir.exists() {
-            fs::create_dir(&save_dir)?;
-        }
-
-        Ok(save_dir)
-    }
-
-    pub fn option_path() -> Result<PathBuf, std::io::Error> {
-        Ok(Self::get_save_dir()?.join("options.json"))
-    }
-
-    pub fn save_options(&self) -> Result<(), std::io::Error> {
-        let option_data = serde_json::to_string(self).unwrap();
-        fs::write(Self::option_path()?, &option_data)?;
-        Ok(())
-    }
-
-    pub fn load_options() -> Result<Self, Box<dyn std::error::Error>> {
-        let option_data = fs::read_to_string(Self::option_path()?)?;
-        Ok(serde_json::from_str(&option_data)?)
-    }
-
-    /// Get a reference to the app options's lgagst min speed.
-    pub fn lgagst_min_speed(&self) -> f32 {
-        self.lgagst_min_speed
-    }
-
-    /// Get a reference to the app options's lgagst min speed grab prev.
-    pub fn lgagst_min_speed_grab_prev(&self) -> bool {
-        self.lgagst_min_speed_grab_prev
-    }
-
-    /// Get a reference to the app options's copy previous framebulk.
-    pub fn copy_previous_framebulk(&self) -> bool {
-        self.copy_previous_framebulk
-    }
-
-    /// Get a reference to the app options's default 0ms frametime.
-    pub fn default_0ms_frametime(&self) -> f32 {
-        self.default_0ms_frametime
-    }
-
-    /// Get a reference to the app options's default frametime.
-    pub fn default_frametime(&self) -> f32 {
-        self.default_frametime
-    }
-
-    /// Get a reference to the app options's save buffer name.
-    pub fn save_buffer_name(&self) -> &str {
-        self.save_buffer_name.as_ref()
-    }
-
-    /// Get a reference to the app options's zero ms if property enabled.
-    pub fn zero_ms_if_property_enabled(&self) -> bool {
-        self.zero_ms_if_property_enabled
-    }
-}
-
-impl AppOptions {
-    /// Get a reference to the app options's jump lgagst option.
-    pub fn jump_lgagst_option(&self) -> &LgagstOption {
-        &self.jump_lgagst_option
-    }
-
-    /// Get a reference to the app options's ducktap lgagst option.
-    pub fn ducktap_lgagst_option(&self) -> &LgagstOption {
-        &self.ducktap_lgagst_option
-    }
-
-    /// Get a reference to the app options's recent path size.
-    pub fn recent_path_size(&self) -> usize {
-        self.recent_path_size
-    }
-
-    /// Get a reference to the app options's locale lang.
-    pub fn locale_lang(&self) -> &LocaleLang {
-        &self.locale_lang
-    }
-
-    /// Get a reference to the app options's auto switch new tab.
-    pub fn auto_switch_new_tab(&self) -> bool {
-        self.auto_switch_new_tab
-    }
-
-    /// Get a reference to the app options's default comment.
-    pub fn default_comment(&self) -> &str {
-        &self.default_comment
-    }
-
-    /// Get a reference to the app options's comment colour.
-    pub fn comment_colour(&self) -> [f32; 4] {
-        self.comment_colour
-    }
-}
-
-impl Default for AppOptions {
-    fn default() -> Self {
-        Self {
-            copy_previous_framebulk: true,
-            jump_lgagst_option: Default::default(),
-            ducktap_lgagst_option: Default::default(),
-            recent_path_size: 20,
-            locale_lang: LocaleLang::new(None),
-            auto_switch_new_tab: true,
-            default_comment: "".to_string(),
-            comment_colour: [0.0, 1.0, 0.0, 1.0],
-            lgagst_min_speed: 30.0,
-            lgagst_min_speed_grab_prev: true,
-            default_0ms_frametime: 0.0000000001,
-            default_frametime: frametime::MAX_STRAFE,
-            save_buffer_name: "buffer".to_string(),
-            zero_ms_if_property_enabled: true,
-        }
-    }
-}
-
-#[derive(Serialize, Deserialize)]
-#[serde(remote = "LeaveGroundActionSpeed")]
-enum LeaveGroundActionSpeedDef {
-    Any,
-    Optimal,
-    OptimalWithFullMaxspeed,
-}
+use super::{
+    actions::Action, key_combination::KeyCombination, key_state::KeyboardState, keymap::Keymap,
+    zero_ms_editor::show_zero_ms_editor,
+};
 
-#[derive(Clone, Serialize, Deserialize)]
-pub struct LgagstOption {
-    #[serde(with = "LeaveGroundActionSpeedDef")]
-    default_selection: LeaveGroundActionSpeed,
+/// Options saved with the core options that only the gui uses
+#[derive(Clone, Default, Serialize, Deserialize)]
+pub struct GuiOptions {
+    #[serde(default)]
+    pub keymap: Keymap,
 }
 
-impl Default for LgagstOption {
-    fn default() -> Self {
-        Self {
-            default_selection: LeaveGroundActionSpeed::Optimal,
-        }
-    }
-}
-
-impl LgagstOption {
-    fn show_ui(&mut self, ui: &Ui, id: &str) -> bool {
-        let lgagst_option_changed = show_list_box_enum(
-            ui,
-            &mut self.default_selection,
-            vec![
-                ("no lgagst", LeaveGroundActionSpeed::Any),
-                ("lgagst", LeaveGroundActionSpeed::Optimal),
-                (
-                    "lgagst with max spd",
-                    LeaveGroundActionSpeed::OptimalWithFullMaxspeed,
-                ),
-            ],
-            &format!("lgagst_option_lgagst_selection{}", id),
-        );
-
-        lgagst_option_changed
-    }
-
-    /// Get a reference to the lgagst option's default selection.
-    pub fn default_selection(&self) -> LeaveGroundActionSpeed {
-        self.default_selection
-    }
+pub type AppOptions = hltas_editor_core::options::AppOptions<GuiOptions>;
+
+fn show_lgagst_option_ui(ui: &Ui, lgagst_option: &mut LgagstOption, id: &str) -> bool {
+    show_list_box_enum(
+        ui,
+        &mut lgagst_option.default_selection,
+        vec![
+            ("no lgagst", LeaveGroundActionSpeed::Any),
+            ("lgagst", LeaveGroundActionSpeed::Optimal),
+            (
+                "lgagst with max spd",
+                LeaveGroundActionSpeed::OptimalWithFullMaxspeed,
+            ),
+        ],
+        &format!("lgagst_option_lgagst_selection{}", id),
+    )
 }
 
 pub struct OptionMenu {
@@ -215,6 +49,8 @@ pub struct OptionMenu {
     opened: bool,
     option_menu_before: Option<AppOptions>,
     modified: bool,
+    /// Action waiting for a key combination to be pressed
+    capturing_key: Option<Action>,
 }
 
 impl Default for OptionMenu {
@@ -224,6 +60,7 @@ impl Default for OptionMenu {
             option_menu_before: None,
             modified: false,
             opened: false,
+            capturing_key: None,
         }
     }
 }
@@ -239,6 +76,7 @@ impl OptionMenu {
         }
         self.modified = false;
         self.option_menu_before = None;
+        self.capturing_key = None;
     }
 
     pub fn open(&mut self) {
@@ -255,22 +93,26 @@ impl OptionMenu {
 
     pub fn close(&mut self) {
         self.opened = false;
+        self.capturing_key = None;
     }
 
-    pub fn show(&mut self, ui: &Ui, app_options: &mut AppOptions) {
+    pub fn open_category(&mut self, category: Category) {
+        self.open();
+        self.category_selection = category;
+    }
+
+    /// Shortcuts shouldn't do anything while a key combination is being bound
+    pub fn is_capturing_key(&self) -> bool {
+        self.opened && self.capturing_key.is_some()
+    }
+
+    pub fn show(&mut self, ui: &Ui, app_options: &mut AppOptions, keyboard_state: &KeyboardState) {
         // back up option before modifying
         if self.option_menu_before.is_none() {
             self.option_menu_before = Some(app_options.clone());
         }
 
-        let button_label_pairs = vec![
-            ("menu options", Category::MenuOption),
-            ("line options", Category::LineOption),
-            ("properties options", Category::PropertiesOption),
-            ("language", Category::Language),
-        ];
-
-        for (i, (label, button_enum)) in button_label_pairs.iter().enumerate() {
+        for (i, button_enum) in Category::ALL.iter().enumerate() {
             let menu_tab_inactive_color =
                 if *button_enum != self.category_selection {
                     Some(ui.push_style_color(
@@ -281,15 +123,16 @@ impl OptionMenu {
                     None
                 };
 
-            if ui.button(label) {
+            if ui.button(button_enum.label()) {
                 self.category_selection = *button_enum;
+                self.capturing_key = None;
             }
 
             if let Some(menu_tab_inactive_color) = menu_tab_inactive_color {
                 menu_tab_inactive_color.pop();
             }
 
-            if i != button_label_pairs.len() - 1 {
+            if i != Category::ALL.len() - 1 {
                 ui.same_line();
             }
         }
@@ -346,6 +189,89 @@ impl OptionMenu {
 
                 changed_using_system_lang || option_menu_changed
             }
+            Category::KeyBindings => {
+                let mut key_bindings_changed = false;
+
+                if let Some(capturing_action) = self.capturing_key {
+                    if keyboard_state.just_pressed(VirtualKeyCode::Escape) {
+                        self.capturing_key = None;
+                    } else if let Some(key) = KeyCombination::from_just_pressed(keyboard_state) {
+                        app_options.front_end.keymap.set(capturing_action, key);
+                        self.capturing_key = None;
+                        key_bindings_changed = true;
+                    }
+                }
+
+                let footer_height = ui.frame_height_with_spacing() * 2.0;
+                ChildWindow::new("key_bindings_list")
+                    .size([0.0, -footer_height])
+                    .build(ui, || {
+                        ui.columns(4, "key bindings table", false);
+
+                        for action in Action::all() {
+                            let conflicts = app_options.front_end.keymap.conflicts(&action);
+
+                            ui.text(action.label(app_options.locale_lang()));
+                            ui.next_column();
+
+                            let key_button_label = if self.capturing_key == Some(action) {
+                                "press a key (esc to cancel)".to_string()
+                            } else {
+                                match app_options.front_end.keymap.get(&action) {
+                                    Some(key) => key.to_string(),
+                                    None => "none".to_string(),
+                                }
+                            };
+                            let conflict_colour = if conflicts.is_empty() {
+                                None
+                            } else {
+                                Some(ui.push_style_color(StyleColor::Text, [1.0, 0.4, 0.4, 1.0]))
+                            };
+                            if ui.button(format!("{}##{}_key", key_button_label, action.id())) {
+                                self.capturing_key = Some(action);
+                            }
+                            if let Some(conflict_colour) = conflict_colour {
+                                conflict_colour.pop();
+                            }
+                            if !conflicts.is_empty() && ui.is_item_hovered() {
+                                ui.tooltip_text(format!(
+                                    "also bound to {}",
+                                    conflicts
+                                        .iter()
+                                        .map(|conflict| conflict.label(app_options.locale_lang()))
+                                        .collect::<Vec<_>>()
+                                        .join(", ")
+                                ));
+                            }
+                            ui.next_column();
+
+                            if ui.small_button(format!("reset##{}_key_reset", action.id())) {
+                                app_options.front_end.keymap.reset(&action);
+                                key_bindings_changed = true;
+                            }
+                            ui.next_column();
+
+                            if ui.small_button(format!("unbind##{}_key_unbind", action.id())) {
+                                app_options.front_end.keymap.unbind(action);
+                                if self.capturing_key == Some(action) {
+                                    self.capturing_key = None;
+                                }
+                                key_bindings_changed = true;
+                            }
+                            ui.next_column();
+                        }
+
+                        ui.columns(1, "key bindings table end", false);
+                    });
+
+                if ui.button("reset all key bindings") {
+                    app_options.front_end.keymap.reset_all();
+                    self.capturing_key = None;
+                    key_bindings_changed = true;
+                }
+
+                key_bindings_changed
+            }
             Category::MenuOption => {
                 let mut recent_path_size = app_options.recent_path_size.to_string();
                 let recent_path_size_edited =
@@ -370,7 +296,29 @@ impl OptionMenu {
                     &mut app_options.auto_switch_new_tab,
                 );
 
-                recent_path_size_edited || auto_switch_new_tab_edited
+                let persist_undo_history_edited = ui.checkbox(
+                    "keep undo history after closing a file",
+                    &mut app_options.persist_undo_history,
+                );
+
+                let mut autosave_interval = app_options.autosave_interval.to_string();
+                let autosave_interval_edited = InputText::new(
+                    ui,
+                    "autosave interval in seconds (0 to disable)",
+                    &mut autosave_interval,
+                )
+                .chars_decimal(true)
+                .chars_noblank(true)
+                .build();
+
+                if autosave_interval_edited {
+                    app_options.autosave_interval = autosave_interval.parse().unwrap_or(0);
+                }
+
+                recent_path_size_edited
+                    || auto_switch_new_tab_edited
+                    || persist_undo_history_edited
+                    || autosave_interval_edited
             }
             Category::LineOption => {
                 ui.columns(2, "line option table", false);
@@ -383,13 +331,15 @@ impl OptionMenu {
                 ui.text("jump lgagst default option");
                 ui.indent();
                 let jump_lgagst_option_changed =
-                    app_options.jump_lgagst_option.show_ui(ui, "jump_lgagst");
+                    show_lgagst_option_ui(ui, &mut app_options.jump_lgagst_option, "jump_lgagst");
                 ui.unindent();
                 ui.text("ducktap lgagst default option");
                 ui.indent();
-                let ducktap_lgagst_option_changed = app_options
-                    .ducktap_lgagst_option
-                    .show_ui(ui, "ducktap_lgagst");
+                let ducktap_lgagst_option_changed = show_lgagst_option_ui(
+                    ui,
+                    &mut app_options.ducktap_lgagst_option,
+                    "ducktap_lgagst",
+                );
                 ui.unindent();
                 ui.dummy(dummy_spacing);
                 ui.text("default comment");
@@ -486,6 +436,7 @@ impl OptionMenu {
         if ui.button("Save") {
             self.option_menu_before = None;
             self.modified = false;
+            self.capturing_key = None;
 
             if let Err(err) = app_options.save_options() {
                 native_dialog::MessageDialog::new()
@@ -504,20 +455,47 @@ impl OptionMenu {
         if ui.button("Default") {
             *app_options = AppOptions::default();
             self.modified = true;
+            self.capturing_key = None;
         }
     }
 }
 
-#[derive(PartialEq, Clone, Copy)]
+#[derive(PartialEq, Eq, Hash, Debug, Clone, Copy, Default)]
 pub enum Category {
+    #[default]
     MenuOption,
     LineOption,
     PropertiesOption,
     Language,
+    KeyBindings,
 }
 
-impl Default for Category {
-    fn default() -> Self {
-        Category::MenuOption
+impl Category {
+    pub const ALL: &'static [Category] = &[
+        Category::MenuOption,
+        Category::LineOption,
+        Category::PropertiesOption,
+        Category::Language,
+        Category::KeyBindings,
+    ];
+
+    pub fn id(&self) -> &'static str {
+        match self {
+            Category::MenuOption => "menu",
+            Category::LineOption => "line",
+            Category::PropertiesOption => "properties",
+            Category::Language => "language",
+            Category::KeyBindings => "key-bindings",
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Category::MenuOption => "menu options",
+            Category::LineOption => "line options",
+            Category::PropertiesOption => "properties options",
+            Category::Language => "language",
+            Category::KeyBindings => "key bindings",
+        }
     }
 }
@@ -0,0 +1,155 @@
+use imgui::{CollapsingHeader, Condition, Selectable, StyleColor, TreeNodeFlags, Ui, Window};
+
+use crate::helpers::hltas::lint::{DiagnosticKind, FixDefaults, QuickFix, Severity};
+
+use super::{option_menu::AppOptions, tab::HLTASFileTab};
+
+pub fn severity_color(severity: Severity) -> [f32; 4] {
+    match severity {
+        Severity::Warning => [1.0, 0.8, 0.2, 1.0],
+        Severity::Error => [1.0, 0.4, 0.4, 1.0],
+    }
+}
+
+pub fn fix_defaults(options: &AppOptions) -> FixDefaults {
+    FixDefaults {
+        frametime: options.default_frametime().to_string(),
+        frametime_0ms: options.default_0ms_frametime().to_string(),
+    }
+}
+
+/// Diagnostics of the tab grouped by kind
+#[derive(Default)]
+pub struct ProblemsMenu {
+    opened: bool,
+}
+
+impl ProblemsMenu {
+    pub fn open(&mut self) {
+        self.opened = true;
+    }
+
+    /// Lists the diagnostics of the tab, which follow edits, clicking a line jumps to it
+    pub fn show(&mut self, ui: &Ui, options: &AppOptions, current_tab: &mut HLTASFileTab) {
+        if !self.opened {
+            return;
+        }
+
+        let locale_lang = options.locale_lang();
+        let diagnostics = current_tab.diagnostics().to_vec();
+        let mut fixes: Vec<(usize, QuickFix)> = Vec::new();
+
+        Window::new(format!(
+            "{} - {}###problems_menu",
+            locale_lang.get_string_from_id("problems"),
+            current_tab.title()
+        ))
+        .opened(&mut self.opened)
+        .size([450.0, 350.0], Condition::FirstUseEver)
+        .position_pivot([0.5, 0.5])
+        .position(
+            {
+                let display_size = ui.io().display_size;
+                [display_size[0] * 0.5, display_size[1] * 0.5]
+            },
+            Condition::Appearing,
+        )
+        .build(ui, || {
+            if diagnostics.is_empty() {
+                ui.text_disabled(locale_lang.get_string_from_id("problems-none"));
+                return;
+            }
+
+            let count = |severity| {
+                diagnostics
+                    .iter()
+                    .filter(|diagnostic| diagnostic.severity() == severity)
+                    .count()
+            };
+            ui.text(format!(
+                "{}: {}, {}: {}",
+                locale_lang.get_string_from_id("problems-errors"),
+                count(Severity::Error),
+                locale_lang.get_string_from_id("problems-warnings"),
+                count(Severity::Warning)
+            ));
+
+            for kind in DiagnosticKind::ALL.iter() {
+                let line_indexes = diagnostics
+                    .iter()
+                    .filter(|diagnostic| diagnostic.kind == *kind)
+                    .map(|diagnostic| diagnostic.line_index)
+                    .collect::<Vec<_>>();
+                if line_indexes.is_empty() {
+                    continue;
+                }
+
+                let color_token =
+                    ui.push_style_color(StyleColor::Text, severity_color(kind.severity()));
+                let header_opened = CollapsingHeader::new(format!(
+                    "{} ({})###problems_{}",
+                    locale_lang.get_string_from_id(kind.id()),
+                    line_indexes.len(),
+                    kind.id()
+                ))
+                .flags(TreeNodeFlags::DEFAULT_OPEN)
+                .build(ui);
+                color_token.pop();
+
+                if !header_opened {
+                    continue;
+                }
+
+                // fix all of this kind
+                for quick_fix in kind.quick_fixes() {
+                    if ui.button(format!(
+                        "{}: {}##problems_fix_all_{}",
+                        locale_lang.get_string_from_id("quick-fix-all"),
+                        locale_lang.get_string_from_id(quick_fix.id()),
+                        quick_fix.id()
+                    )) {
+                        fixes = line_indexes
+                            .iter()
+                            .map(|line_index| (*line_index, *quick_fix))
+                            .collect();
+                    }
+                }
+
+                for line_index in line_indexes {
+                    for quick_fix in kind.quick_fixes() {
+                        if ui.small_button(format!(
+                            "{}##problems_fix_{}_{}",
+                            locale_lang.get_string_from_id(quick_fix.id()),
+                            quick_fix.id(),
+                            line_index
+                        )) {
+                            fixes = vec![(line_index, *quick_fix)];
+                        }
+                        ui.same_line();
+                    }
+
+                    let tab_menu_data = &mut current_tab.line_states;
+
+                    if Selectable::new(format!(
+                        "{} {}##problems_{}_{}",
+                        locale_lang.get_string_from_id("problems-line"),
+                        line_index + 1,
+                        kind.id(),
+                        line_index
+                    ))
+                    .selected(tab_menu_data.is_line_selected(line_index))
+                    .build(ui)
+                    {
+                        tab_menu_data.reset_selected_indexes();
+                        tab_menu_data.change_selected_index(line_index, true);
+                        tab_menu_data.set_goto_line(line_index);
+                    }
+                }
+            }
+        });
+
+        if !fixes.is_empty() {
+            current_tab.apply_quick_fixes(&fixes, &fix_defaults(options));
+        }
+    }
+}
@@ -0,0 +1,75 @@
+use std::{fs, path::PathBuf};
+
+use hltas_editor_core::options::get_save_dir;
+use serde::{Deserialize, Serialize};
+
+/// Unsaved tab written to the recovery folder
+#[derive(Clone, Serialize, Deserialize)]
+pub struct RecoveryTab {
+    pub title: String,
+    pub path: Option<PathBuf>,
+    pub content: String,
+}
+
+pub fn recovery_dir() -> Result<PathBuf, std::io::Error> {
+    let recovery_dir = get_save_dir()?.join("recovery");
+
+    if !recovery_dir.exists() {
+        fs::create_dir(&recovery_dir)?;
+    }
+
+    Ok(recovery_dir)
+}
+
+/// Replaces the tabs in the recovery folder
+pub fn save_recovery_tabs(recovery_tabs: &[RecoveryTab]) -> Result<(), std::io::Error> {
+    clear_recovery_tabs()?;
+
+    let recovery_dir = recovery_dir()?;
+
+    for (i, recovery_tab) in recovery_tabs.iter().enumerate() {
+        let data = serde_json::to_string(recovery_tab).unwrap();
+        fs::write(recovery_dir.join(format!("{}.json", i)), &data)?;
+    }
+
+    Ok(())
+}
+
+/// Loads all tabs in the recovery folder, files that can't be read are skipped
+pub fn load_recovery_tabs() -> Result<Vec<RecoveryTab>, std::io::Error> {
+    let mut recovery_files = fs::read_dir(recovery_dir()?)?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| {
+            path.extension()
+                .is_some_and(|extension| extension == "json")
+        })
+        .collect::<Vec<_>>();
+    recovery_files.sort_by_key(|path| {
+        path.file_stem()
+            .and_then(|file_stem| file_stem.to_str())
+            .and_then(|file_stem| file_stem.parse::<usize>().ok())
+    });
+
+    Ok(recovery_files
+        .iter()
+        .filter_map(|path| {
+            let data = fs::read_to_string(path).ok()?;
+            serde_json::from_str(&data).ok()
+        })
+        .collect())
+}
+
+pub fn clear_recovery_tabs() -> Result<(), std::io::Error> {
+    for entry in fs::read_dir(recovery_dir()?)? {
+        let path = entry?.path();
+
+        if path
+            .extension()
+            .is_some_and(|extension| extension == "json")
+        {
+            fs::remove_file(path)?;
+        }
+    }
+
+    Ok(())
+}
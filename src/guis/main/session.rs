@@ -0,0 +1,53 @@
+use std::{fs, path::PathBuf};
+
+use hltas_editor_core::options::get_save_dir;
+use serde::{Deserialize, Serialize};
+
+/// View state of a tab that gets restored with the session
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct TabViewState {
+    pub simple_view: bool,
+    #[serde(default)]
+    pub timing_gutter: bool,
+    /// Line shown at the top of the graphics editor
+    pub top_line: usize,
+    pub selected_indexes: Vec<usize>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct SessionTab {
+    pub path: PathBuf,
+    pub view_state: TabViewState,
+}
+
+/// Tabs and recent paths from the last time the app was closed
+#[derive(Default, Serialize, Deserialize)]
+pub struct Session {
+    pub tabs: Vec<SessionTab>,
+    /// Index of the active tab in `tabs`
+    pub current_tab: Option<usize>,
+    pub recent_paths: Vec<PathBuf>,
+}
+
+impl Session {
+    pub fn session_path() -> Result<PathBuf, std::io::Error> {
+        Ok(get_save_dir()?.join("session.json"))
+    }
+
+    pub fn save(&self) -> Result<(), std::io::Error> {
+        let session_data = serde_json::to_string(self).unwrap();
+        fs::write(Self::session_path()?, &session_data)?;
+        Ok(())
+    }
+
+    pub fn load() -> Result<Self, Box<dyn std::error::Error>> {
+        let session_path = Self::session_path()?;
+
+        if !session_path.exists() {
+            return Ok(Self::default());
+        }
+
+        let session_data = fs::read_to_string(session_path)?;
+        Ok(serde_json::from_str(&session_data)?)
+    }
+}
@@ -0,0 +1,120 @@
+use imgui::{CollapsingHeader, Condition, TreeNodeFlags, Ui, Window};
+
+use crate::helpers::{hltas::stats::ScriptStats, locale::locale_lang::LocaleLang};
+
+use super::tab::HLTASFileTab;
+
+#[derive(Default)]
+pub struct StatisticsMenu {
+    opened: bool,
+}
+
+impl StatisticsMenu {
+    pub fn open(&mut self) {
+        self.opened = true;
+    }
+
+    /// Shows the statistics of the tab, which are recalculated every frame to follow edits
+    pub fn show(&mut self, ui: &Ui, locale_lang: &LocaleLang, current_tab: &HLTASFileTab) {
+        if !self.opened {
+            return;
+        }
+
+        let stats = ScriptStats::new(current_tab.hltas());
+
+        Window::new(format!(
+            "{} - {}###statistics_menu",
+            locale_lang.get_string_from_id("statistics"),
+            current_tab.title()
+        ))
+        .opened(&mut self.opened)
+        .size([350.0, 450.0], Condition::FirstUseEver)
+        .position_pivot([0.5, 0.5])
+        .position(
+            {
+                let display_size = ui.io().display_size;
+                [display_size[0] * 0.5, display_size[1] * 0.5]
+            },
+            Condition::Appearing,
+        )
+        .build(ui, || {
+            if ui.button(locale_lang.get_string_from_id("stats-copy")) {
+                ui.set_clipboard_text(stats.to_string());
+            }
+
+            ui.text(format!(
+                "{}: {}",
+                locale_lang.get_string_from_id("stats-total-frames"),
+                stats.total_frames
+            ));
+            if stats.zero_ms_frames > 0 {
+                ui.text(format!(
+                    "{}: {}",
+                    locale_lang.get_string_from_id("stats-zero-ms-frames"),
+                    stats.zero_ms_frames
+                ));
+            }
+            ui.text(format!(
+                "{}: {:.3}s",
+                locale_lang.get_string_from_id("stats-total-time"),
+                stats.total_time
+            ));
+            ui.text(format!(
+                "{}: {}",
+                locale_lang.get_string_from_id("stats-saves"),
+                stats.save_count
+            ));
+            ui.text(format!(
+                "{}: {}",
+                locale_lang.get_string_from_id("stats-console-commands"),
+                stats.console_command_count
+            ));
+
+            if CollapsingHeader::new(locale_lang.get_string_from_id("stats-sections"))
+                .flags(TreeNodeFlags::DEFAULT_OPEN)
+                .build(ui)
+            {
+                for section in &stats.sections {
+                    match &section.name {
+                        Some(name) => ui.text(name),
+                        None => {
+                            ui.text_disabled(locale_lang.get_string_from_id("stats-start-section"))
+                        }
+                    }
+                    ui.same_line();
+                    ui.text_disabled(format!("{} / {:.3}s", section.frames, section.time));
+                }
+            }
+
+            if CollapsingHeader::new(locale_lang.get_string_from_id("stats-lines")).build(ui) {
+                for (line_type, count) in stats.line_counts.iter().filter(|(_, count)| *count > 0) {
+                    ui.text(format!("{}: {}", line_type, count));
+                }
+            }
+
+            if CollapsingHeader::new(locale_lang.get_string_from_id("stats-strafe-frames"))
+                .build(ui)
+            {
+                for (strafe_type, frames) in
+                    stats.strafe_frames.iter().filter(|(_, frames)| *frames > 0)
+                {
+                    ui.text(format!("{}: {}", strafe_type, frames));
+                }
+            }
+
+            if CollapsingHeader::new(locale_lang.get_string_from_id("stats-autoaction-frames"))
+                .build(ui)
+            {
+                ui.text(format!("jump: {}", stats.jump_frames));
+                ui.text(format!("ducktap: {}", stats.ducktap_frames));
+                ui.text(format!("jumpbug: {}", stats.jumpbug_frames));
+            }
+
+            if CollapsingHeader::new(locale_lang.get_string_from_id("stats-frametimes")).build(ui) {
+                for (frametime, frames) in &stats.frametimes {
+                    ui.text(format!("{}: {}", frametime, frames));
+                }
+            }
+        });
+    }
+}
@@ -1,145 +1,126 @@
 use std::{
-    fs,
-    ops::{Deref, Range},
+    ops::{Deref, DerefMut, Range},
     path::{Path, PathBuf},
 };
 
-use fluent_templates::{LanguageIdentifier, Loader};
+use fluent_templates::LanguageIdentifier;
 use hltas::{
     types::{AutoMovement, FrameBulk, Line, Properties},
     HLTAS,
 };
-use hltas_cleaner::CleanerResult;
+use hltas_editor_core::{
+    document::{Document, LineStates},
+    selection::Selection,
+    undo_redo_hltas::UndoRedoHandler,
+};
 use native_dialog::FileDialog;
 
-use crate::{
-    helpers::{hltas::hltas_to_str, locale::locale_lang::LocaleLang},
-    locale::LOCALES,
+use crate::helpers::hltas::{
+    hltas_to_str,
+    lint::{lint, Diagnostic},
+    query::Query,
 };
 
-use super::undo_redo_hltas::UndoRedoHandler;
+use super::{recovery::RecoveryTab, session::TabViewState, text_editor::TextEditorState};
 
+/// Document of a tab with the gui states of the tab
 #[derive(Clone, Debug, Default)]
 pub struct HLTASFileTab {
-    title: String,
-    path: Option<PathBuf>,
-    hltas: HLTAS,
-    pub tab_menu_data: HLTASMenuState,
-    pub undo_redo_handler: UndoRedoHandler,
+    document: Document<HLTASMenuState>,
+    text_editor: Option<TextEditorState>,
+    query_matches: Option<QueryMatches>,
+    /// Lint diagnostics with the revision of the document they were found at
+    diagnostics: Option<(u64, Vec<Diagnostic>)>,
 }
 
-impl<'a> HLTASFileTab {
-    pub fn open_path(path: &Path, file_content: &'a str) -> Result<Self, hltas::read::Error<'a>> {
-        let hltas = match HLTAS::from_str(file_content) {
-            Ok(hltas) => hltas,
-            Err(err) => return Err(err),
-        };
+/// Lines matching a query at a revision of the document
+#[derive(Clone, Debug)]
+struct QueryMatches {
+    query: Query,
+    revision: u64,
+    matches: Vec<usize>,
+}
 
-        let title = {
-            let path_name = path.file_name().unwrap();
-            match path_name.to_str() {
-                Some(str_name) => str_name.to_owned(),
-                None => path_name.to_string_lossy().deref().to_owned(),
-            }
-        };
+impl Deref for HLTASFileTab {
+    type Target = Document<HLTASMenuState>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.document
+    }
+}
 
-        let tab_menu_data = HLTASMenuState::new(&hltas);
+impl DerefMut for HLTASFileTab {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.document
+    }
+}
 
+impl<'a> HLTASFileTab {
+    pub fn open_path(path: &Path, file_content: &'a str) -> Result<Self, hltas::read::Error<'a>> {
         Ok(Self {
-            title,
-            path: Some(path.to_path_buf()),
-            hltas,
-            tab_menu_data,
-            ..Default::default()
+            document: Document::open_path(path, file_content)?,
+            text_editor: None,
+            query_matches: None,
+            diagnostics: None,
         })
     }
 
-    pub fn title_from_path(path: &Path, lang: &LanguageIdentifier) -> String {
-        if let Some(os_str) = path.file_name() {
-            if let Some(str) = os_str.to_str() {
-                return str.to_owned();
-            }
-        }
-        HLTASFileTab::default_title(lang)
+    /// Opens an autosaved tab, which is marked as modified since it wasn't saved
+    pub fn open_recovery(recovery_tab: &'a RecoveryTab) -> Result<Self, hltas::read::Error<'a>> {
+        let hltas = HLTAS::from_str(&recovery_tab.content)?;
+
+        Ok(Self::from_document(Document::from_hltas(
+            recovery_tab.title.to_owned(),
+            recovery_tab.path.to_owned(),
+            hltas,
+        )))
     }
 
-    // BUG fix language change for title
-    fn default_title(lang: &LanguageIdentifier) -> String {
-        LOCALES.lookup(lang, "new-file-title")
+    /// Text editor content is used if the tab is text edited, since it might not be applied yet
+    pub fn to_recovery_tab(&self) -> RecoveryTab {
+        let content = match &self.text_editor {
+            Some(text_editor) => text_editor.content().to_owned(),
+            None => hltas_to_str(self.hltas()),
+        };
+
+        RecoveryTab {
+            title: self.title().to_owned(),
+            path: self.path().cloned(),
+            content,
+        }
     }
 
-    pub fn new_file(lang: &LanguageIdentifier) -> Self {
+    fn from_document(document: Document<HLTASMenuState>) -> Self {
         Self {
-            title: Self::default_title(lang),
-            ..Default::default()
+            document,
+            text_editor: None,
+            query_matches: None,
+            diagnostics: None,
         }
     }
 
-    pub fn hltas_properties_mut(&mut self) -> &mut Properties {
-        &mut self.hltas.properties
+    /// Replaces the hltas with the one from the file as an undo point, the tab counts as saved after
+    pub fn reload_hltas(&mut self, hltas: HLTAS) {
+        self.text_editor = None;
+        self.document.reload_hltas(hltas);
     }
 
-    pub fn hltas_lines(&self) -> &[Line] {
-        &self.hltas.lines
+    pub fn new_file(lang: &LanguageIdentifier) -> Self {
+        Self::from_document(Document::new_file(lang))
     }
 
-    pub fn split_fields_mut(
-        &mut self,
-    ) -> (
-        &mut [Line],
-        &mut Properties,
-        &mut HLTASMenuState,
-        &mut UndoRedoHandler,
-    ) {
-        (
-            &mut self.hltas.lines,
-            &mut self.hltas.properties,
-            &mut self.tab_menu_data,
-            &mut self.undo_redo_handler,
-        )
-    }
-
-    pub fn insert_line(&mut self, index: usize, line: hltas::types::Line) {
-        self.tab_menu_data.insert_hltas_line(index, &line);
-        self.hltas.lines.insert(index, line);
-        self.tab_menu_data.got_modified();
-    }
-
-    pub fn push_line(&mut self, line: hltas::types::Line) {
-        self.tab_menu_data.push_hltas_line(&line);
-        self.hltas.lines.push(line);
-        self.tab_menu_data.got_modified();
-    }
-
-    pub fn hltas_cleaner_fn<F>(&mut self, cleaner: F)
-    where
-        F: FnOnce(&mut HLTAS) -> CleanerResult,
-    {
-        let cleaner_result = cleaner(&mut self.hltas);
-
-        for i in cleaner_result.lines_removed.iter().rev() {
-            self.tab_menu_data.remove_line_at_index(*i);
-        }
-
-        self.tab_menu_data.got_modified();
+    /// Tab without a file, marked as modified since it isn't saved anywhere
+    pub fn from_hltas(title: String, hltas: HLTAS) -> Self {
+        Self::from_document(Document::from_hltas(title, None, hltas))
     }
 
-    pub fn new_line_at_click_index(&mut self, line: hltas::types::Line) {
-        match self.tab_menu_data.right_click_popup_index() {
-            Some(index) => self.insert_line(index, line),
-            None => self.push_line(line),
+    /// Sets an undo point for the properties if they were edited since the backup
+    pub fn set_properties_undo_point(&mut self) {
+        if let Some(properties) = self.line_states.take_properties_edit_backup() {
+            if properties != *self.hltas_properties() {
+                self.undo_redo_handler.edit_properties(properties);
+            }
         }
-        self.tab_menu_data.got_modified();
-    }
-
-    pub fn remove_line_at_index(&mut self, index: usize) {
-        self.hltas.lines.remove(index);
-        self.tab_menu_data.remove_line_at_index(index);
-        self.tab_menu_data.got_modified();
-    }
-
-    pub fn path(&self) -> Option<&PathBuf> {
-        self.path.as_ref()
     }
 
     fn ask_hltas_save_location() -> Result<Option<PathBuf>, native_dialog::Error> {
@@ -148,79 +129,120 @@ impl<'a> HLTASFileTab {
             .show_save_single_file()
     }
 
-    pub fn write_hltas_to_file(&mut self, locale_lang: &LocaleLang) -> Result<(), std::io::Error> {
-        if let Some(path) = &self.path {
-            // save_path = Some(path.to_owned());
-            fs::write(path, hltas_to_str(&self.hltas))?;
-            self.tab_menu_data.saved_modified();
-        } else {
-            // no file, save as new file
-            if let Ok(Some(path)) = Self::ask_hltas_save_location() {
-                fs::write(&path, hltas_to_str(&self.hltas))?;
-                self.title = Self::title_from_path(&path, &locale_lang.get_lang());
-            }
+    pub fn write_hltas_to_file(&mut self) -> Result<(), std::io::Error> {
+        // text editor content is what the user sees, so that has to be saved
+        if let Err(err) = self.apply_text_editor() {
+            return Err(std::io::Error::new(std::io::ErrorKind::InvalidData, err));
         }
 
-        Ok(())
+        let path = match self.path() {
+            Some(path) => path.to_owned(),
+            // no file, save as new file
+            None => match Self::ask_hltas_save_location() {
+                Ok(Some(path)) => path,
+                _ => return Ok(()),
+            },
+        };
+
+        self.document.save_to_path(&path)
     }
 
-    pub fn select_all_lines(&mut self) {
-        self.tab_menu_data.select_all_indexes();
+    /// Indexes of the lines matching the query, only searched again if the query or the hltas changed
+    pub fn query_matches(&mut self, query: &Query) -> &[usize] {
+        let revision = self.revision();
+        let outdated = !self.query_matches.as_ref().is_some_and(|query_matches| {
+            query_matches.revision == revision && query_matches.query == *query
+        });
+
+        if outdated {
+            self.query_matches = Some(QueryMatches {
+                query: query.to_owned(),
+                revision,
+                matches: query.find_matches(self.hltas_lines()),
+            });
+        }
+
+        &self.query_matches.as_ref().unwrap().matches
     }
 
-    pub fn title(&self) -> &str {
-        self.title.as_ref()
+    /// Lint diagnostics of the hltas, only linted again after an edit
+    pub fn diagnostics(&mut self) -> &[Diagnostic] {
+        let revision = self.revision();
+        let outdated = self
+            .diagnostics
+            .as_ref()
+            .is_none_or(|(diagnostics_revision, _)| *diagnostics_revision != revision);
+
+        if outdated {
+            self.diagnostics = Some((revision, lint(self.hltas())));
+        }
+
+        &self.diagnostics.as_ref().unwrap().1
     }
 
-    pub fn get_selected_lines(&self) -> Vec<&Line> {
-        self.hltas
-            .lines
-            .iter()
-            .enumerate()
-            .filter_map(|(i, line)| {
-                if self.tab_menu_data.is_index_selected(i) {
-                    Some(line)
-                } else {
-                    None
-                }
-            })
-            .collect::<Vec<_>>()
+    /// Gets the text editor state, creating it from the hltas if the tab isn't text edited yet
+    pub fn text_editor_mut(&mut self) -> &mut TextEditorState {
+        let hltas = self.document.hltas();
+        self.text_editor
+            .get_or_insert_with(|| TextEditorState::new(hltas_to_str(hltas)))
     }
 
-    pub fn remove_selected_lines(&mut self) {
-        for index in self
-            .tab_menu_data
-            .selected_indexes_collection()
-            .iter()
-            .rev()
-        {
-            self.remove_line_at_index(*index);
+    /// Parses the text editor content and replaces the hltas with it
+    ///
+    /// * Returns the parse error as a string, the hltas stays untouched in that case
+    pub fn apply_text_editor(&mut self) -> Result<(), String> {
+        let text_editor = match &mut self.text_editor {
+            Some(text_editor) => text_editor,
+            None => return Ok(()),
+        };
+
+        let hltas = match HLTAS::from_str(text_editor.content()) {
+            Ok(hltas) => hltas,
+            Err(err) => {
+                let err = err.to_string();
+                text_editor.set_parse_error(Some(err.clone()));
+                return Err(err);
+            }
+        };
+        text_editor.set_parse_error(None);
+
+        if hltas_to_str(&hltas) != hltas_to_str(self.document.hltas()) {
+            self.document.replace_hltas(hltas);
         }
-    }
 
-    pub fn undo_hltas(&mut self) {
-        self.undo_redo_handler
-            .undo(&mut self.hltas, &mut self.tab_menu_data);
+        Ok(())
     }
 
-    pub fn redo_hltas(&mut self) {
-        self.undo_redo_handler
-            .redo(&mut self.hltas, &mut self.tab_menu_data);
+    /// Applies the text editor content and stops text editing
+    pub fn close_text_editor(&mut self) -> Result<(), String> {
+        self.apply_text_editor()?;
+        self.text_editor = None;
+        Ok(())
     }
 }
 
 /// Struct to keep track of some menu states for the hltas object in the tab
-#[derive(Clone, Debug, Default)]
+#[derive(Clone, Debug)]
 pub struct HLTASMenuState {
     strafe_menu_selections: Vec<Option<StrafeMenuSelection>>,
     simple_view_show_fps: bool,
     right_click_popup_index: Option<usize>,
-    selected_indexes: Vec<bool>,
-    hltas_modified: bool,
+    /// Line the popup got opened on, `None` if opened between lines or on empty space
+    right_click_line: Option<usize>,
+    /// Frame the right clicked framebulk gets split at
+    right_click_split_frame: usize,
+    selection: Selection,
+    search_matches: Vec<usize>,
+    current_search_match: Option<usize>,
+    line_filter: Option<Query>,
     goto_line: Option<usize>,
     simple_view: bool,
+    /// Shows the frame and time each line starts at
+    timing_gutter: bool,
     is_modifying_line: bool,
     line_edit_backup: Option<(Line, usize)>,
+    properties_edit_backup: Option<Properties>,
+    top_line: usize,
 }
 
 impl HLTASMenuState {
@@ -239,14 +261,21 @@ impl HLTASMenuState {
 
         Self {
             line_edit_backup: None,
+            properties_edit_backup: None,
+            top_line: 0,
             simple_view_show_fps: true,
             is_modifying_line: false,
             strafe_menu_selections,
             right_click_popup_index: None,
-            selected_indexes: vec![false; hltas.lines.len()],
-            hltas_modified: false,
+            right_click_line: None,
+            right_click_split_frame: 1,
+            selection: Selection::new(hltas.lines.len()),
+            search_matches: Vec::new(),
+            current_search_match: None,
+            line_filter: None,
             goto_line: None,
             simple_view: false,
+            timing_gutter: false,
         }
     }
 
@@ -282,6 +311,17 @@ impl HLTASMenuState {
         }
     }
 
+    /// Keeps the properties from before the edit started, does nothing if there's already a backup
+    pub fn set_properties_edit_backup(&mut self, properties: &Properties) {
+        if self.properties_edit_backup.is_none() {
+            self.properties_edit_backup = Some(properties.to_owned());
+        }
+    }
+
+    pub fn take_properties_edit_backup(&mut self) -> Option<Properties> {
+        self.properties_edit_backup.take()
+    }
+
     pub fn get_line_edit_backup(&self) -> &Option<(Line, usize)> {
         &self.line_edit_backup
     }
@@ -299,87 +339,105 @@ impl HLTASMenuState {
     }
 
     pub fn is_index_selected(&self, index: usize) -> bool {
-        self.selected_indexes[index]
+        self.selection.is_selected(index)
     }
 
     /// Get a reference to the hltasmenu state's selected indexes.
     pub fn selected_indexes(&self) -> &[bool] {
-        self.selected_indexes.as_ref()
+        self.selection.selected_indexes()
     }
 
     pub fn selected_indexes_collection(&self) -> Vec<usize> {
-        self.selected_indexes
-            .iter()
-            .enumerate()
-            .filter_map(|(i, is_selected)| if *is_selected { Some(i) } else { None })
-            .collect::<Vec<_>>()
+        self.selection.indexes()
     }
 
     pub fn reset_selected_indexes(&mut self) {
-        self.selected_indexes = vec![false; self.selected_indexes.len()];
-    }
-
-    pub fn select_all_indexes(&mut self) {
-        self.selected_indexes = vec![true; self.selected_indexes.len()];
+        self.selection.clear();
     }
 
     pub fn change_selected_index(&mut self, index: usize, state: bool) {
-        self.selected_indexes[index] = state;
+        self.selection.set(index, state);
     }
 
     pub fn select_index_range(&mut self, range: Range<usize>, state: bool) {
-        self.selected_indexes[range]
-            .iter_mut()
-            .for_each(|index| *index = state);
+        self.selection.set_range(range, state);
     }
 
-    pub fn insert_hltas_line(&mut self, index: usize, line: &hltas::types::Line) {
-        self.strafe_menu_selections.insert(
-            index,
-            match line {
-                Line::FrameBulk(framebulk) => Some(StrafeMenuSelection::new(framebulk)),
-                _ => None,
-            },
-        );
-        self.selected_indexes.insert(index, false);
+    pub fn set_search_matches(&mut self, matches: Vec<usize>, current_match: Option<usize>) {
+        self.search_matches = matches;
+        self.current_search_match = current_match;
     }
 
-    pub fn push_hltas_line(&mut self, line: &hltas::types::Line) {
-        self.strafe_menu_selections.push(match line {
-            Line::FrameBulk(framebulk) => Some(StrafeMenuSelection::new(framebulk)),
-            _ => None,
-        });
-        self.selected_indexes.push(false);
+    pub fn clear_search_matches(&mut self) {
+        self.search_matches.clear();
+        self.current_search_match = None;
     }
 
-    pub fn remove_line_at_index(&mut self, index: usize) {
-        self.strafe_menu_selections.remove(index);
-        self.selected_indexes.remove(index);
+    pub fn is_search_match(&self, index: usize) -> bool {
+        self.search_matches.binary_search(&index).is_ok()
+    }
+
+    pub fn is_current_search_match(&self, index: usize) -> bool {
+        self.current_search_match == Some(index)
+    }
+
+    /// Only lines matching the filter will be shown in the graphics editor
+    ///
+    /// * Hidden lines get deselected, so they can't be edited without being seen
+    pub fn set_line_filter(&mut self, line_filter: Option<Query>, lines: &[Line]) {
+        if line_filter == self.line_filter {
+            return;
+        }
+
+        if let Some(line_filter) = &line_filter {
+            for (i, line) in lines.iter().enumerate() {
+                if !line_filter.matches(line) {
+                    self.selection.set(i, false);
+                }
+            }
+        }
+        self.line_filter = line_filter;
+    }
+
+    pub fn clear_line_filter(&mut self) {
+        self.line_filter = None;
+    }
+
+    pub fn is_line_filtered_out(&self, line: &Line) -> bool {
+        match &self.line_filter {
+            Some(line_filter) => !line_filter.matches(line),
+            None => false,
+        }
     }
 
     pub fn set_right_click_index(&mut self, index: usize) {
         self.right_click_popup_index = Some(index);
+        self.right_click_line = None;
     }
 
-    pub fn right_click_elsewhere(&mut self) {
-        self.right_click_popup_index = None;
+    /// Right click on a line, new lines get inserted after it
+    pub fn set_right_click_line(&mut self, index: usize) {
+        self.right_click_popup_index = Some(index + 1);
+        self.right_click_line = Some(index);
+        self.right_click_split_frame = 1;
     }
 
-    /// Get a reference to the hltasmenu state's right click popup index.
-    pub fn right_click_popup_index(&self) -> Option<usize> {
-        self.right_click_popup_index
+    pub fn right_click_elsewhere(&mut self) {
+        self.right_click_popup_index = None;
+        self.right_click_line = None;
     }
 
-    pub fn got_modified(&mut self) {
-        self.hltas_modified = true;
+    pub fn right_click_line(&self) -> Option<usize> {
+        self.right_click_line
     }
 
-    pub fn saved_modified(&mut self) {
-        self.hltas_modified = false;
+    pub fn right_click_split_frame_mut(&mut self) -> &mut usize {
+        &mut self.right_click_split_frame
     }
 
-    pub fn is_modified(&self) -> bool {
-        self.hltas_modified
+    /// Get a reference to the hltasmenu state's right click popup index.
+    pub fn right_click_popup_index(&self) -> Option<usize> {
+        self.right_click_popup_index
     }
 
     pub fn strafe_menu_selection_at_mut(
@@ -390,7 +448,7 @@ impl HLTASMenuState {
     }
 
     pub fn is_line_selected(&self, index: usize) -> bool {
-        self.selected_indexes()[index]
+        self.selection.is_selected(index)
     }
 
     /// Gets goto line and sets itself to None
@@ -408,6 +466,46 @@ impl HLTASMenuState {
         self.simple_view
     }
 
+    pub fn toggle_timing_gutter(&mut self) {
+        self.timing_gutter = !self.timing_gutter;
+    }
+
+    pub fn timing_gutter(&self) -> bool {
+        self.timing_gutter
+    }
+
+    /// Sets the line shown at the top of the graphics editor
+    pub fn set_top_line(&mut self, top_line: usize) {
+        self.top_line = top_line;
+    }
+
+    pub fn view_state(&self) -> TabViewState {
+        TabViewState {
+            simple_view: self.simple_view,
+            timing_gutter: self.timing_gutter,
+            top_line: self.top_line,
+            selected_indexes: self.selected_indexes_collection(),
+        }
+    }
+
+    /// Restores the view state, selected indexes that don't exist anymore are ignored
+    pub fn set_view_state(&mut self, view_state: &TabViewState) {
+        self.simple_view = view_state.simple_view;
+        self.timing_gutter = view_state.timing_gutter;
+
+        self.reset_selected_indexes();
+        for index in &view_state.selected_indexes {
+            if *index < self.selection.len() {
+                self.change_selected_index(*index, true);
+            }
+        }
+
+        if view_state.top_line < self.selection.len() {
+            self.top_line = view_state.top_line;
+            self.set_goto_line(view_state.top_line);
+        }
+    }
+
     pub fn simple_view_show_fps(&self) -> bool {
         self.simple_view_show_fps
     }
@@ -417,6 +515,64 @@ impl HLTASMenuState {
     }
 }
 
+impl Default for HLTASMenuState {
+    fn default() -> Self {
+        Self::new(&HLTAS::default())
+    }
+}
+
+impl LineStates for HLTASMenuState {
+    fn insert_line(&mut self, index: usize, line: &Line) {
+        self.strafe_menu_selections.insert(
+            index,
+            match line {
+                Line::FrameBulk(framebulk) => Some(StrafeMenuSelection::new(framebulk)),
+                _ => None,
+            },
+        );
+        self.selection.insert_line(index, line);
+    }
+
+    fn remove_line(&mut self, index: usize) {
+        self.strafe_menu_selections.remove(index);
+        self.selection.remove_line(index);
+    }
+
+    /// Keeps the strafe menu selection valid, as the line type could have changed
+    fn update_line(&mut self, index: usize, line: &Line) {
+        let strafe_menu_selection = &mut self.strafe_menu_selections[index];
+
+        match line {
+            Line::FrameBulk(framebulk) => {
+                if strafe_menu_selection.is_none() {
+                    *strafe_menu_selection = Some(StrafeMenuSelection::new(framebulk));
+                }
+            }
+            _ => *strafe_menu_selection = None,
+        }
+    }
+
+    /// Resets all line related states to match the lines of the new hltas
+    fn reset_lines(&mut self, hltas: &HLTAS) {
+        let new_state = Self::new(hltas);
+
+        self.strafe_menu_selections = new_state.strafe_menu_selections;
+        self.selection = new_state.selection;
+        self.right_click_popup_index = None;
+        self.right_click_line = None;
+        self.line_edit_backup = None;
+        self.goto_line = None;
+    }
+
+    fn selection(&self) -> &Selection {
+        &self.selection
+    }
+
+    fn selection_mut(&mut self) -> &mut Selection {
+        &mut self.selection
+    }
+}
+
 #[derive(Clone, Debug)]
 pub enum StrafeMenuSelection {
     Strafe,
@@ -0,0 +1,59 @@
+use imgui::{InputTextMultiline, Ui};
+
+use super::{option_menu::AppOptions, tab::HLTASFileTab};
+
+/// Raw text of a tab while it's being edited with the text editor
+#[derive(Clone, Debug, Default)]
+pub struct TextEditorState {
+    content: String,
+    parse_error: Option<String>,
+}
+
+impl TextEditorState {
+    pub fn new(content: String) -> Self {
+        Self {
+            content,
+            parse_error: None,
+        }
+    }
+
+    pub fn content(&self) -> &str {
+        &self.content
+    }
+
+    pub fn parse_error(&self) -> Option<&str> {
+        self.parse_error.as_deref()
+    }
+
+    pub fn set_parse_error(&mut self, parse_error: Option<String>) {
+        self.parse_error = parse_error;
+    }
+}
+
+pub fn show_text_editor(ui: &Ui, tab: &mut HLTASFileTab, options: &AppOptions) {
+    let text_editor = tab.text_editor_mut();
+
+    if let Some(parse_error) = text_editor.parse_error() {
+        ui.text_colored(
+            [1.0, 0.0, 0.0, 1.0],
+            options
+                .locale_lang()
+                .get_string_from_id("text-editor-parse-err"),
+        );
+        ui.text_colored([1.0, 0.0, 0.0, 1.0], parse_error);
+        ui.separator();
+    }
+
+    let edited = InputTextMultiline::new(
+        ui,
+        "##text_editor",
+        &mut text_editor.content,
+        ui.content_region_avail(),
+    )
+    .allow_tab_input(true)
+    .build();
+
+    if edited {
+        tab.got_modified();
+    }
+}
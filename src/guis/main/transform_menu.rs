@@ -0,0 +1,226 @@
+use std::collections::HashMap;
+
+use fluent_templates::fluent_bundle::FluentValue;
+use imgui::{Condition, InputFloat, InputInt, Ui, Window};
+
+use crate::{
+    guis::radio_button_enum::show_radio_button_enum,
+    helpers::{
+        hltas::{
+            timing::{line_timings, LineTiming},
+            transform::{FrameCountChange, Rounding, Transform},
+        },
+        locale::locale_lang::LocaleLang,
+    },
+};
+
+use super::tab::HLTASFileTab;
+
+#[derive(Clone, Copy, PartialEq)]
+enum FrameCountMode {
+    Scale,
+    Offset,
+}
+
+/// Frames and time taken by the lines at the indexes
+fn selection_total(timings: &[LineTiming], indexes: &[usize]) -> (u64, f64) {
+    indexes.iter().fold((0, 0.0), |(frames, time), i| {
+        (
+            frames + timings[i + 1].frame - timings[*i].frame,
+            time + timings[i + 1].time - timings[*i].time,
+        )
+    })
+}
+
+/// Offsets yaw / pitch and changes frame counts of the selected lines
+pub struct TransformMenu {
+    opened: bool,
+    yaw_offset: f32,
+    pitch_offset: f32,
+    frame_count_mode: FrameCountMode,
+    ratio: f32,
+    frame_offset: i32,
+    rounding: Rounding,
+}
+
+impl TransformMenu {
+    pub fn open(&mut self) {
+        self.opened = true;
+    }
+
+    fn transform(&self) -> Transform {
+        Transform {
+            yaw_offset: self.yaw_offset,
+            pitch_offset: self.pitch_offset,
+            frame_count: match self.frame_count_mode {
+                FrameCountMode::Scale => FrameCountChange::Scale {
+                    ratio: self.ratio as f64,
+                    rounding: self.rounding,
+                },
+                FrameCountMode::Offset => FrameCountChange::Offset(self.frame_offset as i64),
+            },
+        }
+    }
+
+    pub fn show(&mut self, ui: &Ui, locale_lang: &LocaleLang, current_tab: &mut HLTASFileTab) {
+        if !self.opened {
+            return;
+        }
+
+        let transform = self.transform();
+        let mut opened = self.opened;
+
+        Window::new(locale_lang.get_string_from_id("transform-selection"))
+            .opened(&mut opened)
+            .size([380.0, 330.0], Condition::FirstUseEver)
+            .position_pivot([0.5, 0.5])
+            .position(
+                {
+                    let display_size = ui.io().display_size;
+                    [display_size[0] * 0.5, display_size[1] * 0.5]
+                },
+                Condition::Appearing,
+            )
+            .build(ui, || {
+                let selected_indexes = current_tab.line_states.selected_indexes_collection();
+
+                let mut args = HashMap::new();
+                args.insert("count", FluentValue::from(selected_indexes.len()));
+                ui.text(
+                    locale_lang.get_string_from_id_with_args("transform-selected-lines", &args),
+                );
+
+                ui.separator();
+
+                InputFloat::new(
+                    ui,
+                    locale_lang.get_string_from_id("transform-yaw-offset"),
+                    &mut self.yaw_offset,
+                )
+                .build();
+                InputFloat::new(
+                    ui,
+                    locale_lang.get_string_from_id("transform-pitch-offset"),
+                    &mut self.pitch_offset,
+                )
+                .build();
+
+                ui.text(locale_lang.get_string_from_id("transform-frame-count"));
+                let scale_label = locale_lang.get_string_from_id("transform-scale");
+                let offset_label = locale_lang.get_string_from_id("transform-offset");
+                show_radio_button_enum(
+                    ui,
+                    &mut self.frame_count_mode,
+                    vec![
+                        (scale_label.as_str(), FrameCountMode::Scale),
+                        (offset_label.as_str(), FrameCountMode::Offset),
+                    ],
+                    "transform_frame_count_mode".to_string(),
+                    true,
+                );
+
+                match self.frame_count_mode {
+                    FrameCountMode::Scale => {
+                        InputFloat::new(
+                            ui,
+                            locale_lang.get_string_from_id("transform-ratio"),
+                            &mut self.ratio,
+                        )
+                        .build();
+                        if !self.ratio.is_finite() || self.ratio < 0.0 {
+                            self.ratio = 0.0;
+                        }
+
+                        let nearest_label =
+                            locale_lang.get_string_from_id("transform-rounding-nearest");
+                        let down_label = locale_lang.get_string_from_id("transform-rounding-down");
+                        let up_label = locale_lang.get_string_from_id("transform-rounding-up");
+                        show_radio_button_enum(
+                            ui,
+                            &mut self.rounding,
+                            vec![
+                                (nearest_label.as_str(), Rounding::Nearest),
+                                (down_label.as_str(), Rounding::Down),
+                                (up_label.as_str(), Rounding::Up),
+                            ],
+                            "transform_rounding".to_string(),
+                            true,
+                        );
+                    }
+                    FrameCountMode::Offset => {
+                        InputInt::new(
+                            ui,
+                            locale_lang.get_string_from_id("transform-offset"),
+                            &mut self.frame_offset,
+                        )
+                        .build();
+                    }
+                }
+
+                if selected_indexes.is_empty() {
+                    return;
+                }
+
+                // preview with the transform applied to a copy
+                let hltas = current_tab.hltas();
+                let mut transformed = hltas.to_owned();
+                let mut edited_lines = Vec::new();
+                for i in &selected_indexes {
+                    if transform.apply(&mut transformed.lines[*i]) {
+                        edited_lines.push((*i, transformed.lines[*i].to_owned()));
+                    }
+                }
+
+                let timings_before = line_timings(hltas);
+                let timings_after = line_timings(&transformed);
+                let (selection_frames_before, selection_time_before) =
+                    selection_total(&timings_before, &selected_indexes);
+                let (selection_frames_after, selection_time_after) =
+                    selection_total(&timings_after, &selected_indexes);
+                let script_before = timings_before.last().unwrap();
+                let script_after = timings_after.last().unwrap();
+
+                ui.separator();
+                ui.text(locale_lang.get_string_from_id("transform-preview"));
+                ui.text(format!(
+                    "{}: {} -> {} frames, {:.3}s -> {:.3}s",
+                    locale_lang.get_string_from_id("transform-selection-total"),
+                    selection_frames_before,
+                    selection_frames_after,
+                    selection_time_before,
+                    selection_time_after,
+                ));
+                ui.text(format!(
+                    "{}: {} -> {} frames, {:.3}s -> {:.3}s",
+                    locale_lang.get_string_from_id("transform-script-total"),
+                    script_before.frame,
+                    script_after.frame,
+                    script_before.time,
+                    script_after.time,
+                ));
+
+                ui.disabled(edited_lines.is_empty(), || {
+                    if ui.button(locale_lang.get_string_from_id("transform-apply")) {
+                        // a single undo point for the whole transform
+                        current_tab.replace_lines(std::mem::take(&mut edited_lines));
+                    }
+                });
+            });
+
+        self.opened = opened;
+    }
+}
+
+impl Default for TransformMenu {
+    fn default() -> Self {
+        Self {
+            opened: false,
+            yaw_offset: 0.0,
+            pitch_offset: 0.0,
+            frame_count_mode: FrameCountMode::Scale,
+            ratio: 1.0,
+            frame_offset: 0,
+            rounding: Rounding::Nearest,
+        }
+    }
+}
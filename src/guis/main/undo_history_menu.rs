@@ -0,0 +1,87 @@
+use imgui::{Condition, Selectable, StyleColor, Ui, Window};
+
+use crate::helpers::locale::locale_lang::LocaleLang;
+
+use super::tab::HLTASFileTab;
+
+#[derive(Default)]
+pub struct UndoHistoryMenu {
+    opened: bool,
+}
+
+impl UndoHistoryMenu {
+    pub fn open(&mut self) {
+        self.opened = true;
+    }
+
+    pub fn show(&mut self, ui: &Ui, locale_lang: &LocaleLang, current_tab: &mut HLTASFileTab) {
+        if !self.opened {
+            return;
+        }
+
+        Window::new(locale_lang.get_string_from_id("undo-history"))
+            .opened(&mut self.opened)
+            .size([300.0, 400.0], Condition::FirstUseEver)
+            .position(
+                {
+                    let display_size = ui.io().display_size;
+                    [display_size[0] - 320.0, 40.0]
+                },
+                Condition::FirstUseEver,
+            )
+            .build(ui, || {
+                let undo_redo_handler = &current_tab.undo_redo_handler;
+                let undo_count = undo_redo_handler.undo_stack().len();
+
+                // how many times to undo or redo to get to the clicked state
+                let mut undo_times = 0;
+                let mut redo_times = 0;
+
+                if Selectable::new(format!(
+                    "{}##undo_history_initial",
+                    locale_lang.get_string_from_id("undo-history-initial")
+                ))
+                .selected(undo_count == 0)
+                .build(ui)
+                {
+                    undo_times = undo_count;
+                }
+
+                for (i, undo_point) in undo_redo_handler.undo_stack().iter().enumerate() {
+                    if Selectable::new(format!(
+                        "{}##undo_history_undo_{}",
+                        undo_point.description().to_locale_string(locale_lang),
+                        i
+                    ))
+                    .selected(i + 1 == undo_count)
+                    .build(ui)
+                    {
+                        undo_times = undo_count - (i + 1);
+                    }
+                }
+
+                // states that can be redone are dimmed
+                let text_color_token =
+                    ui.push_style_color(StyleColor::Text, ui.style_color(StyleColor::TextDisabled));
+                for (i, redo_point) in undo_redo_handler.redo_stack().iter().rev().enumerate() {
+                    if Selectable::new(format!(
+                        "{}##undo_history_redo_{}",
+                        redo_point.description().to_locale_string(locale_lang),
+                        i
+                    ))
+                    .build(ui)
+                    {
+                        redo_times = i + 1;
+                    }
+                }
+                text_color_token.pop();
+
+                for _ in 0..undo_times {
+                    current_tab.undo_hltas();
+                }
+                for _ in 0..redo_times {
+                    current_tab.redo_hltas();
+                }
+            });
+    }
+}
@@ -0,0 +1,108 @@
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use hltas::HLTAS;
+use hltas_editor_core::{options::get_save_dir, undo_redo_hltas::UndoRedoHandler};
+use serde::{Deserialize, Serialize};
+
+use crate::helpers::hltas::hltas_to_str;
+
+#[derive(Serialize, Deserialize)]
+struct SavedUndoHistory {
+    /// Hash of the file content the undo history belongs to
+    content_hash: u64,
+    undo_redo_handler: UndoRedoHandler,
+    /// Seconds since the unix epoch, the oldest histories get dropped first
+    #[serde(default)]
+    saved_time: u64,
+}
+
+/// Most undo histories kept, as each one can hold a whole file many times over
+const MAX_HISTORIES: usize = 30;
+
+/// Undo histories of closed files, stored next to the options
+#[derive(Default, Serialize, Deserialize)]
+pub struct SavedUndoHistories {
+    histories: HashMap<PathBuf, SavedUndoHistory>,
+}
+
+impl SavedUndoHistories {
+    pub fn save_path() -> Result<PathBuf, std::io::Error> {
+        Ok(get_save_dir()?.join("undo_history.json"))
+    }
+
+    pub fn load() -> Result<Self, Box<dyn std::error::Error>> {
+        let save_path = Self::save_path()?;
+
+        if !save_path.exists() {
+            return Ok(Self::default());
+        }
+
+        let data = fs::read_to_string(save_path)?;
+        let mut saved_undo_histories: Self = serde_json::from_str(&data)?;
+
+        // files that got deleted or moved won't be opened again
+        saved_undo_histories
+            .histories
+            .retain(|path, _| path.exists());
+        saved_undo_histories.drop_oldest();
+
+        Ok(saved_undo_histories)
+    }
+
+    pub fn save(&self) -> Result<(), std::io::Error> {
+        let data = serde_json::to_string(self).unwrap();
+        fs::write(Self::save_path()?, &data)?;
+        Ok(())
+    }
+
+    pub fn insert(&mut self, path: &Path, hltas: &HLTAS, undo_redo_handler: &UndoRedoHandler) {
+        self.histories.insert(
+            path.to_owned(),
+            SavedUndoHistory {
+                content_hash: content_hash(hltas),
+                undo_redo_handler: undo_redo_handler.to_owned(),
+                saved_time: SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .map_or(0, |duration| duration.as_secs()),
+            },
+        );
+        self.drop_oldest();
+    }
+
+    fn drop_oldest(&mut self) {
+        while self.histories.len() > MAX_HISTORIES {
+            let oldest_path = self
+                .histories
+                .iter()
+                .min_by_key(|(_, saved_undo_history)| saved_undo_history.saved_time)
+                .map(|(path, _)| path.to_owned())
+                .unwrap();
+            self.histories.remove(&oldest_path);
+        }
+    }
+
+    /// Removes the undo history of the path, and returns it if the file didn't change since
+    pub fn take(&mut self, path: &Path, hltas: &HLTAS) -> Option<UndoRedoHandler> {
+        let saved_undo_history = self.histories.remove(path)?;
+
+        if saved_undo_history.content_hash == content_hash(hltas) {
+            Some(saved_undo_history.undo_redo_handler)
+        } else {
+            None
+        }
+    }
+}
+
+/// FNV-1a hash of the hltas as text, which stays the same between builds unlike the std hasher
+fn content_hash(hltas: &HLTAS) -> u64 {
+    hltas_to_str(hltas)
+        .bytes()
+        .fold(0xcbf29ce484222325, |hash, byte| {
+            (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+        })
+}
@@ -0,0 +1,44 @@
+/// Matches `query` as a case insensitive subsequence of `text`
+///
+/// * Returns a score if it matched, higher is better
+/// * Consecutive characters and characters at the start of words score higher
+pub fn fuzzy_match(query: &str, text: &str) -> Option<i32> {
+    let query = query
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .flat_map(char::to_lowercase)
+        .collect::<Vec<_>>();
+    let text = text.chars().collect::<Vec<_>>();
+
+    let mut score = 0;
+    let mut query_index = 0;
+    let mut prev_match_index = None;
+
+    for (i, c) in text.iter().enumerate() {
+        if query_index == query.len() {
+            break;
+        }
+
+        if !c.to_lowercase().eq(std::iter::once(query[query_index])) {
+            continue;
+        }
+
+        score += 1;
+        if prev_match_index.is_some_and(|prev_match_index| prev_match_index + 1 == i) {
+            score += 5;
+        }
+        if i == 0 || !text[i - 1].is_alphanumeric() {
+            score += 3;
+        }
+
+        prev_match_index = Some(i);
+        query_index += 1;
+    }
+
+    if query_index == query.len() {
+        // shorter texts are closer matches
+        Some(score * 100 - text.len() as i32)
+    } else {
+        None
+    }
+}
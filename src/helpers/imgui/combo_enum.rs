@@ -1,8 +1,7 @@
 use hltas::types::{FrameBulk, Line};
+use hltas_editor_core::undo_redo_hltas::UndoRedoHandler;
 use imgui::Ui;
 
-use crate::guis::main::undo_redo_hltas::UndoRedoHandler;
-
 // pub fn show_combo_enum<T: Copy + PartialEq>(
 //     ui: &Ui,
 //     value: &mut T,
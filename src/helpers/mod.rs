@@ -1,3 +1,4 @@
-pub mod hltas;
-pub mod locale;
+pub mod fuzzy;
 pub mod imgui;
+
+pub use hltas_editor_core::helpers::{hltas, locale};
@@ -1,12 +1,24 @@
+use std::{cell::RefCell, rc::Rc};
+
 use guis::main::MainGUI;
+use hltas_editor_core::locale;
 
 mod guis;
 mod helpers;
-mod locale;
+mod mergetool;
 mod support;
 
 fn main() {
+    let args = std::env::args().skip(1).collect::<Vec<_>>();
+    if args.first().map(String::as_str) == Some("mergetool") {
+        std::process::exit(mergetool::run(&args[1..]));
+    }
+
     let system = support::init("HLTAS Editor");
-    let mut main_gui = MainGUI::init();
-    system.main_loop(move |run, ui| main_gui.show(run, ui));
+    let main_gui = Rc::new(RefCell::new(MainGUI::init()));
+    let main_gui_exit = Rc::clone(&main_gui);
+    system.main_loop(
+        move |run, ui| main_gui.borrow_mut().show(run, ui),
+        move || main_gui_exit.borrow_mut().exit(),
+    );
 }
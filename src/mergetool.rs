@@ -0,0 +1,72 @@
+//! Headless three-way merge, usable as a git mergetool
+//!
+//! ```text
+//! git config mergetool.hltas.cmd 'hltas-editor-rs mergetool "$BASE" "$LOCAL" "$REMOTE" "$MERGED"'
+//! git config mergetool.hltas.trustExitCode true
+//! ```
+
+use std::fs;
+
+use hltas::HLTAS;
+
+use crate::helpers::hltas::{
+    hltas_to_str,
+    merge::{Merge, PropertiesMerge},
+};
+
+const USAGE: &str = "usage: hltas-editor-rs mergetool BASE OURS THEIRS MERGED";
+
+fn read_hltas(path: &str) -> Result<HLTAS, String> {
+    let file_content = fs::read_to_string(path).map_err(|err| format!("{}: {}", path, err))?;
+    HLTAS::from_str(&file_content).map_err(|err| format!("{}: {}", path, err))
+}
+
+/// Merges the files and writes the result if there are no conflicts, returns the exit code
+///
+/// * `args` are the arguments after `mergetool`
+pub fn run(args: &[String]) -> i32 {
+    let (base_path, ours_path, theirs_path, merged_path) = match args {
+        [base, ours, theirs, merged] => (base, ours, theirs, merged),
+        _ => {
+            eprintln!("{}", USAGE);
+            return 2;
+        }
+    };
+
+    let files = read_hltas(base_path)
+        .and_then(|base| Ok((base, read_hltas(ours_path)?, read_hltas(theirs_path)?)));
+    let (base, ours, theirs) = match files {
+        Ok(files) => files,
+        Err(err) => {
+            eprintln!("{}", err);
+            return 2;
+        }
+    };
+
+    let merge = Merge::new(&base, &ours, &theirs);
+
+    match merge.result() {
+        Some(hltas) => match fs::write(merged_path, hltas_to_str(&hltas)) {
+            Ok(_) => 0,
+            Err(err) => {
+                eprintln!("{}: {}", merged_path, err);
+                2
+            }
+        },
+        None => {
+            if let PropertiesMerge::Conflict { .. } = merge.properties {
+                eprintln!("conflict: properties");
+            }
+            for conflict in merge.conflicts() {
+                eprintln!(
+                    "conflict: {} line {}, {} line {}",
+                    ours_path,
+                    conflict.ours_index + 1,
+                    theirs_path,
+                    conflict.theirs_index + 1
+                );
+            }
+            1
+        }
+    }
+}
@@ -17,7 +17,6 @@ pub struct System {
     pub imgui: Context,
     pub platform: WinitPlatform,
     pub renderer: Renderer,
-    pub font_size: f32,
 }
 
 pub fn init(title: &str) -> System {
@@ -79,12 +78,15 @@ pub fn init(title: &str) -> System {
         imgui,
         platform,
         renderer,
-        font_size,
     }
 }
 
 impl System {
-    pub fn main_loop<F: FnMut(&mut bool, &mut Ui) + 'static>(self, mut run_ui: F) {
+    pub fn main_loop<F, E>(self, mut run_ui: F, mut on_exit: E)
+    where
+        F: FnMut(&mut bool, &mut Ui) + 'static,
+        E: FnMut() + 'static,
+    {
         let System {
             event_loop,
             display,
@@ -131,6 +133,7 @@ impl System {
                 event: WindowEvent::CloseRequested,
                 ..
             } => *control_flow = ControlFlow::Exit,
+            Event::LoopDestroyed => on_exit(),
             event => {
                 let gl_window = display.gl_window();
                 platform.handle_event(imgui.io_mut(), gl_window.window(), &event);